@@ -126,6 +126,15 @@ where
                 b.iter(|| from_slice::<T>(d).unwrap());
             },
         );
+        // derived `deserialize_reader`, which goes through the generic `Read` impl for slices,
+        // as opposed to slice-specialized `deserialize`, used by `from_slice`
+        group.bench_with_input(
+            BenchmarkId::new("borsh_reader", benchmark_param_display.clone()),
+            borsh_data,
+            |b, d| {
+                b.iter(|| T::deserialize_reader(&mut d.as_slice()).unwrap());
+            },
+        );
         group.bench_with_input(
             BenchmarkId::new("speedy", benchmark_param_display),
             speedy_data,
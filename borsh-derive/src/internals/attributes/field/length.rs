@@ -0,0 +1,234 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    meta::ParseNestedMeta, Expr, ExprPath, GenericArgument, Ident, Path, PathArguments, Type,
+};
+
+use crate::internals::attributes::Symbol;
+
+const LENGTH_WIDTHS: [&str; 4] = ["u8", "u16", "u32", "u64"];
+
+pub(super) fn parse(attr_name: Symbol, meta: &ParseNestedMeta) -> syn::Result<Ident> {
+    let width: Ident = meta.value()?.parse()?;
+    if !LENGTH_WIDTHS.contains(&width.to_string().as_str()) {
+        return Err(syn::Error::new_spanned(
+            width,
+            format!(
+                "`{}` only accepts one of {}",
+                attr_name.0,
+                LENGTH_WIDTHS.map(|width| format!("`{}`", width)).join(", ")
+            ),
+        ));
+    }
+    Ok(width)
+}
+
+/// Length prefix of collections in the field's type.
+pub(super) enum Prefix<'a> {
+    /// `#[borsh(length = ...)]`
+    Width(&'a Ident),
+    /// `#[borsh(varint)]`, which also applies to integers
+    Varint,
+}
+
+/// Computes type from `borsh::length` (or `borsh::varint`) module, which (de)serializes field of type `ty`
+/// with `prefix` length prefix for the collection itself and all of its nested collections,
+/// limiting length of the outermost collection to `max_len`.
+///
+/// If `big_endian` is set, integers and floats (including elements of collections)
+/// are (de)serialized with `borsh::endian::BigEndian`.
+pub(super) fn encoding(
+    ty: &Type,
+    prefix: Option<Prefix>,
+    max_len: Option<&Expr>,
+    big_endian: bool,
+    cratename: &Path,
+) -> syn::Result<Type> {
+    let encoding = nested_encoding(ty, prefix.as_ref(), max_len, big_endian, cratename)
+        .ok_or_else(|| {
+            let msg = match prefix {
+                _ if max_len.is_some() => {
+                    "`max_len` is only supported for fields of `Vec`, `VecDeque`, `LinkedList`, \
+                 `BTreeSet`, `HashSet`, `String`, `BTreeMap`, `HashMap` types \
+                 or `Option` of these"
+                }
+                None if big_endian => {
+                    "`big_endian` is only supported for fields of `u16`, `u32`, `u64`, `u128`, \
+                 `i16`, `i32`, `i64`, `i128`, `f32`, `f64` types, `Vec`, `VecDeque`, `LinkedList`, \
+                 `BTreeSet`, `HashSet`, `BTreeMap`, `HashMap` of these or `Option` of these"
+                }
+                None | Some(Prefix::Width(..)) => {
+                    "`length` is only supported for fields of `Vec`, `VecDeque`, `LinkedList`, \
+                 `BTreeSet`, `HashSet`, `String`, `BTreeMap`, `HashMap` types \
+                 or `Option` of these"
+                }
+                Some(Prefix::Varint) => {
+                    "`varint` is only supported for fields of `u16`, `u32`, `u64`, `u128`, \
+                 `i16`, `i32`, `i64`, `i128`, `Vec`, `VecDeque`, `LinkedList`, \
+                 `BTreeSet`, `HashSet`, `String`, `BTreeMap`, `HashMap` types \
+                 or `Option` of these"
+                }
+            };
+            syn::Error::new_spanned(ty, msg)
+        })?;
+    syn::parse2(encoding)
+}
+
+fn type_args(arguments: &PathArguments) -> Vec<&Type> {
+    match arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Single-byte `u8` and `i8` are the same in either byte order, so they keep their encoding.
+const BIG_ENDIAN_PRIMITIVES: [&str; 10] = [
+    "u16", "u32", "u64", "u128", "i16", "i32", "i64", "i128", "f32", "f64",
+];
+
+fn nested_encoding(
+    ty: &Type,
+    prefix: Option<&Prefix>,
+    max_len: Option<&Expr>,
+    big_endian: bool,
+    cratename: &Path,
+) -> Option<TokenStream2> {
+    let segment = match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => type_path.path.segments.last()?,
+        Type::Group(group) => {
+            return nested_encoding(&group.elem, prefix, max_len, big_endian, cratename)
+        }
+        _ => return None,
+    };
+    let width = match prefix {
+        Some(Prefix::Width(width)) => quote! { #width },
+        Some(Prefix::Varint) => quote! { #cratename::varint::VarU32 },
+        None => quote! { u32 },
+    };
+    let width = match max_len {
+        Some(max_len) => quote! { #cratename::length::MaxLen<#width, { #max_len }> },
+        None => width,
+    };
+    let args = type_args(&segment.arguments);
+    // `max_len` only limits the outermost collection, while `prefix` and `big_endian` apply to all of them
+    let element = |ty: &Type| {
+        if prefix.is_none() && !big_endian {
+            return None;
+        }
+        nested_encoding(ty, prefix, None, big_endian, cratename)
+    };
+    // with only `big_endian` set, collections without big-endian elements keep their encoding
+    let only_big_endian = prefix.is_none() && max_len.is_none();
+    let same = || quote! { #cratename::length::Same };
+    match (segment.ident.to_string().as_str(), args.as_slice()) {
+        ("Vec" | "VecDeque" | "LinkedList" | "BTreeSet", [elem])
+        | ("HashSet", [elem] | [elem, _]) => {
+            let elem = element(elem);
+            if only_big_endian && elem.is_none() {
+                return None;
+            }
+            let elem = elem.unwrap_or_else(same);
+            Some(quote! { #cratename::length::Sequence<#width, #elem> })
+        }
+        ("String", []) if !only_big_endian => Some(quote! { #cratename::length::Sequence<#width> }),
+        ("BTreeMap", [key, value]) | ("HashMap", [key, value] | [key, value, _]) => {
+            let key = element(key);
+            let value = element(value);
+            if only_big_endian && key.is_none() && value.is_none() {
+                return None;
+            }
+            let key = key.unwrap_or_else(same);
+            let value = value.unwrap_or_else(same);
+            Some(quote! { #cratename::length::Map<#width, #key, #value> })
+        }
+        ("Option", [elem]) => {
+            let elem = nested_encoding(elem, prefix, max_len, big_endian, cratename)?;
+            Some(quote! { ::core::option::Option<#elem> })
+        }
+        (integer, []) if matches!(prefix, Some(Prefix::Varint)) && max_len.is_none() => {
+            let varint = match integer {
+                "u16" => "VarU16",
+                "u32" => "VarU32",
+                "u64" => "VarU64",
+                "u128" => "VarU128",
+                "i16" => "VarI16",
+                "i32" => "VarI32",
+                "i64" => "VarI64",
+                "i128" => "VarI128",
+                _ => return None,
+            };
+            let varint = Ident::new(varint, segment.ident.span());
+            Some(quote! { #cratename::varint::#varint })
+        }
+        (primitive, [])
+            if big_endian && max_len.is_none() && BIG_ENDIAN_PRIMITIVES.contains(&primitive) =>
+        {
+            let primitive = &segment.ident;
+            Some(quote! { #cratename::endian::BigEndian<#primitive> })
+        }
+        _ => None,
+    }
+}
+
+/// Whether serialization of `ty` can't depend on byte order, as it contains no multi-byte
+/// integers or floats, e.g. `String` or `Vec<u8>`, so that container-level `big_endian`
+/// may leave such a field as is.
+pub(super) fn is_byte_order_independent(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            let segment = match type_path.path.segments.last() {
+                Some(segment) => segment,
+                None => return false,
+            };
+            match (
+                segment.ident.to_string().as_str(),
+                type_args(&segment.arguments).as_slice(),
+            ) {
+                ("u8" | "i8" | "bool" | "String", []) => true,
+                ("PhantomData", [_]) => true,
+                ("Vec" | "VecDeque" | "LinkedList" | "BTreeSet" | "Option" | "Box", [elem])
+                | ("HashSet", [elem] | [elem, _]) => is_byte_order_independent(elem),
+                ("BTreeMap", [key, value]) | ("HashMap", [key, value] | [key, value, _]) => {
+                    is_byte_order_independent(key) && is_byte_order_independent(value)
+                }
+                _ => false,
+            }
+        }
+        Type::Array(array) => is_byte_order_independent(&array.elem),
+        Type::Tuple(tuple) => tuple.elems.iter().all(is_byte_order_independent),
+        Type::Group(group) => is_byte_order_independent(&group.elem),
+        Type::Paren(paren) => is_byte_order_independent(&paren.elem),
+        _ => false,
+    }
+}
+
+pub(super) fn serialize_as(encoding: &Type, ty: &Type, cratename: &Path) -> ExprPath {
+    syn::parse2(quote! { <#encoding as #cratename::ser::BorshSerializeAs<#ty>>::serialize_as })
+        .unwrap()
+}
+
+pub(super) fn deserialize_as(encoding: &Type, ty: &Type, cratename: &Path) -> ExprPath {
+    syn::parse2(quote! { <#encoding as #cratename::de::BorshDeserializeAs<#ty>>::deserialize_as })
+        .unwrap()
+}
+
+#[cfg(feature = "schema")]
+pub(super) fn schema_declaration(encoding: &Type, ty: &Type, cratename: &Path) -> ExprPath {
+    syn::parse2(quote! { <#encoding as #cratename::schema::BorshSchemaAs<#ty>>::declaration })
+        .unwrap()
+}
+
+#[cfg(feature = "schema")]
+pub(super) fn schema_definitions(encoding: &Type, ty: &Type, cratename: &Path) -> ExprPath {
+    syn::parse2(
+        quote! { <#encoding as #cratename::schema::BorshSchemaAs<#ty>>::collect_definitions },
+    )
+    .unwrap()
+}
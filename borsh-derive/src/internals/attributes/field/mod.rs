@@ -1,33 +1,45 @@
 use std::collections::BTreeMap;
 
 use once_cell::sync::Lazy;
-use syn::{meta::ParseNestedMeta, Attribute, WherePredicate};
+use syn::{meta::ParseNestedMeta, Attribute, Expr, ExprPath, Ident, Path, Type, WherePredicate};
 
-use self::bounds::BOUNDS_FIELD_PARSE_MAP;
+use self::{bounds::BOUNDS_FIELD_PARSE_MAP, skip::SKIP_FIELD_PARSE_MAP};
 
 use super::{
     get_one_attribute,
     parsing::{attr_get_by_symbol_keys, meta_get_by_symbol_keys, parse_lit_into},
-    BoundType, Symbol, BORSH, BOUND, DESERIALIZE_WITH, SERIALIZE_WITH, SKIP,
+    BoundType, Symbol, BIG_ENDIAN, BORSH, BOUND, DEFAULT, DESERIALIZE_WITH, LENGTH, MAX_LEN, PAD,
+    RESERVED, SERIALIZE_WITH, SKIP, VARINT, WITH,
 };
 
 #[cfg(feature = "schema")]
 use {
-    super::schema_keys::{PARAMS, SCHEMA, WITH_FUNCS},
+    super::schema_keys::{AS, PARAMS, SCHEMA, WITH_FUNCS},
     schema::SCHEMA_FIELD_PARSE_MAP,
+    syn::spanned::Spanned,
 };
 
 pub mod bounds;
+mod length;
 #[cfg(feature = "schema")]
 pub mod schema;
+pub mod skip;
 
 enum Variants {
     Bounds(bounds::Bounds),
     SerializeWith(syn::ExprPath),
     DeserializeWith(syn::ExprPath),
-    Skip(()),
+    With(Path),
+    Skip(skip::Skip),
+    Default(Path),
+    Length(Ident),
+    Varint(()),
+    BigEndian(()),
+    MaxLen(Expr),
+    Pad(Expr),
+    Reserved(Expr),
     #[cfg(feature = "schema")]
-    Schema(schema::Attributes),
+    Schema(Box<schema::Attributes>),
 }
 
 type ParseFn = dyn Fn(Symbol, Symbol, &ParseNestedMeta) -> syn::Result<Variants> + Send + Sync;
@@ -52,19 +64,67 @@ static BORSH_FIELD_PARSE_MAP: Lazy<BTreeMap<Symbol, Box<ParseFn>>> = Lazy::new(|
             .map(Variants::DeserializeWith)
     });
 
+    let f_with: Box<ParseFn> = Box::new(|attr_name, meta_item_name, meta| {
+        parse_lit_into::<Path>(attr_name, meta_item_name, meta).map(Variants::With)
+    });
+
     #[cfg(feature = "schema")]
     let f_schema: Box<ParseFn> = Box::new(|_attr_name, _meta_item_name, meta| {
         let map_result = meta_get_by_symbol_keys(SCHEMA, meta, &SCHEMA_FIELD_PARSE_MAP)?;
         let schema_attributes: schema::Attributes = map_result.into();
-        Ok(Variants::Schema(schema_attributes))
+        Ok(Variants::Schema(Box::new(schema_attributes)))
+    });
+
+    let f_skip: Box<ParseFn> = Box::new(|_attr_name, _meta_item_name, meta| {
+        if meta.input.peek(syn::token::Paren) {
+            let map_result = meta_get_by_symbol_keys(SKIP, meta, &SKIP_FIELD_PARSE_MAP)?;
+            let skip_attributes: skip::Skip = map_result.into();
+            Ok(Variants::Skip(skip_attributes))
+        } else {
+            Ok(Variants::Skip(skip::Skip::default()))
+        }
+    });
+
+    let f_default: Box<ParseFn> = Box::new(|attr_name, meta_item_name, meta| {
+        parse_lit_into::<Path>(attr_name, meta_item_name, meta).map(Variants::Default)
+    });
+
+    let f_length: Box<ParseFn> = Box::new(|_attr_name, meta_item_name, meta| {
+        length::parse(meta_item_name, meta).map(Variants::Length)
+    });
+
+    let f_varint: Box<ParseFn> =
+        Box::new(|_attr_name, _meta_item_name, _meta| Ok(Variants::Varint(())));
+
+    let f_big_endian: Box<ParseFn> =
+        Box::new(|_attr_name, _meta_item_name, _meta| Ok(Variants::BigEndian(())));
+
+    let f_max_len: Box<ParseFn> = Box::new(|_attr_name, _meta_item_name, meta| {
+        let max_len: Expr = meta.value()?.parse()?;
+        Ok(Variants::MaxLen(max_len))
     });
 
-    let f_skip: Box<ParseFn> =
-        Box::new(|_attr_name, _meta_item_name, _meta| Ok(Variants::Skip(())));
+    let f_pad: Box<ParseFn> = Box::new(|_attr_name, _meta_item_name, meta| {
+        let len: Expr = meta.value()?.parse()?;
+        Ok(Variants::Pad(len))
+    });
+
+    let f_reserved: Box<ParseFn> = Box::new(|_attr_name, _meta_item_name, meta| {
+        let len: Expr = meta.value()?.parse()?;
+        Ok(Variants::Reserved(len))
+    });
     m.insert(BOUND, f_bounds);
     m.insert(SERIALIZE_WITH, f_serialize_with);
     m.insert(DESERIALIZE_WITH, f_deserialize_with);
+    m.insert(WITH, f_with);
     m.insert(SKIP, f_skip);
+    m.insert(DEFAULT, f_default);
+    m.insert(LENGTH, f_length);
+    m.insert(VARINT, f_varint);
+    m.insert(BIG_ENDIAN, f_big_endian);
+    m.insert(MAX_LEN, f_max_len);
+    m.insert(PAD, f_pad);
+    m.insert(RESERVED, f_reserved);
     #[cfg(feature = "schema")]
     m.insert(SCHEMA, f_schema);
     m
@@ -75,7 +135,24 @@ pub(crate) struct Attributes {
     pub bounds: Option<bounds::Bounds>,
     pub serialize_with: Option<syn::ExprPath>,
     pub deserialize_with: Option<syn::ExprPath>,
+    /// `#[borsh(with = "module")]`
+    pub with: Option<Path>,
     pub skip: bool,
+    /// `#[borsh(skip(default = "..."))]`
+    pub skip_default: Option<Expr>,
+    /// `#[borsh(default = "...")]`
+    pub default: Option<Path>,
+    pub length: Option<Ident>,
+    pub varint: bool,
+    /// `#[borsh(big_endian)]`
+    pub big_endian: bool,
+    /// `#[borsh(big_endian)]` of the container, applicable to this field
+    pub inherited_big_endian: bool,
+    pub max_len: Option<Expr>,
+    /// `#[borsh(pad = ...)]`
+    pub pad: Option<Expr>,
+    /// `#[borsh(reserved = ...)]`
+    pub reserved: Option<Expr>,
     #[cfg(feature = "schema")]
     pub schema: Option<schema::Attributes>,
 }
@@ -85,7 +162,15 @@ impl From<BTreeMap<Symbol, Variants>> for Attributes {
         let bounds = map.remove(&BOUND);
         let serialize_with = map.remove(&SERIALIZE_WITH);
         let deserialize_with = map.remove(&DESERIALIZE_WITH);
+        let with = map.remove(&WITH);
         let skip = map.remove(&SKIP);
+        let default = map.remove(&DEFAULT);
+        let length = map.remove(&LENGTH);
+        let varint = map.remove(&VARINT);
+        let big_endian = map.remove(&BIG_ENDIAN);
+        let max_len = map.remove(&MAX_LEN);
+        let pad = map.remove(&PAD);
+        let reserved = map.remove(&RESERVED);
         let bounds = bounds.map(|variant| match variant {
             Variants::Bounds(bounds) => bounds,
             _ => unreachable!("only one enum variant is expected to correspond to given map key"),
@@ -101,16 +186,56 @@ impl From<BTreeMap<Symbol, Variants>> for Attributes {
             _ => unreachable!("only one enum variant is expected to correspond to given map key"),
         });
 
+        let with = with.map(|variant| match variant {
+            Variants::With(with) => with,
+            _ => unreachable!("only one enum variant is expected to correspond to given map key"),
+        });
+
         let skip = skip.map(|variant| match variant {
             Variants::Skip(skip) => skip,
             _ => unreachable!("only one enum variant is expected to correspond to given map key"),
         });
 
+        let default = default.map(|variant| match variant {
+            Variants::Default(default) => default,
+            _ => unreachable!("only one enum variant is expected to correspond to given map key"),
+        });
+
+        let length = length.map(|variant| match variant {
+            Variants::Length(width) => width,
+            _ => unreachable!("only one enum variant is expected to correspond to given map key"),
+        });
+
+        let varint = varint.map(|variant| match variant {
+            Variants::Varint(varint) => varint,
+            _ => unreachable!("only one enum variant is expected to correspond to given map key"),
+        });
+
+        let big_endian = big_endian.map(|variant| match variant {
+            Variants::BigEndian(big_endian) => big_endian,
+            _ => unreachable!("only one enum variant is expected to correspond to given map key"),
+        });
+
+        let max_len = max_len.map(|variant| match variant {
+            Variants::MaxLen(max_len) => max_len,
+            _ => unreachable!("only one enum variant is expected to correspond to given map key"),
+        });
+
+        let pad = pad.map(|variant| match variant {
+            Variants::Pad(pad) => pad,
+            _ => unreachable!("only one enum variant is expected to correspond to given map key"),
+        });
+
+        let reserved = reserved.map(|variant| match variant {
+            Variants::Reserved(reserved) => reserved,
+            _ => unreachable!("only one enum variant is expected to correspond to given map key"),
+        });
+
         #[cfg(feature = "schema")]
         let schema = {
             let schema = map.remove(&SCHEMA);
             schema.map(|variant| match variant {
-                Variants::Schema(schema) => schema,
+                Variants::Schema(schema) => *schema,
                 _ => {
                     unreachable!("only one enum variant is expected to correspond to given map key")
                 }
@@ -120,7 +245,17 @@ impl From<BTreeMap<Symbol, Variants>> for Attributes {
             bounds,
             serialize_with,
             deserialize_with,
+            with,
             skip: skip.is_some(),
+            skip_default: skip.and_then(|skip| skip.default),
+            default,
+            length,
+            varint: varint.is_some(),
+            big_endian: big_endian.is_some(),
+            inherited_big_endian: false,
+            max_len,
+            pad,
+            reserved,
             #[cfg(feature = "schema")]
             schema,
         }
@@ -146,6 +281,114 @@ impl Attributes {
             ));
         }
 
+        if self.with.is_some()
+            && (self.skip
+                || self.serialize_with.is_some()
+                || self.deserialize_with.is_some()
+                || self.length.is_some()
+                || self.varint
+                || self.max_len.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "`{}` cannot be used at the same time as `{}`, `{}`, `{}`, `{}`, `{}` or `{}`",
+                    WITH.0,
+                    SKIP.0,
+                    SERIALIZE_WITH.0,
+                    DESERIALIZE_WITH.0,
+                    LENGTH.0,
+                    VARINT.0,
+                    MAX_LEN.0
+                ),
+            ));
+        }
+
+        if self.default.is_some() && !self.skip {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "`{}` can only be used together with `{}`",
+                    DEFAULT.0, SKIP.0
+                ),
+            ));
+        }
+
+        if self.default.is_some() && self.skip_default.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "`{}` cannot be used at the same time as `{}({})`",
+                    DEFAULT.0, SKIP.0, DEFAULT.1
+                ),
+            ));
+        }
+
+        if self.length.is_some()
+            && (self.skip || self.serialize_with.is_some() || self.deserialize_with.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "`{}` cannot be used at the same time as `{}`, `{}` or `{}`",
+                    LENGTH.0, SKIP.0, SERIALIZE_WITH.0, DESERIALIZE_WITH.0
+                ),
+            ));
+        }
+
+        if self.varint
+            && (self.length.is_some()
+                || self.skip
+                || self.serialize_with.is_some()
+                || self.deserialize_with.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "`{}` cannot be used at the same time as `{}`, `{}`, `{}` or `{}`",
+                    VARINT.0, LENGTH.0, SKIP.0, SERIALIZE_WITH.0, DESERIALIZE_WITH.0
+                ),
+            ));
+        }
+
+        if self.big_endian
+            && (self.skip
+                || self.serialize_with.is_some()
+                || self.deserialize_with.is_some()
+                || self.with.is_some()
+                || self.varint)
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "`{}` cannot be used at the same time as `{}`, `{}`, `{}`, `{}` or `{}`",
+                    BIG_ENDIAN.0, SKIP.0, SERIALIZE_WITH.0, DESERIALIZE_WITH.0, WITH.0, VARINT.0
+                ),
+            ));
+        }
+
+        if self.max_len.is_some()
+            && (self.skip || self.serialize_with.is_some() || self.deserialize_with.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "`{}` cannot be used at the same time as `{}`, `{}` or `{}`",
+                    MAX_LEN.0, SKIP.0, SERIALIZE_WITH.0, DESERIALIZE_WITH.0
+                ),
+            ));
+        }
+
+        if self.pad.is_some() && self.reserved.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "`{}` cannot be used at the same time as `{}`",
+                    PAD.0, RESERVED.0
+                ),
+            ));
+        }
+
         #[cfg(feature = "schema")]
         self.check_schema(attr)?;
 
@@ -180,6 +423,107 @@ impl Attributes {
         let predicates = self.get_bounds(ty);
         predicates.unwrap_or_default()
     }
+
+    /// expression, which computes value of a skipped field on deserialization,
+    /// if it's overridden by `default` or `skip(default = ...)`
+    pub(crate) fn default_override(&self) -> Option<Expr> {
+        self.skip_default.clone().or_else(|| {
+            self.default
+                .as_ref()
+                .map(|path| syn::parse_quote! { #path() })
+        })
+    }
+
+    /// length of `#[borsh(pad = ...)]` or `#[borsh(reserved = ...)]` zero bytes,
+    /// which precede the field
+    pub(crate) fn padding_len(&self) -> Option<&Expr> {
+        self.pad.as_ref().or(self.reserved.as_ref())
+    }
+
+    /// path of `name` function from `#[borsh(with = "module")]` module
+    fn with_func(&self, name: &str) -> Option<ExprPath> {
+        self.with.as_ref().map(|module| {
+            let name = Ident::new(name, proc_macro2::Span::call_site());
+            syn::parse_quote! { #module::#name }
+        })
+    }
+
+    /// applies `#[borsh(big_endian)]` of the container to the field,
+    /// unless encoding of the field is overridden by other attributes
+    pub(crate) fn inherit_big_endian(mut self, container_big_endian: bool) -> Self {
+        self.inherited_big_endian = container_big_endian
+            && !self.skip
+            && self.serialize_with.is_none()
+            && self.deserialize_with.is_none()
+            && self.with.is_none()
+            && !self.varint;
+        #[cfg(feature = "schema")]
+        if let Some(ref schema) = self.schema {
+            self.inherited_big_endian &= schema.with_funcs.is_none() && schema.as_type.is_none();
+        }
+        self
+    }
+
+    fn length_encoding(&self, ty: &Type, cratename: &Path) -> syn::Result<Option<Type>> {
+        let prefix = if self.varint {
+            Some(length::Prefix::Varint)
+        } else {
+            self.length.as_ref().map(length::Prefix::Width)
+        };
+        let big_endian = self.big_endian || self.inherited_big_endian;
+        if prefix.is_none() && self.max_len.is_none() && !big_endian {
+            return Ok(None);
+        }
+        let encoding = length::encoding(ty, prefix, self.max_len.as_ref(), big_endian, cratename);
+        if self.inherited_big_endian
+            && !self.big_endian
+            && self.length.is_none()
+            && self.max_len.is_none()
+        {
+            // container-level `big_endian` skips fields, which can't contain multi-byte numbers,
+            // and rejects ones, which may contain them, but it can't be applied to
+            return match encoding {
+                Ok(encoding) => Ok(Some(encoding)),
+                Err(_) if length::is_byte_order_independent(ty) => Ok(None),
+                Err(_) => Err(syn::Error::new_spanned(
+                    ty,
+                    "`big_endian` of the container can't be applied to field of this type, \
+                     which may contain multi-byte numbers; put `big_endian` on individual fields \
+                     instead, or override encoding of the field with `with` \
+                     or `serialize_with`/`deserialize_with`",
+                )),
+            };
+        }
+        encoding.map(Some)
+    }
+
+    /// path of function, which serializes field of type `ty`,
+    /// if it's overridden by `serialize_with`, `with`, `length`, `varint`, `big_endian` or `max_len`
+    pub(crate) fn serialize_override(
+        &self,
+        ty: &Type,
+        cratename: &Path,
+    ) -> syn::Result<Option<ExprPath>> {
+        let length = self.length_encoding(ty, cratename)?;
+        Ok(length
+            .map(|encoding| length::serialize_as(&encoding, ty, cratename))
+            .or_else(|| self.serialize_with.clone())
+            .or_else(|| self.with_func("serialize")))
+    }
+
+    /// path of function, which deserializes field of type `ty`,
+    /// if it's overridden by `deserialize_with`, `with`, `length`, `varint`, `big_endian` or `max_len`
+    pub(crate) fn deserialize_override(
+        &self,
+        ty: &Type,
+        cratename: &Path,
+    ) -> syn::Result<Option<ExprPath>> {
+        let length = self.length_encoding(ty, cratename)?;
+        Ok(length
+            .map(|encoding| length::deserialize_as(&encoding, ty, cratename))
+            .or_else(|| self.deserialize_with.clone())
+            .or_else(|| self.with_func("deserialize")))
+    }
 }
 
 #[cfg(feature = "schema")]
@@ -196,6 +540,38 @@ impl Attributes {
                 ));
             }
 
+            if (self.length.is_some() || self.varint || self.max_len.is_some())
+                && schema.with_funcs.is_some()
+            {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    format!(
+                        "`{}`, `{}` or `{}` cannot be used at the same time as `{}({})`",
+                        LENGTH.0, VARINT.0, MAX_LEN.0, SCHEMA.0, WITH_FUNCS.1
+                    ),
+                ));
+            }
+
+            if self.big_endian && schema.with_funcs.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    format!(
+                        "`{}` cannot be used at the same time as `{}({})`",
+                        BIG_ENDIAN.0, SCHEMA.0, WITH_FUNCS.1
+                    ),
+                ));
+            }
+
+            if self.with.is_some() && schema.with_funcs.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    format!(
+                        "`{}` cannot be used at the same time as `{}({})`",
+                        WITH.0, SCHEMA.0, WITH_FUNCS.1
+                    ),
+                ));
+            }
+
             if self.skip && schema.with_funcs.is_some() {
                 return Err(syn::Error::new_spanned(
                     attr,
@@ -205,6 +581,33 @@ impl Attributes {
                     ),
                 ));
             }
+
+            if schema.as_type.is_some() && schema.with_funcs.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    format!(
+                        "`{}({})` cannot be used at the same time as `{}({})`",
+                        SCHEMA.0, AS.1, SCHEMA.0, WITH_FUNCS.1
+                    ),
+                ));
+            }
+
+            if schema.as_type.is_some()
+                && (self.skip
+                    || self.with.is_some()
+                    || self.length.is_some()
+                    || self.varint
+                    || self.big_endian
+                    || self.max_len.is_some())
+            {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    format!(
+                        "`{}({})` cannot be used at the same time as `{}`, `{}`, `{}`, `{}`, `{}` or `{}`",
+                        SCHEMA.0, AS.1, SKIP.0, WITH.0, LENGTH.0, VARINT.0, BIG_ENDIAN.0, MAX_LEN.0
+                    ),
+                ));
+            }
         }
         Ok(())
     }
@@ -218,25 +621,79 @@ impl Attributes {
         true
     }
 
-    pub(crate) fn schema_declaration(&self) -> Option<syn::ExprPath> {
-        self.schema.as_ref().and_then(|schema| {
-            schema
-                .with_funcs
-                .as_ref()
-                .and_then(|with_funcs| with_funcs.declaration.clone())
-        })
+    /// path of `name` function of `BorshSchema` implementation of `#[borsh(schema(as = "Type"))]` proxy type;
+    /// the span of the proxy type makes a missing implementation reported at the attribute
+    fn schema_as_func(&self, name: &str, cratename: &Path) -> Option<syn::ExprPath> {
+        self.schema
+            .as_ref()
+            .and_then(|schema| schema.as_type.as_ref())
+            .map(|as_type| {
+                let name = Ident::new(name, as_type.span());
+                syn::parse_quote_spanned! { as_type.span() =>
+                    <#as_type as #cratename::BorshSchema>::#name
+                }
+            })
     }
 
-    pub(crate) fn schema_definitions(&self) -> Option<syn::ExprPath> {
-        self.schema.as_ref().and_then(|schema| {
-            schema
-                .with_funcs
-                .as_ref()
-                .and_then(|with_funcs| with_funcs.definitions.clone())
-        })
+    pub(crate) fn schema_declaration(
+        &self,
+        ty: &Type,
+        cratename: &Path,
+    ) -> syn::Result<Option<syn::ExprPath>> {
+        if let Some(encoding) = self.length_encoding(ty, cratename)? {
+            return Ok(Some(length::schema_declaration(&encoding, ty, cratename)));
+        }
+        if let Some(path) = self.schema_as_func("declaration", cratename) {
+            return Ok(Some(path));
+        }
+        Ok(self
+            .schema
+            .as_ref()
+            .and_then(|schema| {
+                schema
+                    .with_funcs
+                    .as_ref()
+                    .and_then(|with_funcs| with_funcs.declaration.clone())
+            })
+            .or_else(|| self.with_func("declaration")))
+    }
+
+    pub(crate) fn schema_definitions(
+        &self,
+        ty: &Type,
+        cratename: &Path,
+    ) -> syn::Result<Option<SchemaDefinitions>> {
+        if let Some(encoding) = self.length_encoding(ty, cratename)? {
+            return Ok(Some(SchemaDefinitions::Collect(
+                length::schema_definitions(&encoding, ty, cratename),
+            )));
+        }
+        if let Some(path) = self.schema_as_func("collect_definitions", cratename) {
+            return Ok(Some(SchemaDefinitions::Collect(path)));
+        }
+        Ok(self
+            .schema
+            .as_ref()
+            .and_then(|schema| {
+                schema
+                    .with_funcs
+                    .as_ref()
+                    .and_then(|with_funcs| with_funcs.definitions.clone())
+            })
+            .or_else(|| self.with_func("add_definitions_recursively"))
+            .map(SchemaDefinitions::AddRecursively))
     }
 }
 
+/// Function, which adds definitions of a field to the schema.
+#[cfg(feature = "schema")]
+pub(crate) enum SchemaDefinitions {
+    /// `collect_definitions` of `BorshSchema` or `BorshSchemaAs`, which takes `&mut DefinitionsCollector`.
+    Collect(syn::ExprPath),
+    /// user-provided `add_definitions_recursively`, which takes `&mut BTreeMap`.
+    AddRecursively(syn::ExprPath),
+}
+
 #[cfg(test)]
 mod tests {
     use quote::quote;
@@ -435,6 +892,39 @@ mod tests {
         local_insta_assert_snapshot!(debug_print_tokenizable(attrs.deserialize_with));
     }
     #[test]
+    fn test_with_parsing() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(with = "borsh::with::big_endian::u64")]
+                x: u64,
+                y: String,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let attrs = Attributes::parse(&first_field.attrs).unwrap();
+        local_insta_assert_snapshot!(debug_print_tokenizable(attrs.with_func("serialize")));
+        local_insta_assert_snapshot!(debug_print_tokenizable(attrs.with_func("deserialize")));
+    }
+    #[test]
+    fn test_with_serialize_with_conflict() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(with = "be", serialize_with = "be::serialize")]
+                x: u64,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+    #[test]
     fn test_borsh_skip() {
         let item_struct: ItemStruct = syn::parse2(quote! {
             struct A {
@@ -687,6 +1177,87 @@ mod tests_schema {
         local_insta_assert_debug_snapshot!(err);
     }
 
+    #[test]
+    fn test_schema_as_parsing() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(
+                    serialize_with = "third_party_impl::serialize",
+                    schema(as = "BTreeMap<K, Vec<V>>")
+                )]
+                x: ThirdParty<K, V>,
+                y: String,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let attrs = Attributes::parse(&first_field.attrs).unwrap();
+        let schema = attrs.schema.unwrap();
+
+        local_insta_assert_snapshot!(debug_print_tokenizable(schema.as_type));
+    }
+
+    #[test]
+    fn test_schema_as_with_funcs_conflict() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(schema(
+                    as = "u64",
+                    with_funcs(declaration = "be::declaration", definitions = "be::add_definitions_recursively")
+                ))]
+                x: u64,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn test_schema_as_length_conflict() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(length = u8, schema(as = "Vec<u8>"))]
+                x: Vec<u8>,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn test_with_schema_with_funcs_conflict() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(
+                    with = "be",
+                    schema(with_funcs(declaration = "be::declaration", definitions = "be::add_definitions_recursively"))
+                )]
+                x: u64,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
     #[test]
     fn test_root_error() {
         let item_struct: ItemStruct = syn::parse2(quote! {
@@ -727,4 +1298,184 @@ mod tests_schema {
         };
         local_insta_assert_debug_snapshot!(err);
     }
+
+    #[test]
+    fn test_length_wrong_width() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(length = u128)]
+                x: Vec<u8>,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn test_length_skip_conflict() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(skip, length = u8)]
+                x: Vec<u8>,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn test_varint_length_conflict() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(varint, length = u8)]
+                x: Vec<u8>,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn test_big_endian_varint_conflict() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(big_endian, varint)]
+                x: u64,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn test_pad_reserved_conflict() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(pad = 2, reserved = 2)]
+                x: u64,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn test_max_len_skip_conflict() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(max_len = 10, skip)]
+                x: Vec<u8>,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn test_default_without_skip() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(default = "new_handle")]
+                x: Handle,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn test_default_not_string() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(skip, default = new_handle)]
+                x: Handle,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn test_default_twice() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(skip(default = "Handle::new()"), default = "new_handle")]
+                x: Handle,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn test_skip_wrong_key() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(skip(value = "Handle::new()"))]
+                x: Handle,
+            }
+        })
+        .unwrap();
+
+        let first_field = &item_struct.fields.into_iter().collect::<Vec<_>>()[0];
+        let err = match Attributes::parse(&first_field.attrs) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
 }
@@ -1,8 +1,8 @@
 use std::collections::BTreeMap;
 
 use crate::internals::attributes::{
-    parsing::{meta_get_by_symbol_keys, parse_lit_into_vec},
-    schema_keys::{DECLARATION, DEFINITIONS, PARAMS, WITH_FUNCS},
+    parsing::{meta_get_by_symbol_keys, parse_lit_into, parse_lit_into_vec},
+    schema_keys::{AS, DECLARATION, DEFINITIONS, PARAMS, WITH_FUNCS},
     Symbol,
 };
 use once_cell::sync::Lazy;
@@ -20,6 +20,7 @@ pub mod with_funcs;
 pub enum Variants {
     Params(Vec<ParameterOverride>),
     WithFuncs(WithFuncs),
+    As(Type),
 }
 
 type ParseFn = dyn Fn(Symbol, Symbol, &ParseNestedMeta) -> syn::Result<Variants> + Send + Sync;
@@ -49,8 +50,13 @@ pub static SCHEMA_FIELD_PARSE_MAP: Lazy<BTreeMap<Symbol, Box<ParseFn>>> = Lazy::
         }
         Ok(Variants::WithFuncs(with_funcs))
     });
+
+    let f_as: Box<ParseFn> = Box::new(|attr_name, meta_item_name, meta| {
+        parse_lit_into::<Type>(attr_name, meta_item_name, meta).map(Variants::As)
+    });
     m.insert(PARAMS, f_params);
     m.insert(WITH_FUNCS, f_with_funcs);
+    m.insert(AS, f_as);
     m
 });
 
@@ -87,6 +93,8 @@ impl ToTokens for ParameterOverride {
 pub(crate) struct Attributes {
     pub params: Option<Vec<ParameterOverride>>,
     pub with_funcs: Option<WithFuncs>,
+    /// `schema(as = "Type")`, proxy type, which schema is used for the field
+    pub as_type: Option<Type>,
 }
 
 impl From<BTreeMap<Symbol, Variants>> for Attributes {
@@ -102,6 +110,16 @@ impl From<BTreeMap<Symbol, Variants>> for Attributes {
             Variants::WithFuncs(with_funcs) => with_funcs,
             _ => unreachable!("only one enum variant is expected to correspond to given map key"),
         });
-        Self { params, with_funcs }
+
+        let as_type = map.remove(&AS);
+        let as_type = as_type.map(|variant| match variant {
+            Variants::As(as_type) => as_type,
+            _ => unreachable!("only one enum variant is expected to correspond to given map key"),
+        });
+        Self {
+            params,
+            with_funcs,
+            as_type,
+        }
     }
 }
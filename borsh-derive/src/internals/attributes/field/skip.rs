@@ -0,0 +1,38 @@
+use std::collections::BTreeMap;
+
+use syn::{meta::ParseNestedMeta, Expr};
+
+use crate::internals::attributes::{parsing::parse_lit_into, Symbol, DEFAULT};
+use once_cell::sync::Lazy;
+
+pub enum Variants {
+    Default(Expr),
+}
+
+type ParseFn = dyn Fn(Symbol, Symbol, &ParseNestedMeta) -> syn::Result<Variants> + Send + Sync;
+
+pub static SKIP_FIELD_PARSE_MAP: Lazy<BTreeMap<Symbol, Box<ParseFn>>> = Lazy::new(|| {
+    let mut m = BTreeMap::new();
+    // assigning closure `let f = |args| {...};` and boxing closure `let f: Box<ParseFn> = Box::new(f);`
+    // on 2 separate lines doesn't work
+    let f_default: Box<ParseFn> = Box::new(|attr_name, meta_item_name, meta| {
+        parse_lit_into::<Expr>(attr_name, meta_item_name, meta).map(Variants::Default)
+    });
+    m.insert(DEFAULT, f_default);
+    m
+});
+
+#[derive(Default, Clone)]
+pub struct Skip {
+    pub default: Option<Expr>,
+}
+
+impl From<BTreeMap<Symbol, Variants>> for Skip {
+    fn from(mut map: BTreeMap<Symbol, Variants>) -> Self {
+        let default = map.remove(&DEFAULT);
+        let default = default.map(|variant| match variant {
+            Variants::Default(default) => default,
+        });
+        Self { default }
+    }
+}
@@ -1,39 +1,150 @@
-use crate::internals::attributes::{BORSH, CRATE, INIT, USE_DISCRIMINANT};
+#[cfg(feature = "schema")]
+use crate::internals::attributes::schema_keys::{NAME, SCHEMA};
+use crate::internals::attributes::{
+    BIG_ENDIAN, BORSH, CONTEXT, CRATE, DISCRIMINATOR, INIT, PAD_AFTER, REMOTE, RESERVED_AFTER,
+    TAG_CONSTS, USE_DISCRIMINANT,
+};
 use quote::ToTokens;
-use syn::{spanned::Spanned, Attribute, DeriveInput, Error, Expr, ItemEnum, Path};
+use syn::{
+    meta::ParseNestedMeta, spanned::Spanned, Attribute, DeriveInput, Error, Expr, Ident, ItemEnum,
+    Path, Type,
+};
 
 use super::{get_one_attribute, parsing};
 
-pub fn check_attributes(derive_input: &DeriveInput) -> Result<(), Error> {
-    let borsh = get_one_attribute(&derive_input.attrs)?;
+mod discriminator;
+
+/// `#[borsh(...)]` attribute of an item, parsed once, which the `contains_*` helpers read from.
+#[derive(Default)]
+pub(crate) struct Attributes {
+    use_discriminant: Option<bool>,
+    init: Option<Path>,
+    crate_: Option<Path>,
+    remote: Option<Path>,
+    big_endian: bool,
+    tag_consts: bool,
+    trailing_padding: Option<TrailingPadding>,
+    /// number of `pad_after` and `reserved_after` keys, at most one of which is allowed
+    trailing_padding_count: usize,
+    /// `Some(None)` for `#[borsh(discriminator)]`, which is derived from the name
+    discriminator: Option<Option<Vec<u8>>>,
+    context: Option<Type>,
+    #[cfg(feature = "schema")]
+    schema_name: Option<String>,
+}
 
-    if let Some(attr) = borsh {
+impl Attributes {
+    pub(crate) fn parse(attrs: &[Attribute]) -> Result<Self, Error> {
+        let mut res = Self::default();
+        let attr = match get_one_attribute(attrs)? {
+            Some(attr) => attr,
+            None => return Ok(res),
+        };
         attr.parse_nested_meta(|meta| {
-            if meta.path != USE_DISCRIMINANT && meta.path != INIT && meta.path != CRATE {
+            if meta.path == USE_DISCRIMINANT {
+                let value_expr: Expr = meta.value()?.parse()?;
+                let value = value_expr.to_token_stream().to_string();
+                res.use_discriminant = match value.as_str() {
+                    "true" => Some(true),
+                    "false" => Some(false),
+                    _ => {
+                        return Err(syn::Error::new(
+                            value_expr.span(),
+                            "`use_discriminant` accepts only `true` or `false`",
+                        ));
+                    }
+                };
+            } else if meta.path == INIT {
+                res.init = Some(meta.value()?.parse()?);
+            } else if meta.path == CRATE {
+                res.crate_ = Some(parsing::parse_lit_into(BORSH, CRATE, &meta)?);
+            } else if meta.path == REMOTE {
+                res.remote = Some(parsing::parse_lit_into(BORSH, REMOTE, &meta)?);
+            } else if meta.path == BIG_ENDIAN {
+                res.big_endian = true;
+            } else if meta.path == TAG_CONSTS {
+                res.tag_consts = true;
+            } else if meta.path == PAD_AFTER || meta.path == RESERVED_AFTER {
+                res.trailing_padding = Some(TrailingPadding {
+                    len: meta.value()?.parse()?,
+                    reserved: meta.path == RESERVED_AFTER,
+                });
+                res.trailing_padding_count += 1;
+            } else if meta.path == DISCRIMINATOR {
+                res.discriminator = Some(if meta.input.peek(syn::Token![=]) {
+                    Some(discriminator::parse(&meta)?)
+                } else {
+                    None
+                });
+            } else if meta.path == CONTEXT {
+                res.context = Some(meta.value()?.parse()?);
+            } else if is_schema(&meta) {
+                #[cfg(feature = "schema")]
+                {
+                    res.schema_name = parse_schema(&meta)?;
+                }
+            } else {
                 return Err(syn::Error::new(
                     meta.path.span(),
-                    "`crate`, `use_discriminant` or `init` are the only supported attributes for `borsh`",
+                    "`crate`, `use_discriminant`, `init`, `remote`, `big_endian`, `discriminator`, `context`, `tag_consts`, `pad_after`, `reserved_after` or `schema` are the only supported attributes for `borsh`",
                 ));
             }
-            if meta.path == USE_DISCRIMINANT {
-                let _expr: Expr = meta.value()?.parse()?;
-                if let syn::Data::Struct(ref _data) = derive_input.data {
-                    return Err(syn::Error::new(
-                        derive_input.ident.span(),
-                        "borsh(use_discriminant=<bool>) does not support structs",
-                    ));
-                }
-            } else if meta.path == INIT || meta.path == CRATE {
-                let _expr: Expr = meta.value()?.parse()?;
-            }
-
             Ok(())
         })?;
+        Ok(res)
+    }
+}
+
+pub fn check_attributes(derive_input: &DeriveInput) -> Result<(), Error> {
+    let attributes = Attributes::parse(&derive_input.attrs)?;
+    let unsupported = |message: &str| Err(syn::Error::new(derive_input.ident.span(), message));
+    let is_union = matches!(derive_input.data, syn::Data::Union(_));
+
+    if attributes.use_discriminant.is_some() {
+        if let syn::Data::Struct(ref _data) = derive_input.data {
+            return unsupported("borsh(use_discriminant=<bool>) does not support structs");
+        }
+        if is_union {
+            return unsupported("borsh(use_discriminant=<bool>) does not support unions");
+        }
+    }
+    if attributes.remote.is_some() && is_union {
+        return unsupported("borsh(remote = ...) does not support unions");
+    }
+    if attributes.big_endian && is_union {
+        return unsupported("borsh(big_endian) does not support unions");
+    }
+    if attributes.discriminator.is_some() && is_union {
+        return unsupported("borsh(discriminator) does not support unions");
+    }
+    if attributes.context.is_some() && is_union {
+        return unsupported("borsh(context = ...) does not support unions");
+    }
+    if attributes.tag_consts && !matches!(derive_input.data, syn::Data::Enum(_)) {
+        return unsupported("borsh(tag_consts) only supports enums");
+    }
+    if attributes.trailing_padding.is_some() && !matches!(derive_input.data, syn::Data::Struct(_)) {
+        return unsupported(
+            "borsh(pad_after = ...) and borsh(reserved_after = ...) only support structs",
+        );
+    }
+    if attributes.remote.is_some() && attributes.context.is_some() {
+        return unsupported(
+            "borsh(context = ...) cannot be used at the same time as borsh(remote = ...)",
+        );
+    }
+    if attributes.trailing_padding_count > 1 {
+        return unsupported(
+            "only one of borsh(pad_after = ...) and borsh(reserved_after = ...) can be specified",
+        );
     }
     Ok(())
 }
 
-pub(crate) fn contains_use_discriminant(input: &ItemEnum) -> Result<bool, syn::Error> {
+pub(crate) fn contains_use_discriminant(
+    input: &ItemEnum,
+    attributes: &Attributes,
+) -> Result<bool, syn::Error> {
     if input.variants.len() > 256 {
         return Err(syn::Error::new(
             input.span(),
@@ -41,88 +152,132 @@ pub(crate) fn contains_use_discriminant(input: &ItemEnum) -> Result<bool, syn::E
         ));
     }
 
-    let attrs = &input.attrs;
-    let mut use_discriminant = None;
-    let attr = attrs.iter().find(|attr| attr.path() == BORSH);
-    if let Some(attr) = attr {
-        attr.parse_nested_meta(|meta| {
-            if meta.path == USE_DISCRIMINANT {
-                let value_expr: Expr = meta.value()?.parse()?;
-                let value = value_expr.to_token_stream().to_string();
-                match value.as_str() {
-                    "true" => {
-                        use_discriminant = Some(true);
-                    }
-                    "false" => use_discriminant = Some(false),
-                    _ => {
-                        return Err(syn::Error::new(
-                            value_expr.span(),
-                            "`use_discriminant` accepts only `true` or `false`",
-                        ));
-                    }
-                };
-            } else if meta.path == INIT || meta.path == CRATE {
-                let _value_expr: Expr = meta.value()?.parse()?;
-            }
-            Ok(())
-        })?;
-    }
     let has_explicit_discriminants = input
         .variants
         .iter()
         .any(|variant| variant.discriminant.is_some());
-    if has_explicit_discriminants && use_discriminant.is_none() {
+    if has_explicit_discriminants && attributes.use_discriminant.is_none() {
         return Err(syn::Error::new(
                 input.ident.span(),
                 "You have to specify `#[borsh(use_discriminant=true)]` or `#[borsh(use_discriminant=false)]` for all enums with explicit discriminant",
             ));
     }
-    Ok(use_discriminant.unwrap_or(false))
+    Ok(attributes.use_discriminant.unwrap_or(false))
 }
 
-pub(crate) fn contains_initialize_with(attrs: &[Attribute]) -> Result<Option<Path>, Error> {
-    let mut res = None;
-    let attr = attrs.iter().find(|attr| attr.path() == BORSH);
-    if let Some(attr) = attr {
-        attr.parse_nested_meta(|meta| {
-            if meta.path == INIT {
-                let value_expr: Path = meta.value()?.parse()?;
-                res = Some(value_expr);
-            } else if meta.path == USE_DISCRIMINANT || meta.path == CRATE {
-                let _value_expr: Expr = meta.value()?.parse()?;
-            }
+pub(crate) fn contains_initialize_with(attributes: &Attributes) -> Option<Path> {
+    attributes.init.clone()
+}
 
-            Ok(())
-        })?;
-    }
+pub(crate) fn get_crate(attributes: &Attributes) -> Option<Path> {
+    attributes.crate_.clone()
+}
 
-    Ok(res)
+/// Path to the type from another crate, which the annotated item mirrors,
+/// if it's annotated with `#[borsh(remote = "...")]`.
+pub(crate) fn contains_remote(attributes: &Attributes) -> Option<Path> {
+    attributes.remote.clone()
 }
 
-pub(crate) fn get_crate(attrs: &[Attribute]) -> Result<Option<Path>, Error> {
-    let mut res = None;
-    let attr = attrs.iter().find(|attr| attr.path() == BORSH);
-    if let Some(attr) = attr {
-        attr.parse_nested_meta(|meta| {
-            if meta.path == CRATE {
-                let value_expr: Path = parsing::parse_lit_into(BORSH, CRATE, &meta)?;
-                res = Some(value_expr);
-            } else if meta.path == USE_DISCRIMINANT || meta.path == INIT {
-                let _value_expr: Expr = meta.value()?.parse()?;
-            }
+/// Whether the item is annotated with `#[borsh(big_endian)]`, which applies
+/// to all of its fields of multi-byte integer and float types (and collections of these).
+pub(crate) fn contains_big_endian(attributes: &Attributes) -> bool {
+    attributes.big_endian
+}
 
-            Ok(())
-        })?;
+/// Whether the enum is annotated with `#[borsh(tag_consts)]`, which adds
+/// an associated constant with the tag of each variant to the enum.
+pub(crate) fn contains_tag_consts(attributes: &Attributes) -> bool {
+    attributes.tag_consts
+}
+
+/// Padding after the last field of the struct, if it's annotated with
+/// `#[borsh(pad_after = ...)]` or `#[borsh(reserved_after = ...)]`.
+#[derive(Clone)]
+pub(crate) struct TrailingPadding {
+    /// number of zero bytes
+    pub len: Expr,
+    /// whether the bytes are reserved, i.e. have to be zero on deserialization
+    pub reserved: bool,
+}
+
+pub(crate) fn contains_trailing_padding(attributes: &Attributes) -> Option<TrailingPadding> {
+    attributes.trailing_padding.clone()
+}
+
+/// Bytes of the discriminator, if the item is annotated with `#[borsh(discriminator)]`
+/// (default one, derived from the name of `ident`, or the remote type's name)
+/// or `#[borsh(discriminator = [...])]`.
+pub(crate) fn contains_discriminator(attributes: &Attributes, ident: &Ident) -> Option<Vec<u8>> {
+    let bytes = attributes.discriminator.as_ref()?;
+    Some(match bytes {
+        Some(bytes) => bytes.clone(),
+        None => {
+            let remote = attributes.remote.as_ref();
+            let name = match remote.and_then(|remote| remote.segments.last()) {
+                Some(segment) => segment.ident.to_string(),
+                None => ident.to_string(),
+            };
+            discriminator::default(&name)
+        }
+    })
+}
+
+/// Type of the context, if the item is annotated with `#[borsh(context = Type)]`,
+/// in which case `BorshDeserializeWith<Type>` is derived instead of `BorshDeserialize`.
+pub(crate) fn contains_context(attributes: &Attributes) -> Option<Type> {
+    attributes.context.clone()
+}
+
+/// Whether the nested meta is `schema(...)`, which is supported only with `schema` feature.
+fn is_schema(meta: &ParseNestedMeta) -> bool {
+    #[cfg(feature = "schema")]
+    {
+        meta.path == SCHEMA
+    }
+    #[cfg(not(feature = "schema"))]
+    {
+        let _ = meta;
+        false
     }
+}
 
+/// Parses `schema(name = "...")` nested meta, returning the name.
+#[cfg(feature = "schema")]
+fn parse_schema(meta: &ParseNestedMeta) -> Result<Option<String>, Error> {
+    let mut res = None;
+    meta.parse_nested_meta(|meta| {
+        if meta.path != NAME {
+            return Err(syn::Error::new(
+                meta.path.span(),
+                "`name` is the only supported attribute for `schema` on items",
+            ));
+        }
+        let name = parsing::get_lit_str2(SCHEMA, NAME, &meta)?;
+        if name.value().is_empty() {
+            return Err(syn::Error::new(
+                name.span(),
+                "`name` of `schema` can't be empty",
+            ));
+        }
+        res = Some(name.value());
+        Ok(())
+    })?;
     Ok(res)
 }
 
+/// Name of the item in schema, if it's annotated with `#[borsh(schema(name = "..."))]`,
+/// which is used instead of the item's name (or the remote type's name) in its declaration.
+#[cfg(feature = "schema")]
+pub(crate) fn contains_schema_name(attributes: &Attributes) -> Option<String> {
+    attributes.schema_name.clone()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::internals::test_helpers::local_insta_assert_debug_snapshot;
     use quote::{quote, ToTokens};
-    use syn::ItemEnum;
+    use syn::{ItemEnum, ItemStruct};
 
     use super::*;
     #[test]
@@ -136,7 +291,8 @@ mod tests {
             }
         })
         .unwrap();
-        let actual = contains_use_discriminant(&item_enum);
+        let actual = Attributes::parse(&item_enum.attrs)
+            .and_then(|attributes| contains_use_discriminant(&item_enum, &attributes));
         assert!(!actual.unwrap());
     }
 
@@ -151,7 +307,8 @@ mod tests {
             }
         })
         .unwrap();
-        let actual = contains_use_discriminant(&item_enum);
+        let actual = Attributes::parse(&item_enum.attrs)
+            .and_then(|attributes| contains_use_discriminant(&item_enum, &attributes));
         assert!(actual.unwrap());
     }
 
@@ -166,7 +323,8 @@ mod tests {
             }
         })
         .unwrap();
-        let actual = contains_use_discriminant(&item_enum);
+        let actual = Attributes::parse(&item_enum.attrs)
+            .and_then(|attributes| contains_use_discriminant(&item_enum, &attributes));
         let err = match actual {
             Ok(..) => unreachable!("expecting error here"),
             Err(err) => err,
@@ -188,6 +346,274 @@ mod tests {
         local_insta_assert_debug_snapshot!(actual.unwrap_err());
     }
     #[test]
+    fn test_check_attrs_use_discriminant_on_union() {
+        let item_union: DeriveInput = syn::parse2(quote! {
+            #[derive(BorshDeserialize)]
+            #[borsh(use_discriminant = false)]
+            #[repr(C)]
+            union AWithUseDiscriminantFalse {
+                x: u32,
+                y: f32,
+            }
+        })
+        .unwrap();
+        let actual = check_attributes(&item_union);
+        local_insta_assert_debug_snapshot!(actual.unwrap_err());
+    }
+    #[test]
+    fn test_check_attrs_remote_on_union() {
+        let item_union: DeriveInput = syn::parse2(quote! {
+            #[derive(BorshSerialize)]
+            #[borsh(remote = "other::Word")]
+            #[repr(C)]
+            union WordDef {
+                x: u32,
+                y: f32,
+            }
+        })
+        .unwrap();
+        let actual = check_attributes(&item_union);
+        local_insta_assert_debug_snapshot!(actual.unwrap_err());
+    }
+    #[test]
+    fn test_check_attrs_big_endian_on_union() {
+        let item_union: DeriveInput = syn::parse2(quote! {
+            #[derive(BorshSerialize)]
+            #[borsh(big_endian)]
+            #[repr(C)]
+            union Word {
+                x: u32,
+                y: f32,
+            }
+        })
+        .unwrap();
+        let actual = check_attributes(&item_union);
+        local_insta_assert_debug_snapshot!(actual.unwrap_err());
+    }
+    #[test]
+    fn test_contains_remote() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(crate = "borsh", remote = "other::Config", init = init)]
+            struct ConfigDef {
+                x: u8,
+            }
+        })
+        .unwrap();
+        let actual = contains_remote(&Attributes::parse(&item_struct.attrs).unwrap());
+        assert_eq!(actual.to_token_stream().to_string(), "other :: Config");
+        assert!(
+            contains_initialize_with(&Attributes::parse(&item_struct.attrs).unwrap()).is_some()
+        );
+    }
+    #[test]
+    fn test_contains_big_endian() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(big_endian, crate = "borsh", init = init)]
+            struct Header {
+                x: u32,
+            }
+        })
+        .unwrap();
+        assert!(contains_big_endian(
+            &Attributes::parse(&item_struct.attrs).unwrap()
+        ));
+        assert!(
+            contains_initialize_with(&Attributes::parse(&item_struct.attrs).unwrap()).is_some()
+        );
+        assert!(get_crate(&Attributes::parse(&item_struct.attrs).unwrap()).is_some());
+    }
+    #[test]
+    fn test_check_attrs_discriminator_on_union() {
+        let item_union: DeriveInput = syn::parse2(quote! {
+            #[derive(BorshSerialize)]
+            #[borsh(discriminator)]
+            #[repr(C)]
+            union Word {
+                x: u32,
+                y: f32,
+            }
+        })
+        .unwrap();
+        let actual = check_attributes(&item_union);
+        local_insta_assert_debug_snapshot!(actual.unwrap_err());
+    }
+    #[test]
+    fn test_check_attrs_context_on_union() {
+        let item_union: DeriveInput = syn::parse2(quote! {
+            #[derive(BorshDeserialize)]
+            #[borsh(context = Interner)]
+            #[repr(C)]
+            union Word {
+                x: u32,
+                y: f32,
+            }
+        })
+        .unwrap();
+        let actual = check_attributes(&item_union);
+        local_insta_assert_debug_snapshot!(actual.unwrap_err());
+    }
+    #[test]
+    fn test_check_attrs_context_with_remote() {
+        let item_struct: DeriveInput = syn::parse2(quote! {
+            #[derive(BorshDeserialize)]
+            #[borsh(remote = "other::Config", context = Interner)]
+            struct ConfigDef {
+                x: u8,
+            }
+        })
+        .unwrap();
+        let actual = check_attributes(&item_struct);
+        local_insta_assert_debug_snapshot!(actual.unwrap_err());
+    }
+    #[test]
+    fn test_check_attrs_pad_after_on_enum() {
+        let item_enum: DeriveInput = syn::parse2(quote! {
+            #[derive(BorshSerialize)]
+            #[borsh(pad_after = 4)]
+            enum A {
+                X,
+                Y,
+            }
+        })
+        .unwrap();
+        let actual = check_attributes(&item_enum);
+        local_insta_assert_debug_snapshot!(actual.unwrap_err());
+    }
+    #[test]
+    fn test_check_attrs_pad_after_with_reserved_after() {
+        let item_struct: DeriveInput = syn::parse2(quote! {
+            #[derive(BorshSerialize)]
+            #[borsh(pad_after = 4, reserved_after = 4)]
+            struct A {
+                x: u8,
+            }
+        })
+        .unwrap();
+        let actual = check_attributes(&item_struct);
+        local_insta_assert_debug_snapshot!(actual.unwrap_err());
+    }
+    #[test]
+    fn test_contains_context() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(context = Vec<u8>, crate = "borsh", init = init, discriminator)]
+            struct Config {
+                x: u8,
+            }
+        })
+        .unwrap();
+        let actual = contains_context(&Attributes::parse(&item_struct.attrs).unwrap());
+        assert_eq!(actual.to_token_stream().to_string(), "Vec < u8 >");
+        assert!(
+            contains_initialize_with(&Attributes::parse(&item_struct.attrs).unwrap()).is_some()
+        );
+        assert!(get_crate(&Attributes::parse(&item_struct.attrs).unwrap()).is_some());
+        assert!(contains_discriminator(
+            &Attributes::parse(&item_struct.attrs).unwrap(),
+            &item_struct.ident
+        )
+        .is_some());
+    }
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_contains_schema_name() {
+        let item_struct: DeriveInput = syn::parse2(quote! {
+            #[borsh(schema(name = "v1::Config"), init = init, crate = "borsh", big_endian)]
+            struct Config {
+                x: u8,
+            }
+        })
+        .unwrap();
+        check_attributes(&item_struct).unwrap();
+        let actual = contains_schema_name(&Attributes::parse(&item_struct.attrs).unwrap());
+        assert_eq!(actual.as_deref(), Some("v1::Config"));
+        assert!(
+            contains_initialize_with(&Attributes::parse(&item_struct.attrs).unwrap()).is_some()
+        );
+        assert!(get_crate(&Attributes::parse(&item_struct.attrs).unwrap()).is_some());
+        assert!(contains_big_endian(
+            &Attributes::parse(&item_struct.attrs).unwrap()
+        ));
+        assert!(contains_remote(&Attributes::parse(&item_struct.attrs).unwrap()).is_none());
+    }
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_check_attrs_schema_unknown_key() {
+        let item_struct: DeriveInput = syn::parse2(quote! {
+            #[derive(BorshSchema)]
+            #[borsh(schema(params = "T => <T as Trait>::Assoc"))]
+            struct Config<T> {
+                x: T,
+            }
+        })
+        .unwrap();
+        let actual = check_attributes(&item_struct);
+        local_insta_assert_debug_snapshot!(actual.unwrap_err());
+    }
+    #[test]
+    fn test_check_attrs_discriminator_wrong_format() {
+        let item_struct: DeriveInput = syn::parse2(quote! {
+            #[derive(BorshSerialize)]
+            #[borsh(discriminator = [1, 256])]
+            struct Counter {
+                value: u8,
+            }
+        })
+        .unwrap();
+        let actual = check_attributes(&item_struct);
+        local_insta_assert_debug_snapshot!(actual.unwrap_err());
+    }
+    #[test]
+    fn test_contains_discriminator() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(crate = "borsh", discriminator, init = init)]
+            struct Counter {
+                value: u8,
+            }
+        })
+        .unwrap();
+        assert_eq!(
+            contains_discriminator(
+                &Attributes::parse(&item_struct.attrs).unwrap(),
+                &item_struct.ident
+            ),
+            Some(vec![255, 176, 4, 245, 188, 253, 124, 25])
+        );
+        assert!(
+            contains_initialize_with(&Attributes::parse(&item_struct.attrs).unwrap()).is_some()
+        );
+
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(discriminator = [0xca, 0xfe], remote = "other::Counter")]
+            struct CounterDef {
+                value: u8,
+            }
+        })
+        .unwrap();
+        assert_eq!(
+            contains_discriminator(
+                &Attributes::parse(&item_struct.attrs).unwrap(),
+                &item_struct.ident
+            ),
+            Some(vec![0xca, 0xfe])
+        );
+        assert!(contains_remote(&Attributes::parse(&item_struct.attrs).unwrap()).is_some());
+
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(discriminator, remote = "other::Counter")]
+            struct CounterDef {
+                value: u8,
+            }
+        })
+        .unwrap();
+        assert_eq!(
+            contains_discriminator(
+                &Attributes::parse(&item_struct.attrs).unwrap(),
+                &item_struct.ident
+            ),
+            Some(vec![255, 176, 4, 245, 188, 253, 124, 25])
+        );
+    }
+    #[test]
     fn test_check_attrs_borsh_skip_on_whole_item() {
         let item_enum: DeriveInput = syn::parse2(quote! {
             #[derive(BorshDeserialize, Debug)]
@@ -311,7 +737,7 @@ mod tests {
         })
         .unwrap();
 
-        let actual = contains_initialize_with(&item_struct.attrs);
+        let actual = contains_initialize_with(&Attributes::parse(&item_struct.attrs).unwrap());
         assert_eq!(
             actual.unwrap().to_token_stream().to_string(),
             "initialization_method"
@@ -329,7 +755,7 @@ mod tests {
         })
         .unwrap();
 
-        let actual = contains_initialize_with(&item_struct.attrs);
+        let actual = Attributes::parse(&item_struct.attrs);
         let err = match actual {
             Ok(..) => unreachable!("expecting error here"),
             Err(err) => err,
@@ -350,12 +776,13 @@ mod tests {
         })
         .unwrap();
 
-        let actual = contains_initialize_with(&item_struct.attrs);
+        let actual = contains_initialize_with(&Attributes::parse(&item_struct.attrs).unwrap());
         assert_eq!(
             actual.unwrap().to_token_stream().to_string(),
             "initialization_method"
         );
-        let actual = contains_use_discriminant(&item_struct);
+        let actual = Attributes::parse(&item_struct.attrs)
+            .and_then(|attributes| contains_use_discriminant(&item_struct, &attributes));
         assert!(actual.unwrap());
     }
 
@@ -372,12 +799,13 @@ mod tests {
         })
         .unwrap();
 
-        let actual = contains_initialize_with(&item_struct.attrs);
+        let actual = contains_initialize_with(&Attributes::parse(&item_struct.attrs).unwrap());
         assert_eq!(
             actual.unwrap().to_token_stream().to_string(),
             "initialization_method"
         );
-        let actual = contains_use_discriminant(&item_struct);
+        let actual = Attributes::parse(&item_struct.attrs)
+            .and_then(|attributes| contains_use_discriminant(&item_struct, &attributes));
         assert!(actual.unwrap());
     }
 
@@ -394,15 +822,16 @@ mod tests {
         })
         .unwrap();
 
-        let actual = contains_initialize_with(&item_struct.attrs);
+        let actual = contains_initialize_with(&Attributes::parse(&item_struct.attrs).unwrap());
         assert_eq!(
             actual.unwrap().to_token_stream().to_string(),
             "initialization_method"
         );
-        let actual = contains_use_discriminant(&item_struct);
+        let actual = Attributes::parse(&item_struct.attrs)
+            .and_then(|attributes| contains_use_discriminant(&item_struct, &attributes));
         assert!(actual.unwrap());
 
-        let crate_ = get_crate(&item_struct.attrs);
+        let crate_ = get_crate(&Attributes::parse(&item_struct.attrs).unwrap());
         assert_eq!(
             crate_.unwrap().to_token_stream().to_string(),
             "reexporter :: borsh"
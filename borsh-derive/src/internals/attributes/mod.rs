@@ -3,6 +3,7 @@ use syn::{Attribute, Path};
 pub mod field;
 pub mod item;
 pub mod parsing;
+pub mod variant;
 
 /// first field is attr name
 /// second field is its expected value format representation for error printing
@@ -21,12 +22,43 @@ pub const SERIALIZE: Symbol = Symbol("serialize", "serialize = ...");
 pub const DESERIALIZE: Symbol = Symbol("deserialize", "deserialize = ...");
 /// skip - sub-borsh nested meta, field-level only attribute, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
 pub const SKIP: Symbol = Symbol("skip", "skip");
+/// default - sub-borsh nested meta, field-level only, `BorshDeserialize` context;
+/// also sub-skip nested meta, `#[borsh(skip(default = "..."))]`
+pub const DEFAULT: Symbol = Symbol("default", "default = ...");
 /// init - sub-borsh nested meta, item-level only attribute  `BorshDeserialize` context
 pub const INIT: Symbol = Symbol("init", "init = ...");
 /// serialize_with - sub-borsh nested meta, field-level only, `BorshSerialize` context
 pub const SERIALIZE_WITH: Symbol = Symbol("serialize_with", "serialize_with = ...");
 /// deserialize_with - sub-borsh nested meta, field-level only, `BorshDeserialize` context
 pub const DESERIALIZE_WITH: Symbol = Symbol("deserialize_with", "deserialize_with = ...");
+/// with - sub-borsh nested meta, field-level only, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
+pub const WITH: Symbol = Symbol("with", "with = ...");
+/// length - sub-borsh nested meta, field-level only, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
+pub const LENGTH: Symbol = Symbol("length", "length = ...");
+/// varint - sub-borsh nested meta, field-level only, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
+pub const VARINT: Symbol = Symbol("varint", "varint");
+/// big_endian - sub-borsh nested meta, field-level and item-level, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
+pub const BIG_ENDIAN: Symbol = Symbol("big_endian", "big_endian");
+/// max_len - sub-borsh nested meta, field-level only, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
+pub const MAX_LEN: Symbol = Symbol("max_len", "max_len = ...");
+/// pad - sub-borsh nested meta, field-level only, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
+pub const PAD: Symbol = Symbol("pad", "pad = ...");
+/// reserved - sub-borsh nested meta, field-level only, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
+pub const RESERVED: Symbol = Symbol("reserved", "reserved = ...");
+/// pad_after - sub-borsh nested meta, item-level only, structs only, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
+pub const PAD_AFTER: Symbol = Symbol("pad_after", "pad_after = ...");
+/// reserved_after - sub-borsh nested meta, item-level only, structs only, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
+pub const RESERVED_AFTER: Symbol = Symbol("reserved_after", "reserved_after = ...");
+/// other - sub-borsh nested meta, variant-level only, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
+pub const OTHER: Symbol = Symbol("other", "other");
+/// remote - sub-borsh nested meta, item-level only, structs and enums only, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
+pub const REMOTE: Symbol = Symbol("remote", "remote = ...");
+/// discriminator - sub-borsh nested meta, item-level only, structs and enums only, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
+pub const DISCRIMINATOR: Symbol = Symbol("discriminator", "discriminator = [...]");
+/// context - sub-borsh nested meta, item-level only, structs and enums only, `BorshDeserialize` contexts
+pub const CONTEXT: Symbol = Symbol("context", "context = ...");
+/// tag_consts - sub-borsh nested meta, item-level only, enums only, `BorshSerialize` context
+pub const TAG_CONSTS: Symbol = Symbol("tag_consts", "tag_consts");
 /// crate - sub-borsh nested meta, item-level only, `BorshSerialize`, `BorshDeserialize`, `BorshSchema` contexts
 pub const CRATE: Symbol = Symbol("crate", "crate = ...");
 
@@ -38,6 +70,10 @@ pub mod schema_keys {
     pub const SCHEMA: Symbol = Symbol("schema", "schema(...)");
     /// params - sub-schema nested meta, field-level only attribute
     pub const PARAMS: Symbol = Symbol("params", "params = ...");
+    /// as - sub-schema nested meta, field-level only attribute
+    pub const AS: Symbol = Symbol("as", "as = ...");
+    /// name - sub-schema nested meta, item-level only attribute
+    pub const NAME: Symbol = Symbol("name", "name = ...");
     /// serialize_with - sub-borsh nested meta, field-level only, `BorshSerialize` context
     /// with_funcs - sub-schema nested meta, field-level only attribute
     pub const WITH_FUNCS: Symbol = Symbol("with_funcs", "with_funcs(...)");
@@ -7,7 +7,7 @@ use syn::{
 
 use super::Symbol;
 
-fn get_lit_str2(
+pub(super) fn get_lit_str2(
     attr_name: Symbol,
     meta_item_name: Symbol,
     meta: &ParseNestedMeta,
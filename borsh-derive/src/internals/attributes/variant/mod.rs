@@ -0,0 +1,180 @@
+use syn::{Fields, ItemEnum, Variant};
+
+use super::{get_one_attribute, BORSH, OTHER};
+
+/// `#[borsh(other)]` catch-all variant, which captures unknown variant tag
+pub(crate) struct OtherVariant<'a> {
+    pub variant: &'a Variant,
+    /// whether the variant also captures length-prefixed bytes of the body as its second field,
+    /// otherwise it consists of the tag alone
+    pub captures_bytes: bool,
+}
+
+fn contains_other(variant: &Variant) -> syn::Result<bool> {
+    let mut other = false;
+    if let Some(attr) = get_one_attribute(&variant.attrs)? {
+        attr.parse_nested_meta(|meta| {
+            if meta.path != OTHER {
+                return Err(meta.error(format_args!(
+                    "malformed {0} attribute, expected `{0}({1})`",
+                    BORSH.0, OTHER.1
+                )));
+            }
+            other = true;
+            Ok(())
+        })?;
+    }
+    Ok(other)
+}
+
+pub(crate) fn other_variant(input: &ItemEnum) -> syn::Result<Option<OtherVariant<'_>>> {
+    let mut result = None;
+    for (variant_idx, variant) in input.variants.iter().enumerate() {
+        if !contains_other(variant)? {
+            continue;
+        }
+        if variant_idx + 1 != input.variants.len() {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!("`{}` variant must be the last one", OTHER.0),
+            ));
+        }
+        if variant.discriminant.is_some() {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!("`{}` variant cannot have an explicit discriminant", OTHER.0),
+            ));
+        }
+        let fields = match &variant.fields {
+            Fields::Unnamed(fields) if matches!(fields.unnamed.len(), 1 | 2) => fields,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    format!(
+                        "`{}` variant must be a tuple variant of `u8` tag \
+                         and (optionally) `Vec<u8>` body bytes",
+                        OTHER.0
+                    ),
+                ))
+            }
+        };
+        if let Some(attr) = fields
+            .unnamed
+            .iter()
+            .flat_map(|field| field.attrs.iter())
+            .find(|attr| attr.path() == BORSH)
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "`borsh` attributes are not supported on fields of `{}` variant",
+                    OTHER.0
+                ),
+            ));
+        }
+        let captures_bytes = fields.unnamed.len() == 2;
+        let has_fields_variants = input
+            .variants
+            .iter()
+            .take(variant_idx)
+            .any(|variant| !matches!(variant.fields, Fields::Unit));
+        if !captures_bytes && has_fields_variants {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!(
+                    "`{}` variant, which doesn't capture `Vec<u8>` body bytes, \
+                     is only supported in enums with unit variants",
+                    OTHER.0
+                ),
+            ));
+        }
+        result = Some(OtherVariant {
+            variant,
+            captures_bytes,
+        });
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+    use syn::ItemEnum;
+
+    use crate::internals::test_helpers::local_insta_assert_debug_snapshot;
+
+    use super::other_variant;
+
+    fn other_variant_err(item_enum: ItemEnum) -> syn::Error {
+        match other_variant(&item_enum) {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        }
+    }
+
+    #[test]
+    fn test_other_variant() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum A {
+                B(u64),
+                #[borsh(other)]
+                Unknown(u8, Vec<u8>),
+            }
+        })
+        .unwrap();
+        let other = other_variant(&item_enum).unwrap().unwrap();
+        assert_eq!(other.variant.ident, "Unknown");
+        assert!(other.captures_bytes);
+    }
+
+    #[test]
+    fn test_other_variant_not_last() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum A {
+                #[borsh(other)]
+                Unknown(u8),
+                B,
+            }
+        })
+        .unwrap();
+        local_insta_assert_debug_snapshot!(other_variant_err(item_enum));
+    }
+
+    #[test]
+    fn test_other_variant_wrong_shape() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum A {
+                B,
+                #[borsh(other)]
+                Unknown { tag: u8 },
+            }
+        })
+        .unwrap();
+        local_insta_assert_debug_snapshot!(other_variant_err(item_enum));
+    }
+
+    #[test]
+    fn test_other_variant_tag_only_with_fields() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum A {
+                B(u64),
+                #[borsh(other)]
+                Unknown(u8),
+            }
+        })
+        .unwrap();
+        local_insta_assert_debug_snapshot!(other_variant_err(item_enum));
+    }
+
+    #[test]
+    fn test_variant_wrong_key() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum A {
+                #[borsh(skip)]
+                B,
+            }
+        })
+        .unwrap();
+        local_insta_assert_debug_snapshot!(other_variant_err(item_enum));
+    }
+}
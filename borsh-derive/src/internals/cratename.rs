@@ -7,7 +7,7 @@ use super::attributes::item;
 pub(crate) const BORSH: &str = "borsh";
 
 pub(crate) fn get(attrs: &[Attribute]) -> Result<Path, Error> {
-    let path = item::get_crate(attrs)?;
+    let path = item::get_crate(&item::Attributes::parse(attrs)?);
     match path {
         Some(path) => Ok(path),
         None => {
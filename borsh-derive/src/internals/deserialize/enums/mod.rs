@@ -1,40 +1,153 @@
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{Fields, ItemEnum, Path, Variant};
+use syn::{Fields, Generics, ItemEnum, Path, Type, Variant};
 
-use crate::internals::{attributes::item, deserialize, enum_discriminant::Discriminants, generics};
+use crate::internals::{
+    attributes::{item, variant},
+    deserialize,
+    enum_discriminant::Discriminants,
+    generics,
+};
 
 pub fn process(input: &ItemEnum, cratename: Path) -> syn::Result<TokenStream2> {
+    let attributes = item::Attributes::parse(&input.attrs)?;
     let name = &input.ident;
     let generics = generics::without_defaults(&input.generics);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let mut where_clause = generics::default_where(where_clause);
     let mut variant_arms = TokenStream2::new();
-    let use_discriminant = item::contains_use_discriminant(input)?;
+    let mut slice_variant_arms = TokenStream2::new();
+    let mut has_prefix = false;
+    let use_discriminant = item::contains_use_discriminant(input, &attributes)?;
     let discriminants = Discriminants::new(&input.variants);
     let mut generics_output = deserialize::GenericsOutput::new(&generics);
+    let other = variant::other_variant(input)?;
+    let remote = item::contains_remote(&attributes);
+    let big_endian = item::contains_big_endian(&attributes);
+    let context = item::contains_context(&attributes);
+    let check_discriminator = deserialize::check_discriminator_output(
+        item::contains_discriminator(&attributes, name),
+        &cratename,
+    );
+    let enum_path: Path = remote.clone().unwrap_or_else(|| name.clone().into());
 
     for (variant_idx, variant) in input.variants.iter().enumerate() {
-        let variant_body = process_variant(variant, &cratename, &mut generics_output)?;
+        if matches!(&other, Some(other) if std::ptr::eq(other.variant, variant)) {
+            continue;
+        }
+        let (variant_body, slice) = process_variant(
+            variant,
+            &cratename,
+            big_endian,
+            context.as_ref(),
+            &generics,
+            &mut generics_output,
+        )?;
         let variant_ident = &variant.ident;
 
         let discriminant_value = discriminants.get(variant_ident, use_discriminant, variant_idx)?;
         variant_arms.extend(quote! {
-            if variant_tag == #discriminant_value { #name::#variant_ident #variant_body } else
+            if variant_tag == #discriminant_value { #enum_path::#variant_ident #variant_body } else
+        });
+        has_prefix |= slice.has_prefix();
+        let read_prefix = slice.prefix_statement(&cratename);
+        let slice_variant_body = slice_variant_body(&variant.fields, slice);
+        slice_variant_arms.extend(quote! {
+            if variant_tag == #discriminant_value { #read_prefix #enum_path::#variant_ident #slice_variant_body } else
         });
     }
-    let init = if let Some(method_ident) = item::contains_initialize_with(&input.attrs)? {
+    let unknown_variant = match other {
+        Some(variant::OtherVariant {
+            variant,
+            captures_bytes,
+        }) => {
+            let variant_ident = &variant.ident;
+            if captures_bytes {
+                quote! {
+                    {
+                        let bytes = <#cratename::__private::maybestd::vec::Vec<u8> as #cratename::de::BorshDeserialize>::deserialize_reader(reader)?;
+                        #enum_path::#variant_ident(variant_tag, bytes)
+                    }
+                }
+            } else {
+                quote! {
+                    { #enum_path::#variant_ident(variant_tag) }
+                }
+            }
+        }
+        None => quote! {
+            {
+                return Err(#cratename::io::Error::new(
+                    #cratename::io::ErrorKind::InvalidData,
+                    #cratename::__private::maybestd::format!("Unexpected variant tag: {:?}", variant_tag),
+                ))
+            }
+        },
+    };
+    let init = if let Some(method_ident) = item::contains_initialize_with(&attributes) {
         quote! {
             return_value.#method_ident();
         }
     } else {
         quote! {}
     };
-    generics_output.extend(&mut where_clause, &cratename);
+    generics_output.extend(&mut where_clause, &cratename, context.as_ref());
+
+    if let Some(context) = context {
+        let body = quote! {
+            #check_discriminator
+            let variant_tag = <u8 as #cratename::de::BorshDeserialize>::deserialize_reader(reader)?;
+            let mut return_value =
+                #variant_arms #unknown_variant;
+            #init
+            Ok(return_value)
+        };
+        return Ok(deserialize::context_output(
+            name,
+            &generics,
+            &where_clause,
+            &context,
+            body,
+            &cratename,
+        ));
+    }
+
+    if let Some(remote) = remote {
+        let vis = &input.vis;
+        return Ok(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #vis fn deserialize<__R: #cratename::io::Read>(reader: &mut __R) -> ::core::result::Result<#remote #ty_generics, #cratename::io::Error> {
+                    #check_discriminator
+                    let variant_tag = <u8 as #cratename::de::BorshDeserialize>::deserialize_reader(reader)?;
+                    let mut return_value =
+                        #variant_arms #unknown_variant;
+                    #init
+                    Ok(return_value)
+                }
+            }
+        });
+    }
 
+    let context_free = deserialize::context_free_output(name, &generics, &cratename);
+    // without fixed-size prefix in any variant the default `deserialize`, which calls `deserialize_reader`, is as good
+    let slice_deserialize = has_prefix.then(|| {
+        quote! {
+            fn deserialize(reader: &mut &[u8]) -> ::core::result::Result<Self, #cratename::io::Error> {
+                #check_discriminator
+                let variant_tag = <u8 as #cratename::de::BorshDeserialize>::deserialize(reader)?;
+                let mut return_value =
+                    #slice_variant_arms #unknown_variant;
+                #init
+                Ok(return_value)
+            }
+        }
+    });
     Ok(quote! {
         impl #impl_generics #cratename::de::BorshDeserialize for #name #ty_generics #where_clause {
+            #slice_deserialize
+
             fn deserialize_reader<__R: #cratename::io::Read>(reader: &mut __R) -> ::core::result::Result<Self, #cratename::io::Error> {
+                #check_discriminator
                 let tag = <u8 as #cratename::de::BorshDeserialize>::deserialize_reader(reader)?;
                 <Self as #cratename::de::EnumExt>::deserialize_variant(reader, tag)
             }
@@ -46,41 +159,56 @@ pub fn process(input: &ItemEnum, cratename: Path) -> syn::Result<TokenStream2> {
                 variant_tag: u8,
             ) -> ::core::result::Result<Self, #cratename::io::Error> {
                 let mut return_value =
-                    #variant_arms {
-                    return Err(#cratename::io::Error::new(
-                        #cratename::io::ErrorKind::InvalidData,
-                        #cratename::__private::maybestd::format!("Unexpected variant tag: {:?}", variant_tag),
-                    ))
-                };
+                    #variant_arms #unknown_variant;
                 #init
                 Ok(return_value)
             }
         }
+
+        #context_free
     })
 }
 
 fn process_variant(
     variant: &Variant,
     cratename: &Path,
+    big_endian: bool,
+    context: Option<&Type>,
+    item_generics: &Generics,
     generics: &mut deserialize::GenericsOutput,
-) -> syn::Result<TokenStream2> {
+) -> syn::Result<(TokenStream2, deserialize::SliceFields)> {
     let mut body = TokenStream2::new();
+    let mut slice = deserialize::SliceFields::new(item_generics);
     match &variant.fields {
         Fields::Named(fields) => {
             for field in &fields.named {
-                deserialize::process_field(field, cratename, &mut body, generics)?;
+                deserialize::process_field(
+                    field, cratename, big_endian, context, &mut body, &mut slice, generics,
+                )?;
             }
             body = quote! { { #body }};
         }
         Fields::Unnamed(fields) => {
             for field in fields.unnamed.iter() {
-                deserialize::process_field(field, cratename, &mut body, generics)?;
+                deserialize::process_field(
+                    field, cratename, big_endian, context, &mut body, &mut slice, generics,
+                )?;
             }
             body = quote! { ( #body )};
         }
         Fields::Unit => {}
     }
-    Ok(body)
+    Ok((body, slice))
+}
+
+/// body of the variant for slice-specialized `BorshDeserialize::deserialize`
+fn slice_variant_body(fields: &Fields, slice: deserialize::SliceFields) -> TokenStream2 {
+    let body = slice.body;
+    match fields {
+        Fields::Named(..) => quote! { { #body } },
+        Fields::Unnamed(..) => quote! { ( #body ) },
+        Fields::Unit => TokenStream2::new(),
+    }
 }
 
 #[cfg(test)]
@@ -328,4 +456,122 @@ mod tests {
         let actual = process(&item_enum, default_cratename()).unwrap();
         local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
     }
+
+    #[test]
+    fn other_variant_with_bytes() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum Message {
+                Ping,
+                Data(Vec<u8>),
+                #[borsh(other)]
+                Unknown(u8, Vec<u8>),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn other_variant_tag_only() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum Status {
+                Active,
+                Inactive,
+                #[borsh(other)]
+                Unknown(u8),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn remote_enum() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(remote = "other::Shape")]
+            enum ShapeDef {
+                Dot,
+                Circle { radius: u32 },
+                Line(i16, i16),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn big_endian_enum() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(big_endian)]
+            enum Message {
+                Ping,
+                Data { id: u32, payload: Vec<u8> },
+                Pair(i16, String),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn discriminator_enum() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(discriminator = [1, 2, 3, 4])]
+            enum Message {
+                Ping,
+                Data { id: u32 },
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn pad_enum_fields() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum Frame {
+                Data(u8, #[borsh(pad = 3)] u32),
+                Ack {
+                    #[borsh(reserved = 2)]
+                    seq: u16,
+                },
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_context_attr() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(context = registry::Registry)]
+            enum Message<T> {
+                Ping,
+                Call { method: Symbol, args: Vec<T> },
+                Reply(u64, Option<Symbol>),
+            }
+        })
+        .unwrap();
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
 }
@@ -1,9 +1,9 @@
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{ExprPath, Generics, Ident, Path};
+use syn::{Expr, ExprPath, Generics, Ident, Path, Type};
 
 use super::{
-    attributes::{field, BoundType},
+    attributes::{field, item, BoundType},
     generics,
 };
 
@@ -25,8 +25,12 @@ impl GenericsOutput {
             default_visitor: generics::FindTyParams::new(generics),
         }
     }
-    fn extend(self, where_clause: &mut syn::WhereClause, cratename: &Path) {
-        let de_trait: Path = syn::parse2(quote! { #cratename::de::BorshDeserialize }).unwrap();
+    fn extend(self, where_clause: &mut syn::WhereClause, cratename: &Path, context: Option<&Type>) {
+        let de_trait: Path = match context {
+            Some(context) => syn::parse2(quote! { #cratename::de::BorshDeserializeWith<#context> }),
+            None => syn::parse2(quote! { #cratename::de::BorshDeserialize }),
+        }
+        .unwrap();
         let default_trait: Path = syn::parse2(quote! { core::default::Default }).unwrap();
         let de_predicates =
             generics::compute_predicates(self.deserialize_visitor.process_for_bounds(), &de_trait);
@@ -38,13 +42,126 @@ impl GenericsOutput {
     }
 }
 
+/// `#[borsh(discriminator)]`: the statement, which checks the discriminator before the item's body
+fn check_discriminator_output(discriminator: Option<Vec<u8>>, cratename: &Path) -> TokenStream2 {
+    match discriminator {
+        Some(bytes) => quote! {
+            #cratename::discriminator::check(reader, &[#(#bytes),*])?;
+        },
+        None => TokenStream2::new(),
+    }
+}
+
+/// Fields of a struct or an enum variant, deserialized by the slice-specialized
+/// `BorshDeserialize::deserialize(buf: &mut &[u8])`.
+///
+/// Leading fields, which may be of fixed-size types, are read through `__private::FixedPrefix`,
+/// which reads those of them with `BorshDeserialize::FIXED_SIZE` (e.g. integers) with
+/// a single upfront length check, the rest of fields are read with `BorshDeserialize::deserialize`.
+struct SliceFields {
+    body: TokenStream2,
+    /// whether the prefix may still be extended with the next field
+    prefix_open: bool,
+    prefix_types: Vec<Type>,
+    prefix_ident: Ident,
+    type_params: Vec<Ident>,
+}
+
+impl SliceFields {
+    fn new(generics: &Generics) -> Self {
+        Self {
+            body: TokenStream2::new(),
+            prefix_open: true,
+            prefix_types: vec![],
+            prefix_ident: Ident::new("__fixed", Span::mixed_site()),
+            type_params: generics
+                .type_params()
+                .map(|param| param.ident.clone())
+                .collect(),
+        }
+    }
+
+    /// whether the field may be added to the prefix, i.e. it has no attributes, affecting
+    /// its encoding, and its type may be a fixed-size one, which `BorshDeserialize::FIXED_SIZE`
+    /// of the type decides on: type parameters of the item and types with generic arguments,
+    /// arrays or tuples are never added, so that the prefix isn't emitted for them at all
+    fn is_prefix_field(&self, read_padding: &Option<TokenStream2>, ty: &Type) -> bool {
+        if !self.prefix_open || read_padding.is_some() {
+            return false;
+        }
+        let path = match ty {
+            Type::Path(type_path) if type_path.qself.is_none() => &type_path.path,
+            _ => return false,
+        };
+        let is_type_param =
+            matches!(path.get_ident(), Some(ident) if self.type_params.contains(ident));
+        !is_type_param
+            && path
+                .segments
+                .iter()
+                .all(|segment| segment.arguments.is_none())
+    }
+
+    /// whether there is a prefix, i.e. the slice-specialized `deserialize` is worth emitting
+    fn has_prefix(&self) -> bool {
+        !self.prefix_types.is_empty()
+    }
+
+    /// statement, which reads the fixed-size part of the prefix, if there is one
+    fn prefix_statement(&self, cratename: &Path) -> TokenStream2 {
+        if self.prefix_types.is_empty() {
+            return TokenStream2::new();
+        }
+        let prefix_ident = &self.prefix_ident;
+        let prefix_types = &self.prefix_types;
+        quote! {
+            let mut #prefix_ident = #cratename::__private::FixedPrefix::take(
+                reader,
+                &[#(<#prefix_types as #cratename::BorshDeserialize>::FIXED_SIZE),*],
+            )?;
+        }
+    }
+}
+
+/// `#[borsh(context = Type)]`: the output of a derive, which implements
+/// `BorshDeserializeWith<Type>` instead of `BorshDeserialize`
+fn context_output(
+    name: &Ident,
+    generics: &Generics,
+    where_clause: &syn::WhereClause,
+    context: &Type,
+    body: TokenStream2,
+    cratename: &Path,
+) -> TokenStream2 {
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #cratename::de::BorshDeserializeWith<#context> for #name #ty_generics #where_clause {
+            fn deserialize_with_context<__R: #cratename::io::Read>(reader: &mut __R, ctx: &mut #context) -> ::core::result::Result<Self, #cratename::io::Error> {
+                #body
+            }
+        }
+    }
+}
+
+/// `impl ContextFree`, which makes a type, deriving `BorshDeserialize`,
+/// implement `BorshDeserializeWith` for any context
+fn context_free_output(name: &Ident, generics: &Generics, cratename: &Path) -> TokenStream2 {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #cratename::de::ContextFree for #name #ty_generics #where_clause {}
+    }
+}
+
 fn process_field(
     field: &syn::Field,
     cratename: &Path,
+    big_endian: bool,
+    context: Option<&Type>,
     body: &mut TokenStream2,
+    slice: &mut SliceFields,
     generics: &mut GenericsOutput,
 ) -> syn::Result<()> {
-    let parsed = field::Attributes::parse(&field.attrs)?;
+    let parsed = field::Attributes::parse(&field.attrs)?.inherit_big_endian(big_endian);
 
     generics
         .overrides
@@ -52,50 +169,156 @@ fn process_field(
     let needs_bounds_derive = parsed.needs_bounds_derive(BoundType::Deserialize);
 
     let field_name = field.ident.as_ref();
-    let delta = if parsed.skip {
-        if needs_bounds_derive {
+    let read_padding = padding_output(&parsed, cratename);
+    if parsed.skip {
+        let default_override = parsed.default_override();
+        // custom default value doesn't require `Default` bound on field's type
+        if needs_bounds_derive && default_override.is_none() {
             generics.default_visitor.visit_field(field);
         }
-        field_default_output(field_name)
-    } else {
-        if needs_bounds_derive {
-            generics.deserialize_visitor.visit_field(field);
+        // skipped field doesn't consume any bytes, unless there's padding before it
+        slice.prefix_open &= read_padding.is_none();
+        let delta = field_default_output(field_name, read_padding, default_override);
+        body.extend(delta.clone());
+        slice.body.extend(delta);
+        return Ok(());
+    }
+
+    if needs_bounds_derive {
+        generics.deserialize_visitor.visit_field(field);
+    }
+    match (parsed.deserialize_override(&field.ty, cratename)?, context) {
+        (Some(path), _) => {
+            let delta = field_output(field_name, read_padding, path);
+            body.extend(delta.clone());
+            slice.prefix_open = false;
+            slice.body.extend(delta);
         }
-        field_output(field_name, cratename, parsed.deserialize_with)
-    };
-    body.extend(delta);
+        (None, Some(context)) => {
+            let ty = &field.ty;
+            let context_path: ExprPath = syn::parse_quote! {
+                <#ty as #cratename::de::BorshDeserializeWith<#context>>::deserialize_with_context
+            };
+            let read_field = quote! { #context_path(reader, ctx)? };
+            body.extend(value_output(field_name, read_padding, read_field));
+        }
+        (None, None) => {
+            let reader_path: ExprPath = syn::parse_quote! {
+                #cratename::BorshDeserialize::deserialize_reader
+            };
+            body.extend(field_output(field_name, read_padding.clone(), reader_path));
+            if slice.is_prefix_field(&read_padding, &field.ty) {
+                let ty = &field.ty;
+                let prefix_ident = &slice.prefix_ident;
+                let read_field = quote! { #prefix_ident.read::<#ty>(reader)? };
+                slice
+                    .body
+                    .extend(value_output(field_name, None, read_field));
+                slice.prefix_types.push(ty.clone());
+            } else {
+                let slice_path: ExprPath = syn::parse_quote! {
+                    #cratename::BorshDeserialize::deserialize
+                };
+                slice.prefix_open = false;
+                slice
+                    .body
+                    .extend(field_output(field_name, read_padding, slice_path));
+            }
+        }
+    }
     Ok(())
 }
 
+/// `#[borsh(pad = ...)]` or `#[borsh(reserved = ...)]`: the statement, which reads
+/// (and checks, for `reserved`) zero bytes before the field
+fn padding_output(parsed: &field::Attributes, cratename: &Path) -> Option<TokenStream2> {
+    if let Some(len) = &parsed.pad {
+        Some(quote! { #cratename::padding::skip(reader, #len)?; })
+    } else {
+        parsed
+            .reserved
+            .as_ref()
+            .map(|len| quote! { #cratename::padding::check_zeros(reader, #len)?; })
+    }
+}
+
+/// `#[borsh(pad_after = ...)]` or `#[borsh(reserved_after = ...)]`: the expression, which
+/// evaluates `value` and then reads (and checks, for `reserved_after`) zero bytes after it
+fn trailing_padding_output(
+    trailing: Option<&item::TrailingPadding>,
+    value: TokenStream2,
+    cratename: &Path,
+) -> TokenStream2 {
+    let read_trailing = match trailing {
+        Some(item::TrailingPadding {
+            len,
+            reserved: false,
+        }) => quote! { #cratename::padding::skip(reader, #len)?; },
+        Some(item::TrailingPadding {
+            len,
+            reserved: true,
+        }) => quote! { #cratename::padding::check_zeros(reader, #len)?; },
+        None => return value,
+    };
+    quote! {
+        {
+            let value = #value;
+            #read_trailing
+            value
+        }
+    }
+}
+
 /// function which computes derive output [proc_macro2::TokenStream]
 /// of code, which deserializes single field
 fn field_output(
     field_name: Option<&Ident>,
-    cratename: &Path,
-    deserialize_with: Option<ExprPath>,
+    read_padding: Option<TokenStream2>,
+    path: ExprPath,
 ) -> TokenStream2 {
-    let default_path: ExprPath =
-        syn::parse2(quote! { #cratename::BorshDeserialize::deserialize_reader }).unwrap();
-    let path: ExprPath = deserialize_with.unwrap_or(default_path);
+    value_output(field_name, read_padding, quote! { #path(reader)? })
+}
+
+/// function which computes derive output [proc_macro2::TokenStream]
+/// of code, which initializes single field with `read_field` expression
+fn value_output(
+    field_name: Option<&Ident>,
+    read_padding: Option<TokenStream2>,
+    read_field: TokenStream2,
+) -> TokenStream2 {
+    let value = match read_padding {
+        Some(read_padding) => quote! { { #read_padding #read_field } },
+        None => read_field,
+    };
     if let Some(field_name) = field_name {
         quote! {
-            #field_name: #path(reader)?,
+            #field_name: #value,
         }
     } else {
         quote! {
-            #path(reader)?,
+            #value,
         }
     }
 }
 
 /// function which computes derive output [proc_macro2::TokenStream]
 /// of code, which deserializes single skipped field
-fn field_default_output(field_name: Option<&Ident>) -> TokenStream2 {
+fn field_default_output(
+    field_name: Option<&Ident>,
+    read_padding: Option<TokenStream2>,
+    default_override: Option<Expr>,
+) -> TokenStream2 {
+    let default_expr: Expr =
+        default_override.unwrap_or_else(|| syn::parse_quote! { core::default::Default::default() });
+    let default_expr = match read_padding {
+        Some(read_padding) => quote! { { #read_padding #default_expr } },
+        None => quote! { #default_expr },
+    };
     if let Some(field_name) = field_name {
         quote! {
-            #field_name: core::default::Default::default(),
+            #field_name: #default_expr,
         }
     } else {
-        quote! { core::default::Default::default(), }
+        quote! { #default_expr, }
     }
 }
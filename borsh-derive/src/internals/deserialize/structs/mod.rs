@@ -5,57 +5,152 @@ use syn::{Fields, ItemStruct, Path};
 use crate::internals::{attributes::item, deserialize, generics};
 
 pub fn process(input: &ItemStruct, cratename: Path) -> syn::Result<TokenStream2> {
+    let attributes = item::Attributes::parse(&input.attrs)?;
     let name = &input.ident;
     let generics = generics::without_defaults(&input.generics);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let mut where_clause = generics::default_where(where_clause);
     let mut body = TokenStream2::new();
+    let mut slice = deserialize::SliceFields::new(&generics);
     let mut generics_output = deserialize::GenericsOutput::new(&generics);
+    let remote = item::contains_remote(&attributes);
+    let big_endian = item::contains_big_endian(&attributes);
+    let context = item::contains_context(&attributes);
+    let check_discriminator = deserialize::check_discriminator_output(
+        item::contains_discriminator(&attributes, name),
+        &cratename,
+    );
+    let constructor = match &remote {
+        Some(remote) => quote! { #remote },
+        None => quote! { Self },
+    };
 
-    let return_value = match &input.fields {
+    let (return_value, slice_return_value) = match &input.fields {
         Fields::Named(fields) => {
             for field in &fields.named {
-                deserialize::process_field(field, &cratename, &mut body, &mut generics_output)?;
-            }
-            quote! {
-                Self { #body }
+                deserialize::process_field(
+                    field,
+                    &cratename,
+                    big_endian,
+                    context.as_ref(),
+                    &mut body,
+                    &mut slice,
+                    &mut generics_output,
+                )?;
             }
+            let slice_body = &slice.body;
+            (
+                quote! { #constructor { #body } },
+                quote! { #constructor { #slice_body } },
+            )
         }
         Fields::Unnamed(fields) => {
             for field in fields.unnamed.iter() {
-                deserialize::process_field(field, &cratename, &mut body, &mut generics_output)?;
-            }
-            quote! {
-                Self( #body )
-            }
-        }
-        Fields::Unit => {
-            quote! {
-                Self {}
+                deserialize::process_field(
+                    field,
+                    &cratename,
+                    big_endian,
+                    context.as_ref(),
+                    &mut body,
+                    &mut slice,
+                    &mut generics_output,
+                )?;
             }
+            let slice_body = &slice.body;
+            (
+                quote! { #constructor( #body ) },
+                quote! { #constructor( #slice_body ) },
+            )
         }
+        Fields::Unit => (quote! { #constructor {} }, quote! { #constructor {} }),
     };
-    generics_output.extend(&mut where_clause, &cratename);
+    let trailing = item::contains_trailing_padding(&attributes);
+    let return_value =
+        deserialize::trailing_padding_output(trailing.as_ref(), return_value, &cratename);
+    let slice_return_value =
+        deserialize::trailing_padding_output(trailing.as_ref(), slice_return_value, &cratename);
+    let read_prefix = slice.prefix_statement(&cratename);
+    generics_output.extend(&mut where_clause, &cratename, context.as_ref());
+    let init = item::contains_initialize_with(&attributes)
+        .map(|method_ident| quote! { return_value.#method_ident(); });
+
+    if let Some(context) = context {
+        let body = match init {
+            Some(init) => quote! {
+                #check_discriminator
+                let mut return_value = #return_value;
+                #init
+                Ok(return_value)
+            },
+            None => quote! {
+                #check_discriminator
+                Ok(#return_value)
+            },
+        };
+        return Ok(deserialize::context_output(
+            name,
+            &generics,
+            &where_clause,
+            &context,
+            body,
+            &cratename,
+        ));
+    }
 
-    if let Some(method_ident) = item::contains_initialize_with(&input.attrs)? {
-        Ok(quote! {
-            impl #impl_generics #cratename::de::BorshDeserialize for #name #ty_generics #where_clause {
-                fn deserialize_reader<__R: #cratename::io::Read>(reader: &mut __R) -> ::core::result::Result<Self, #cratename::io::Error> {
+    if let Some(remote) = remote {
+        let vis = &input.vis;
+        return Ok(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #vis fn deserialize<__R: #cratename::io::Read>(reader: &mut __R) -> ::core::result::Result<#remote #ty_generics, #cratename::io::Error> {
+                    #check_discriminator
                     let mut return_value = #return_value;
-                    return_value.#method_ident();
+                    #init
                     Ok(return_value)
                 }
             }
-        })
-    } else {
-        Ok(quote! {
-            impl #impl_generics #cratename::de::BorshDeserialize for #name #ty_generics #where_clause {
-                fn deserialize_reader<__R: #cratename::io::Read>(reader: &mut __R) -> ::core::result::Result<Self, #cratename::io::Error> {
-                    Ok(#return_value)
-                }
-            }
-        })
+        });
     }
+
+    let context_free = deserialize::context_free_output(name, &generics, &cratename);
+    let (slice_body, body) = match item::contains_initialize_with(&attributes) {
+        Some(method_ident) => (
+            quote! {
+                let mut return_value = #slice_return_value;
+                return_value.#method_ident();
+                Ok(return_value)
+            },
+            quote! {
+                let mut return_value = #return_value;
+                return_value.#method_ident();
+                Ok(return_value)
+            },
+        ),
+        None => (
+            quote! { Ok(#slice_return_value) },
+            quote! { Ok(#return_value) },
+        ),
+    };
+    // without fixed-size prefix the default `deserialize`, which calls `deserialize_reader`, is as good
+    let slice_deserialize = slice.has_prefix().then(|| {
+        quote! {
+            fn deserialize(reader: &mut &[u8]) -> ::core::result::Result<Self, #cratename::io::Error> {
+                #check_discriminator
+                #read_prefix
+                #slice_body
+            }
+        }
+    });
+    Ok(quote! {
+        impl #impl_generics #cratename::de::BorshDeserialize for #name #ty_generics #where_clause {
+            #slice_deserialize
+
+            fn deserialize_reader<__R: #cratename::io::Read>(reader: &mut __R) -> ::core::result::Result<Self, #cratename::io::Error> {
+                #check_discriminator
+                #body
+            }
+        }
+        #context_free
+    })
 }
 
 #[cfg(test)]
@@ -234,6 +329,24 @@ mod tests {
         local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
     }
 
+    #[test]
+    fn generic_named_fields_struct_borsh_skip_default() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct G<K, V, U> {
+                #[borsh(skip, default = "new_cache")]
+                x: HashMap<K, V>,
+                #[borsh(skip(default = "U::placeholder()"))]
+                y: U,
+                z: V,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
     #[test]
     fn check_deserialize_with_attr() {
         let item_struct: ItemStruct = syn::parse2(quote! {
@@ -262,4 +375,195 @@ mod tests {
         let actual = process(&item_enum, default_cratename()).unwrap();
         local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
     }
+
+    #[test]
+    fn check_length_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(length = u8)]
+                x: Vec<String>,
+                #[borsh(length = u16)]
+                y: BTreeMap<u64, Vec<u8>>,
+                z: u64,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn remote_struct() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(remote = "other::Config")]
+            struct ConfigDef<T> {
+                name: String,
+                #[borsh(skip)]
+                cache: Vec<u8>,
+                value: T,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_with_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A<K, V> {
+                #[borsh(with = "third_party_impl")]
+                x: ThirdParty<K, V>,
+                y: u64,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_big_endian_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(big_endian)]
+                x: u64,
+                #[borsh(big_endian, length = u8)]
+                y: Vec<f32>,
+                #[borsh(big_endian)]
+                z: Option<i16>,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_big_endian_container_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(big_endian)]
+            struct A {
+                x: u32,
+                y: String,
+                z: BTreeMap<String, u64>,
+                #[borsh(varint)]
+                w: u64,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_discriminator_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(discriminator)]
+            struct Counter<T> {
+                value: T,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_discriminator_custom_bytes_remote() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(discriminator = [0xca, 0xfe], remote = "other::Counter")]
+            struct CounterDef {
+                value: u8,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_pad_and_reserved_attrs() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct Header {
+                version: u8,
+                #[borsh(pad = 3)]
+                length: u32,
+                #[borsh(reserved = RESERVED_LEN, skip)]
+                cache: u64,
+                #[borsh(reserved = 4)]
+                _reserved: (),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_context_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(context = Interner, init = validate)]
+            struct Record<T> {
+                id: u32,
+                name: Symbol,
+                tags: Vec<T>,
+                #[borsh(deserialize_with = "read_flags")]
+                flags: u8,
+                #[borsh(skip)]
+                cache: u64,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_trailing_padding_attrs() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(reserved_after = 4)]
+            struct Header {
+                version: u8,
+                #[borsh(pad = 3)]
+                length: u32,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_pad_after_on_unit_struct() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(pad_after = PAD_LEN)]
+            struct Filler;
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
 }
@@ -1,6 +1,76 @@
 use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
 use syn::{ItemUnion, Path};
 
-pub fn process(_input: &ItemUnion, _cratename: Path) -> syn::Result<TokenStream2> {
-    unimplemented!()
+use crate::internals::{attributes::item, deserialize, unions};
+
+pub fn process(input: &ItemUnion, cratename: Path) -> syn::Result<TokenStream2> {
+    unions::check(input)?;
+    let attributes = item::Attributes::parse(&input.attrs)?;
+    let name = &input.ident;
+    let layout_assertions = unions::layout_assertions(input, &cratename);
+
+    let init = item::contains_initialize_with(&attributes)
+        .map(|method_ident| quote! { return_value.#method_ident(); });
+    let mutability = init.as_ref().map(|_| quote! { mut });
+    let context_free = deserialize::context_free_output(name, &input.generics, &cratename);
+
+    Ok(quote! {
+        #layout_assertions
+        impl #cratename::de::BorshDeserialize for #name {
+            fn deserialize_reader<__R: #cratename::io::Read>(reader: &mut __R) -> ::core::result::Result<Self, #cratename::io::Error> {
+                let bytes = <[u8; ::core::mem::size_of::<#name>()] as #cratename::de::BorshDeserialize>::deserialize_reader(reader)?;
+                // SAFETY: every field of the union is plain old data, which is valid for any bytes,
+                // and spans all bytes of the union
+                let #mutability return_value = unsafe {
+                    ::core::mem::transmute::<[u8; ::core::mem::size_of::<#name>()], #name>(bytes)
+                };
+                #init
+                Ok(return_value)
+            }
+        }
+        #context_free
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::internals::test_helpers::{
+        default_cratename, local_insta_assert_snapshot, pretty_print_syn_str,
+    };
+
+    use super::*;
+
+    #[test]
+    fn simple_union() {
+        let item_union: ItemUnion = syn::parse2(quote! {
+            #[repr(C)]
+            union A {
+                x: u32,
+                y: [u8; 4],
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_union, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn union_init_func() {
+        let item_union: ItemUnion = syn::parse2(quote! {
+            #[repr(C)]
+            #[borsh(init = initialization_method)]
+            union A {
+                x: u64,
+                y: [u8; 8],
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_union, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
 }
@@ -4,7 +4,7 @@ use std::collections::HashSet;
 use syn::{Fields, Generics, Ident, ItemEnum, ItemStruct, Path, Variant, Visibility};
 
 use crate::internals::{
-    attributes::{field, item},
+    attributes::{field, item, variant},
     enum_discriminant::Discriminants,
     generics, schema,
 };
@@ -29,20 +29,37 @@ fn transform_variant_fields(mut input: Fields) -> Fields {
 }
 
 pub fn process(input: &ItemEnum, cratename: Path) -> syn::Result<TokenStream2> {
+    let attributes = item::Attributes::parse(&input.attrs)?;
     let name = &input.ident;
-    let enum_name = name.to_token_stream().to_string();
+    let remote = item::contains_remote(&attributes);
+    let schema_name = item::contains_schema_name(&attributes);
+    // identifiers of inner structs of variants are derived from the name of the enum in Rust
+    let rust_name = schema::item_name(name, remote.as_ref(), None);
+    let enum_name = schema::item_name(name, remote.as_ref(), schema_name.clone());
+    let self_declaration = schema::self_declaration(remote.as_ref(), &cratename);
     let generics = generics::without_defaults(&input.generics);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let mut where_clause = generics::default_where(where_clause);
     let mut generics_output = schema::GenericsOutput::new(&generics);
-    let use_discriminant = item::contains_use_discriminant(input)?;
+    let use_discriminant = item::contains_use_discriminant(input, &attributes)?;
+    let big_endian = item::contains_big_endian(&attributes);
+    let add_self_definition = schema::add_self_definition(
+        &self_declaration,
+        item::contains_discriminator(&attributes, name),
+        &cratename,
+    );
     let discriminants = Discriminants::new(&input.variants);
 
     // Generate functions that return the schema for variants.
     let mut variants_defs = vec![];
     let mut inner_defs = TokenStream2::new();
     let mut add_recursive_defs = TokenStream2::new();
+    let other = variant::other_variant(input)?;
     for (variant_idx, variant) in input.variants.iter().enumerate() {
+        // catch-all variant doesn't have a tag of its own
+        if matches!(&other, Some(other) if std::ptr::eq(other.variant, variant)) {
+            continue;
+        }
         let discriminant_info = DiscriminantInfo {
             variant_idx,
             discriminants: &discriminants,
@@ -52,29 +69,47 @@ pub fn process(input: &ItemEnum, cratename: Path) -> syn::Result<TokenStream2> {
             variant,
             discriminant_info,
             &cratename,
-            &enum_name,
+            EnumName {
+                rust: &rust_name,
+                schema: schema_name.as_deref(),
+            },
+            big_endian,
             &generics,
             &mut generics_output,
         )?;
         inner_defs.extend(variant_output.inner_struct);
-        add_recursive_defs.extend(variant_output.add_definitions_recursively_call);
+        add_recursive_defs.extend(variant_output.collect_definitions_call);
         variants_defs.push(variant_output.variant_entry);
     }
 
+    let vis = remote.as_ref().map(|_| &input.vis);
+    let add_definitions_recursively =
+        schema::add_definitions_recursively(remote.as_ref(), vis, &cratename);
     let type_definitions = quote! {
-        fn add_definitions_recursively(definitions: &mut #cratename::__private::maybestd::collections::BTreeMap<#cratename::schema::Declaration, #cratename::schema::Definition>) {
+        #add_definitions_recursively
+        #vis fn collect_definitions(collector: &mut #cratename::schema::DefinitionsCollector<'_>) {
             #inner_defs
             #add_recursive_defs
             let definition = #cratename::schema::Definition::Enum {
                 tag_width: 1,
                 variants: #cratename::__private::maybestd::vec![#(#variants_defs),*],
             };
-            #cratename::schema::add_definition(<Self as #cratename::BorshSchema>::declaration(), definition, definitions);
+            #add_self_definition
         }
     };
 
     let (predicates, declaration) = generics_output.result(&enum_name, &cratename);
     where_clause.predicates.extend(predicates);
+    if remote.is_some() {
+        return Ok(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #vis fn declaration() -> #cratename::schema::Declaration {
+                    #declaration
+                }
+                #type_definitions
+            }
+        });
+    }
     Ok(quote! {
         impl #impl_generics #cratename::BorshSchema for #name #ty_generics #where_clause {
             fn declaration() -> #cratename::schema::Declaration {
@@ -88,12 +123,20 @@ pub fn process(input: &ItemEnum, cratename: Path) -> syn::Result<TokenStream2> {
 struct VariantOutput {
     /// rust definition of the inner struct used in variant.
     inner_struct: TokenStream2,
-    /// call to `add_definitions_recursively`.
-    add_definitions_recursively_call: TokenStream2,
+    /// call to `collect_definitions`.
+    collect_definitions_call: TokenStream2,
     /// entry with a variant's declaration, element in vector of whole enum's definition
     variant_entry: TokenStream2,
 }
 
+/// Names of the enum, which prefix names of inner structs of variants.
+struct EnumName<'a> {
+    /// name of the enum in Rust, for identifiers of inner structs.
+    rust: &'a str,
+    /// `#[borsh(schema(name = ...))]` of the enum, for declarations of inner structs.
+    schema: Option<&'a str>,
+}
+
 struct DiscriminantInfo<'a> {
     variant_idx: usize,
     discriminants: &'a Discriminants,
@@ -112,17 +155,27 @@ fn process_variant(
     variant: &Variant,
     discriminant_info: DiscriminantInfo,
     cratename: &Path,
-    enum_name: &str,
+    enum_name: EnumName,
+    big_endian: bool,
     enum_generics: &Generics,
     generics_output: &mut schema::GenericsOutput,
 ) -> syn::Result<VariantOutput> {
     let variant_name = variant.ident.to_token_stream().to_string();
-    let full_variant_name = format!("{}{}", enum_name, variant_name);
+    let full_variant_name = format!("{}{}", enum_name.rust, variant_name);
     let full_variant_ident = Ident::new(&full_variant_name, Span::call_site());
+    let full_variant_schema_name = enum_name
+        .schema
+        .map(|schema_name| format!("{}{}", schema_name, variant_name));
 
     schema::visit_struct_fields(&variant.fields, &mut generics_output.params_visitor)?;
-    let (inner_struct, inner_struct_generics) =
-        inner_struct_definition(variant, cratename, &full_variant_ident, enum_generics);
+    let (inner_struct, inner_struct_generics) = inner_struct_definition(
+        variant,
+        cratename,
+        big_endian,
+        &full_variant_ident,
+        full_variant_schema_name,
+        enum_generics,
+    );
     let (_ig, inner_struct_ty_generics, _wc) = inner_struct_generics.split_for_impl();
 
     let variant_type = quote! {
@@ -132,8 +185,8 @@ fn process_variant(
 
     Ok(VariantOutput {
         inner_struct,
-        add_definitions_recursively_call: quote! {
-            #variant_type::add_definitions_recursively(definitions);
+        collect_definitions_call: quote! {
+            #variant_type::collect_definitions(collector);
         },
         variant_entry: quote! {
             (u8::from(#discriminant_value) as i64,
@@ -146,7 +199,9 @@ fn process_variant(
 fn inner_struct_definition(
     variant: &Variant,
     cratename: &Path,
+    big_endian: bool,
     inner_struct_ident: &Ident,
+    inner_struct_schema_name: Option<String>,
     enum_generics: &Generics,
 ) -> (TokenStream2, Generics) {
     let transformed_fields = transform_variant_fields(variant.fields.clone());
@@ -170,10 +225,14 @@ fn inner_struct_definition(
         semi_token: Some(Default::default()),
     };
     let crate_str = syn::LitStr::new(&cratename.to_token_stream().to_string(), Span::call_site());
+    // container-level `big_endian` applies to fields of the variant
+    let big_endian = big_endian.then(|| quote! { , big_endian });
+    // `#[borsh(schema(name = "..."))]` of the enum applies to declarations of its variants
+    let schema_name = inner_struct_schema_name.map(|name| quote! { , schema(name = #name) });
     let inner_struct = quote! {
         #[allow(dead_code)]
         #[derive(#cratename::BorshSchema)]
-        #[borsh(crate = #crate_str)]
+        #[borsh(crate = #crate_str #big_endian #schema_name)]
         #inner_struct
     };
     (inner_struct, inner_struct_generics)
@@ -513,4 +572,104 @@ mod tests {
 
         local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
     }
+
+    #[test]
+    fn other_variant_with_bytes() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum Message {
+                Ping,
+                Data(Vec<u8>),
+                #[borsh(other)]
+                Unknown(u8, Vec<u8>),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn other_variant_tag_only() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum Status {
+                Active,
+                Inactive,
+                #[borsh(other)]
+                Unknown(u8),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn remote_enum() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(remote = "other::Shape")]
+            enum ShapeDef {
+                Dot,
+                Circle { radius: u32 },
+                Line(i16, i16),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn big_endian_enum() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(big_endian)]
+            enum Message {
+                Ping,
+                Data { id: u32, payload: Vec<u8> },
+                Pair(i16, String),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn discriminator_enum() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(discriminator = [1, 2, 3, 4])]
+            enum Message {
+                Ping,
+                Data { id: u32 },
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn schema_name_enum() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(schema(name = "v2::Message"))]
+            enum Message {
+                Ping,
+                Data { id: u32 },
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
 }
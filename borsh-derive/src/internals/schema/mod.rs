@@ -4,13 +4,14 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::{
     punctuated::Punctuated, token::Comma, Field, Fields, GenericParam, Generics, Ident, Path, Type,
-    WherePredicate,
+    Visibility, WherePredicate,
 };
 
 use crate::internals::{attributes::field, generics};
 
 pub mod enums;
 pub mod structs;
+pub mod unions;
 
 struct GenericsOutput {
     params_visitor: generics::FindTyParams,
@@ -39,6 +40,62 @@ impl GenericsOutput {
     }
 }
 
+/// Name of the item in schema, which is the name given by `#[borsh(schema(name = "..."))]`,
+/// or the name of the remote type for `#[borsh(remote = "...")]` items.
+fn item_name(ident: &Ident, remote: Option<&Path>, schema_name: Option<String>) -> String {
+    if let Some(schema_name) = schema_name {
+        return schema_name;
+    }
+    match remote.and_then(|remote| remote.segments.last()) {
+        Some(segment) => segment.ident.to_string(),
+        None => ident.to_string(),
+    }
+}
+
+/// Path to `declaration` function of the item, which is an inherent function
+/// for `#[borsh(remote = "...")]` items.
+fn self_declaration(remote: Option<&Path>, cratename: &Path) -> TokenStream2 {
+    match remote {
+        Some(..) => quote! { Self::declaration },
+        None => quote! { <Self as #cratename::BorshSchema>::declaration },
+    }
+}
+
+/// Call, which adds `definition` of the item, prefixed with its discriminator
+/// for `#[borsh(discriminator)]` items.
+fn add_self_definition(
+    self_declaration: &TokenStream2,
+    discriminator: Option<Vec<u8>>,
+    cratename: &Path,
+) -> TokenStream2 {
+    match discriminator {
+        Some(bytes) => quote! {
+            #cratename::discriminator::add_definition(#self_declaration(), &[#(#bytes),*], definition, collector);
+        },
+        None => quote! {
+            collector.add_definition(#self_declaration(), definition);
+        },
+    }
+}
+
+/// `add_definitions_recursively` function of the item, which panics on conflicts
+/// recorded by `collect_definitions` function of the item.
+fn add_definitions_recursively(
+    remote: Option<&Path>,
+    vis: Option<&Visibility>,
+    cratename: &Path,
+) -> TokenStream2 {
+    let collect_definitions = match remote {
+        Some(..) => quote! { Self::collect_definitions },
+        None => quote! { <Self as #cratename::BorshSchema>::collect_definitions },
+    };
+    quote! {
+        #vis fn add_definitions_recursively(definitions: &mut #cratename::__private::maybestd::collections::BTreeMap<#cratename::schema::Declaration, #cratename::schema::Definition>) {
+            #cratename::schema::DefinitionsCollector::collect_or_panic(definitions, #collect_definitions);
+        }
+    }
+}
+
 fn declaration(ident_str: &str, cratename: Path, params_for_bounds: Vec<Type>) -> TokenStream2 {
     // Generate function that returns the name of the type.
     let mut declaration_params = vec![];
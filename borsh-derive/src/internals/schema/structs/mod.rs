@@ -1,8 +1,11 @@
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{quote, ToTokens};
-use syn::{ExprPath, Fields, Ident, ItemStruct, Path, Type};
+use syn::{ext::IdentExt, ExprPath, Fields, Ident, ItemStruct, Path, Type};
 
-use crate::internals::{attributes::field, generics, schema};
+use crate::internals::{
+    attributes::{field, item, PAD, PAD_AFTER, RESERVED, RESERVED_AFTER},
+    generics, schema,
+};
 
 /// function which computes derive output [proc_macro2::TokenStream]
 /// of code, which computes declaration of a single field, which is later added to
@@ -31,117 +34,176 @@ fn field_declaration_output(
 }
 
 /// function which computes derive output [proc_macro2::TokenStream]
-/// of code, which adds definitions of a field to the output `definitions: &mut BTreeMap`
+/// of code, which adds definitions of a field to the output `collector: &mut DefinitionsCollector`
 fn field_definitions_output(
     field_type: &Type,
     cratename: &Path,
-    definitions_override: Option<ExprPath>,
+    definitions_override: Option<field::SchemaDefinitions>,
 ) -> TokenStream2 {
-    let default_path: ExprPath = syn::parse2(
-        quote! { <#field_type as #cratename::BorshSchema>::add_definitions_recursively },
-    )
-    .unwrap();
-    let path = definitions_override.unwrap_or(default_path);
-
-    quote! {
-        #path(definitions);
+    match definitions_override {
+        None => quote! {
+            <#field_type as #cratename::BorshSchema>::collect_definitions(collector);
+        },
+        Some(field::SchemaDefinitions::Collect(path)) => quote! {
+            #path(collector);
+        },
+        Some(field::SchemaDefinitions::AddRecursively(path)) => quote! {
+            #path(collector.definitions_mut());
+        },
     }
 }
 
 pub fn process(input: &ItemStruct, cratename: Path) -> syn::Result<TokenStream2> {
+    let attributes = item::Attributes::parse(&input.attrs)?;
     let name = &input.ident;
-    let struct_name = name.to_token_stream().to_string();
+    let remote = item::contains_remote(&attributes);
+    let struct_name = schema::item_name(
+        name,
+        remote.as_ref(),
+        item::contains_schema_name(&attributes),
+    );
+    let self_declaration = schema::self_declaration(remote.as_ref(), &cratename);
     let generics = generics::without_defaults(&input.generics);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let mut where_clause = generics::default_where(where_clause);
     let mut generics_output = schema::GenericsOutput::new(&generics);
-    let (struct_fields, add_definitions_recursively) =
-        process_fields(&cratename, &input.fields, &mut generics_output)?;
-
-    let add_definitions_recursively = quote! {
-        fn add_definitions_recursively(definitions: &mut #cratename::__private::maybestd::collections::BTreeMap<#cratename::schema::Declaration, #cratename::schema::Definition>) {
+    let big_endian = item::contains_big_endian(&attributes);
+    let add_self_definition = schema::add_self_definition(
+        &self_declaration,
+        item::contains_discriminator(&attributes, name),
+        &cratename,
+    );
+    let (struct_fields, collect_definitions) = process_fields(
+        &cratename,
+        big_endian,
+        &input.fields,
+        item::contains_trailing_padding(&attributes),
+        &mut generics_output,
+    )?;
+
+    let vis = remote.as_ref().map(|_| &input.vis);
+    let add_definitions_recursively =
+        schema::add_definitions_recursively(remote.as_ref(), vis, &cratename);
+    let collect_definitions = quote! {
+        #vis fn collect_definitions(collector: &mut #cratename::schema::DefinitionsCollector<'_>) {
             #struct_fields
             let definition = #cratename::schema::Definition::Struct { fields };
 
-            let no_recursion_flag = definitions.get(&<Self as #cratename::BorshSchema>::declaration()).is_none();
-            #cratename::schema::add_definition(<Self as #cratename::BorshSchema>::declaration(), definition, definitions);
+            let no_recursion_flag = collector.definitions().get(&#self_declaration()).is_none();
+            #add_self_definition
             if no_recursion_flag {
-                #add_definitions_recursively
+                #collect_definitions
             }
         }
     };
 
     let (predicates, declaration) = generics_output.result(&struct_name, &cratename);
     where_clause.predicates.extend(predicates);
+    if remote.is_some() {
+        return Ok(quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #vis fn declaration() -> #cratename::schema::Declaration {
+                    #declaration
+                }
+                #add_definitions_recursively
+                #collect_definitions
+            }
+        });
+    }
     Ok(quote! {
         impl #impl_generics #cratename::BorshSchema for #name #ty_generics #where_clause {
             fn declaration() -> #cratename::schema::Declaration {
                 #declaration
             }
             #add_definitions_recursively
+            #collect_definitions
         }
     })
 }
 
 fn process_fields(
     cratename: &Path,
+    big_endian: bool,
     fields: &Fields,
+    trailing: Option<item::TrailingPadding>,
     generics: &mut schema::GenericsOutput,
 ) -> syn::Result<(TokenStream2, TokenStream2)> {
-    let mut struct_fields = TokenStream2::new();
-    let mut add_definitions_recursively = TokenStream2::new();
+    let mut collect_definitions = TokenStream2::new();
 
     // Generate function that returns the schema of required types.
     let mut fields_vec = vec![];
     schema::visit_struct_fields(fields, &mut generics.params_visitor)?;
-    match fields {
-        Fields::Named(fields) => {
-            for field in &fields.named {
-                process_field(
-                    field,
-                    cratename,
-                    &mut fields_vec,
-                    &mut add_definitions_recursively,
-                )?;
-            }
-            if !fields_vec.is_empty() {
-                struct_fields = quote! {
-                    let fields = #cratename::schema::Fields::NamedFields(#cratename::__private::maybestd::vec![#(#fields_vec),*]);
-                };
-            }
-        }
-        Fields::Unnamed(fields) => {
-            for field in &fields.unnamed {
-                process_field(
-                    field,
-                    cratename,
-                    &mut fields_vec,
-                    &mut add_definitions_recursively,
-                )?;
-            }
-            if !fields_vec.is_empty() {
-                struct_fields = quote! {
-                    let fields = #cratename::schema::Fields::UnnamedFields(#cratename::__private::maybestd::vec![#(#fields_vec),*]);
-                };
-            }
-        }
-        Fields::Unit => {}
+    for field in fields {
+        process_field(
+            field,
+            cratename,
+            big_endian,
+            &mut fields_vec,
+            &mut collect_definitions,
+        )?;
+    }
+    let named = matches!(fields, Fields::Named(_));
+    if let Some(item::TrailingPadding { len, reserved }) = trailing {
+        // zero bytes are described as a pseudo-field of `[u8; len]` type after the last field
+        let padding_name = named.then(|| {
+            let symbol = if reserved { RESERVED_AFTER } else { PAD_AFTER };
+            Ident::new(&format!("_{}", symbol.0), Span::call_site())
+        });
+        let padding_type: Type = syn::parse_quote! { [u8; #len] };
+        fields_vec.push(field_declaration_output(
+            padding_name.as_ref(),
+            &padding_type,
+            cratename,
+            None,
+        ));
+        collect_definitions.extend(field_definitions_output(&padding_type, cratename, None));
     }
 
-    if fields_vec.is_empty() {
-        struct_fields = quote! {
+    let struct_fields = if fields_vec.is_empty() {
+        quote! {
             let fields = #cratename::schema::Fields::Empty;
-        };
-    }
-    Ok((struct_fields, add_definitions_recursively))
+        }
+    } else if named {
+        quote! {
+            let fields = #cratename::schema::Fields::NamedFields(#cratename::__private::maybestd::vec![#(#fields_vec),*]);
+        }
+    } else {
+        quote! {
+            let fields = #cratename::schema::Fields::UnnamedFields(#cratename::__private::maybestd::vec![#(#fields_vec),*]);
+        }
+    };
+    Ok((struct_fields, collect_definitions))
 }
 fn process_field(
     field: &syn::Field,
     cratename: &Path,
+    big_endian: bool,
     fields_vec: &mut Vec<TokenStream2>,
-    add_definitions_recursively: &mut TokenStream2,
+    collect_definitions: &mut TokenStream2,
 ) -> syn::Result<()> {
-    let parsed = field::Attributes::parse(&field.attrs)?;
+    let parsed = field::Attributes::parse(&field.attrs)?.inherit_big_endian(big_endian);
+    if let Some(len) = parsed.padding_len() {
+        // zero bytes are described as a pseudo-field of `[u8; len]` type before the field
+        let padding_name = field.ident.as_ref().map(|field_name| {
+            let prefix = if parsed.pad.is_some() {
+                PAD.0
+            } else {
+                RESERVED.0
+            };
+            Ident::new(
+                &format!("_{}_{}", prefix, field_name.unraw()),
+                Span::call_site(),
+            )
+        });
+        let padding_type: Type = syn::parse_quote! { [u8; #len] };
+        fields_vec.push(field_declaration_output(
+            padding_name.as_ref(),
+            &padding_type,
+            cratename,
+            None,
+        ));
+        collect_definitions.extend(field_definitions_output(&padding_type, cratename, None));
+    }
     if !parsed.skip {
         let field_name = field.ident.as_ref();
         let field_type = &field.ty;
@@ -149,12 +211,12 @@ fn process_field(
             field_name,
             field_type,
             cratename,
-            parsed.schema_declaration(),
+            parsed.schema_declaration(field_type, cratename)?,
         ));
-        add_definitions_recursively.extend(field_definitions_output(
+        collect_definitions.extend(field_definitions_output(
             field_type,
             cratename,
-            parsed.schema_definitions(),
+            parsed.schema_definitions(field_type, cratename)?,
         ));
     }
     Ok(())
@@ -506,6 +568,27 @@ mod tests {
         local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
     }
 
+    #[test]
+    fn schema_as_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(big_endian)]
+            struct A<K, V> {
+                #[borsh(
+                    serialize_with = "third_party_impl::serialize",
+                    schema(as = "BTreeMap<K, V>")
+                )]
+                x: ThirdParty<K, V>,
+                #[borsh(schema(as = "[u8; 4]"))]
+                y: u32,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
     #[test]
     fn schema_param_override3() {
         let item_struct: ItemStruct = syn::parse2(quote! {
@@ -525,4 +608,187 @@ mod tests {
 
         local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
     }
+
+    #[test]
+    fn check_length_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(length = u8)]
+                x: Vec<String>,
+                z: u64,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn remote_struct() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(remote = "other::Config")]
+            struct ConfigDef<T> {
+                name: String,
+                #[borsh(skip)]
+                cache: Vec<u8>,
+                value: T,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_with_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A<K, V> {
+                #[borsh(with = "third_party_impl")]
+                x: ThirdParty<K, V>,
+                y: u64,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_big_endian_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(big_endian)]
+                x: u64,
+                #[borsh(big_endian, length = u8)]
+                y: Vec<f32>,
+                #[borsh(big_endian)]
+                z: Option<i16>,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_big_endian_container_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(big_endian)]
+            struct A {
+                x: u32,
+                y: String,
+                z: BTreeMap<String, u64>,
+                #[borsh(varint)]
+                w: u64,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_discriminator_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(discriminator)]
+            struct Counter<T> {
+                value: T,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_discriminator_custom_bytes_remote() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(discriminator = [0xca, 0xfe], remote = "other::Counter")]
+            struct CounterDef {
+                value: u8,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_schema_name_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(schema(name = "v1::Config"))]
+            struct Config<T> {
+                value: T,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_pad_and_reserved_attrs() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct Header {
+                version: u8,
+                #[borsh(pad = 3)]
+                length: u32,
+                #[borsh(reserved = RESERVED_LEN, skip)]
+                cache: u64,
+                #[borsh(reserved = 4)]
+                _reserved: (),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_trailing_padding_attrs() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(reserved_after = 4)]
+            struct Header {
+                version: u8,
+                #[borsh(pad = 3)]
+                length: u32,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_pad_after_on_unit_struct() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(pad_after = PAD_LEN)]
+            struct Filler;
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
 }
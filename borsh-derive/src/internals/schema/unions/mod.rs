@@ -0,0 +1,73 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{ItemUnion, Path};
+
+use crate::internals::{attributes::item, schema, unions};
+
+pub fn process(input: &ItemUnion, cratename: Path) -> syn::Result<TokenStream2> {
+    unions::check(input)?;
+    let attributes = item::Attributes::parse(&input.attrs)?;
+    let name = &input.ident;
+    let union_name = match item::contains_schema_name(&attributes) {
+        Some(schema_name) => schema_name,
+        None => name.to_token_stream().to_string(),
+    };
+    let declaration = schema::declaration(&union_name, cratename.clone(), vec![]);
+    let add_definitions_recursively = schema::add_definitions_recursively(None, None, &cratename);
+
+    let mut variants_defs = vec![];
+    let mut add_recursive_defs = TokenStream2::new();
+    for (field_idx, field) in input.fields.named.iter().enumerate() {
+        let field_idx = field_idx as i64;
+        let field_name = field.ident.to_token_stream().to_string();
+        let field_type = &field.ty;
+        variants_defs.push(quote! {
+            (#field_idx, #field_name.into(), <#field_type as #cratename::BorshSchema>::declaration())
+        });
+        add_recursive_defs.extend(quote! {
+            <#field_type as #cratename::BorshSchema>::collect_definitions(collector);
+        });
+    }
+
+    Ok(quote! {
+        impl #cratename::BorshSchema for #name {
+            fn declaration() -> #cratename::schema::Declaration {
+                #declaration
+            }
+            #add_definitions_recursively
+            fn collect_definitions(collector: &mut #cratename::schema::DefinitionsCollector<'_>) {
+                #add_recursive_defs
+                let definition = #cratename::schema::Definition::Enum {
+                    tag_width: 0,
+                    variants: #cratename::__private::maybestd::vec![#(#variants_defs),*],
+                };
+                collector.add_definition(<Self as #cratename::BorshSchema>::declaration(), definition);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::internals::test_helpers::{
+        default_cratename, local_insta_assert_snapshot, pretty_print_syn_str,
+    };
+
+    use super::*;
+
+    #[test]
+    fn simple_union() {
+        let item_union: ItemUnion = syn::parse2(quote! {
+            #[repr(C)]
+            union A {
+                x: u32,
+                y: [u8; 4],
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_union, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+}
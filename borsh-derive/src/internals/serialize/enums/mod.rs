@@ -1,64 +1,205 @@
+use std::collections::BTreeMap;
+
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::{Fields, Ident, ItemEnum, Path, Variant};
 
 use crate::internals::{
-    attributes::{field, item, BoundType},
+    attributes::{field, item, variant, BoundType},
     enum_discriminant::Discriminants,
     generics, serialize,
 };
 
 pub fn process(input: &ItemEnum, cratename: Path) -> syn::Result<TokenStream2> {
+    let attributes = item::Attributes::parse(&input.attrs)?;
     let enum_ident = &input.ident;
     let generics = generics::without_defaults(&input.generics);
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-    let mut where_clause = generics::default_where(where_clause);
+    let (impl_generics, ty_generics, inherent_where_clause) = generics.split_for_impl();
+    let mut where_clause = generics::default_where(inherent_where_clause);
     let mut generics_output = serialize::GenericsOutput::new(&generics);
     let mut all_variants_idx_body = TokenStream2::new();
     let mut fields_body = TokenStream2::new();
-    let use_discriminant = item::contains_use_discriminant(input)?;
+    let use_discriminant = item::contains_use_discriminant(input, &attributes)?;
     let discriminants = Discriminants::new(&input.variants);
     let mut has_unit_variant = false;
+    let other = variant::other_variant(input)?;
+    let remote = item::contains_remote(&attributes);
+    let big_endian = item::contains_big_endian(&attributes);
+    let (discriminator_impl, write_discriminator) = serialize::discriminator_output(
+        item::contains_discriminator(&attributes, enum_ident),
+        enum_ident,
+        &generics,
+        &cratename,
+    );
+    let enum_path: Path = remote.clone().unwrap_or_else(|| enum_ident.clone().into());
+    let mut tags = vec![];
+    let with_tag_consts = item::contains_tag_consts(&attributes);
+    let mut tag_consts = TokenStream2::new();
+    let mut tag_const_variants = BTreeMap::new();
 
     for (variant_idx, variant) in input.variants.iter().enumerate() {
         let variant_ident = &variant.ident;
+        if let Some(other) = other
+            .as_ref()
+            .filter(|other| std::ptr::eq(other.variant, variant))
+        {
+            // unknown variant is written back as it was read, with its length-prefixed bytes
+            all_variants_idx_body.extend(quote!(
+                #enum_path::#variant_ident(tag, ..) => *tag,
+            ));
+            if other.captures_bytes {
+                fields_body.extend(quote!(
+                    #enum_path::#variant_ident(_, bytes) => {
+                        #cratename::ser::BorshSerialize::serialize(bytes, writer)?;
+                    }
+                ));
+            } else {
+                has_unit_variant = true;
+            }
+            continue;
+        }
         let discriminant_value = discriminants.get(variant_ident, use_discriminant, variant_idx)?;
+        if with_tag_consts {
+            let tag_const = tag_const_ident(variant_ident);
+            if let Some(previous) = tag_const_variants.insert(tag_const.to_string(), variant_ident)
+            {
+                return Err(syn::Error::new_spanned(
+                    variant_ident,
+                    format!(
+                        "variants `{}` and `{}` have the same tag constant `{}`",
+                        previous, variant_ident, tag_const
+                    ),
+                ));
+            }
+            let vis = &input.vis;
+            tag_consts.extend(quote! {
+                #vis const #tag_const: u8 = #discriminant_value;
+            });
+        }
+        tags.push(discriminant_value.clone());
         let variant_output = process_variant(
             variant,
-            enum_ident,
+            &enum_path,
             &discriminant_value,
             &cratename,
+            big_endian,
             &mut generics_output,
         )?;
         all_variants_idx_body.extend(variant_output.variant_idx_body);
         match variant_output.body {
             VariantBody::Unit => has_unit_variant = true,
             VariantBody::Fields(VariantFields { header, body }) => fields_body.extend(quote!(
-                #enum_ident::#variant_ident #header => {
+                #enum_path::#variant_ident #header => {
                     #body
                 }
             )),
         }
     }
-    let fields_body = optimize_fields_body(fields_body, has_unit_variant);
     generics_output.extend(&mut where_clause, &cratename);
+    let check_other = |value: TokenStream2| {
+        other.as_ref().map(|other| {
+            let variant_ident = &other.variant.ident;
+            quote! {
+                if let #enum_path::#variant_ident(tag, ..) = #value {
+                    if [#(#tags),*].contains(tag) {
+                        return Err(#cratename::io::Error::new(
+                            #cratename::io::ErrorKind::InvalidData,
+                            #cratename::__private::maybestd::format!("Unknown variant tag {:?} is a tag of a known variant", tag),
+                        ));
+                    }
+                }
+            }
+        })
+    };
+
+    if let Some(remote) = remote {
+        let vis = &input.vis;
+        let check_other = check_other(quote! { value });
+        let fields_body = optimize_fields_body(fields_body, has_unit_variant, quote! { value });
+        return Ok(quote! {
+            impl #impl_generics #enum_ident #ty_generics #where_clause {
+                #vis fn serialize<__W: #cratename::io::Write>(value: &#remote #ty_generics, writer: &mut __W) -> ::core::result::Result<(), #cratename::io::Error> {
+                    #check_other
+                    #write_discriminator
+                    let variant_idx: u8 = match value {
+                        #all_variants_idx_body
+                    };
+                    writer.write_all(&variant_idx.to_le_bytes())?;
+
+                    #fields_body
+                    Ok(())
+                }
+            }
+            #discriminator_impl
+        });
+    }
+    let check_other = check_other(quote! { self });
+    let tag_consts_impl = with_tag_consts.then(|| {
+        quote! {
+            #[allow(dead_code)]
+            impl #impl_generics #enum_ident #ty_generics #inherent_where_clause {
+                #tag_consts
+            }
+        }
+    });
+    let fields_body = optimize_fields_body(fields_body, has_unit_variant, quote! { self });
 
     Ok(quote! {
         impl #impl_generics #cratename::ser::BorshSerialize for #enum_ident #ty_generics #where_clause {
             fn serialize<__W: #cratename::io::Write>(&self, writer: &mut __W) -> ::core::result::Result<(), #cratename::io::Error> {
-                let variant_idx: u8 = match self {
-                    #all_variants_idx_body
-                };
+                #check_other
+                #write_discriminator
+                let variant_idx: u8 = <Self as #cratename::ser::EnumExt>::variant_tag(self);
                 writer.write_all(&variant_idx.to_le_bytes())?;
+                <Self as #cratename::ser::EnumExt>::serialize_variant_body(self, writer)
+            }
+        }
+
+        impl #impl_generics #cratename::ser::EnumExt for #enum_ident #ty_generics #where_clause {
+            const TAGS: &'static [u8] = &[#(#tags),*];
+
+            fn variant_tag(&self) -> u8 {
+                match self {
+                    #all_variants_idx_body
+                }
+            }
 
+            fn serialize_variant_body<__W: #cratename::io::Write>(&self, writer: &mut __W) -> ::core::result::Result<(), #cratename::io::Error> {
                 #fields_body
                 Ok(())
             }
         }
+
+        #tag_consts_impl
+        #discriminator_impl
     })
 }
 
-fn optimize_fields_body(fields_body: TokenStream2, has_unit_variant: bool) -> TokenStream2 {
+/// Name of the associated constant with variant's tag, e.g. `MY_VARIANT_TAG` for `MyVariant`.
+fn tag_const_ident(variant_ident: &Ident) -> Ident {
+    let name = variant_ident.to_string();
+    let name = name.trim_start_matches("r#");
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = matches!(chars.get(i + 1), Some(next) if next.is_lowercase());
+            if prev != '_' && (prev.is_lowercase() || prev.is_ascii_digit() || next_is_lower) {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_uppercase());
+    }
+    result.push_str("_TAG");
+    Ident::new(&result, variant_ident.span())
+}
+
+fn optimize_fields_body(
+    fields_body: TokenStream2,
+    has_unit_variant: bool,
+    receiver: TokenStream2,
+) -> TokenStream2 {
     if fields_body.is_empty() {
         // If we no variants with fields, there's nothing to match against. Just
         // re-use the empty token stream.
@@ -77,7 +218,7 @@ fn optimize_fields_body(fields_body: TokenStream2, has_unit_variant: bool) -> To
         // variant and add a catch-all at the bottom if we do have unit
         // variants.
         quote!(
-            match self {
+            match #receiver {
                 #fields_body
                 #unit_fields_catchall
             }
@@ -125,9 +266,10 @@ struct VariantOutput {
 
 fn process_variant(
     variant: &Variant,
-    enum_ident: &Ident,
+    enum_path: &Path,
     discriminant_value: &TokenStream2,
     cratename: &Path,
+    big_endian: bool,
     generics: &mut serialize::GenericsOutput,
 ) -> syn::Result<VariantOutput> {
     let variant_ident = &variant.ident;
@@ -136,12 +278,19 @@ fn process_variant(
             let mut variant_fields = VariantFields::default();
             for field in &fields.named {
                 let field_id = serialize::FieldId::Enum(field.ident.clone().unwrap());
-                process_field(field, field_id, cratename, generics, &mut variant_fields)?;
+                process_field(
+                    field,
+                    field_id,
+                    cratename,
+                    big_endian,
+                    generics,
+                    &mut variant_fields,
+                )?;
             }
             VariantOutput {
                 body: VariantBody::Fields(variant_fields.named_header()),
                 variant_idx_body: quote!(
-                    #enum_ident::#variant_ident {..} => #discriminant_value,
+                    #enum_path::#variant_ident {..} => #discriminant_value,
                 ),
             }
         }
@@ -149,19 +298,26 @@ fn process_variant(
             let mut variant_fields = VariantFields::default();
             for (field_idx, field) in fields.unnamed.iter().enumerate() {
                 let field_id = serialize::FieldId::new_enum_unnamed(field_idx)?;
-                process_field(field, field_id, cratename, generics, &mut variant_fields)?;
+                process_field(
+                    field,
+                    field_id,
+                    cratename,
+                    big_endian,
+                    generics,
+                    &mut variant_fields,
+                )?;
             }
             VariantOutput {
                 body: VariantBody::Fields(variant_fields.unnamed_header()),
                 variant_idx_body: quote!(
-                    #enum_ident::#variant_ident(..) => #discriminant_value,
+                    #enum_path::#variant_ident(..) => #discriminant_value,
                 ),
             }
         }
         Fields::Unit => VariantOutput {
             body: VariantBody::Unit,
             variant_idx_body: quote!(
-                #enum_ident::#variant_ident => #discriminant_value,
+                #enum_path::#variant_ident => #discriminant_value,
             ),
         },
     };
@@ -172,10 +328,11 @@ fn process_field(
     field: &syn::Field,
     field_id: serialize::FieldId,
     cratename: &Path,
+    big_endian: bool,
     generics: &mut serialize::GenericsOutput,
     output: &mut VariantFields,
 ) -> syn::Result<()> {
-    let parsed = field::Attributes::parse(&field.attrs)?;
+    let parsed = field::Attributes::parse(&field.attrs)?.inherit_big_endian(big_endian);
 
     let needs_bounds_derive = parsed.needs_bounds_derive(BoundType::Serialize);
     generics
@@ -187,8 +344,12 @@ fn process_field(
         output.header.extend(field_variant_header);
     }
 
+    output
+        .body
+        .extend(serialize::padding_output(&parsed, cratename));
     if !parsed.skip {
-        let delta = field_id.serialize_output(cratename, parsed.serialize_with);
+        let delta =
+            field_id.serialize_output(cratename, parsed.serialize_override(&field.ty, cratename)?);
         output.body.extend(delta);
         if needs_bounds_derive {
             generics.serialize_visitor.visit_field(field);
@@ -200,7 +361,8 @@ fn process_field(
 #[cfg(test)]
 mod tests {
     use crate::internals::test_helpers::{
-        default_cratename, local_insta_assert_snapshot, pretty_print_syn_str,
+        default_cratename, local_insta_assert_debug_snapshot, local_insta_assert_snapshot,
+        pretty_print_syn_str,
     };
 
     use super::*;
@@ -495,4 +657,154 @@ mod tests {
 
         local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
     }
+
+    #[test]
+    fn tag_consts_enum() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(tag_consts)]
+            pub enum X {
+                A(u16),
+                MyVariant,
+            }
+        })
+        .unwrap();
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_tag_consts_clash() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(tag_consts)]
+            enum X {
+                FooBar,
+                Foo_Bar,
+            }
+        })
+        .unwrap();
+        let actual = process(&item_enum, default_cratename());
+
+        let err = match actual {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn test_tag_const_ident() {
+        for (variant, expected) in [
+            ("A", "A_TAG"),
+            ("MyVariant", "MY_VARIANT_TAG"),
+            ("HTTPRequest", "HTTP_REQUEST_TAG"),
+            ("V2Beta", "V2_BETA_TAG"),
+            ("snake_case", "SNAKE_CASE_TAG"),
+        ] {
+            let variant = Ident::new(variant, proc_macro2::Span::call_site());
+            assert_eq!(tag_const_ident(&variant).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn other_variant_with_bytes() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum Message {
+                Ping,
+                Data(Vec<u8>),
+                #[borsh(other)]
+                Unknown(u8, Vec<u8>),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn other_variant_tag_only() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum Status {
+                Active,
+                Inactive,
+                #[borsh(other)]
+                Unknown(u8),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn remote_enum() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(remote = "other::Shape")]
+            enum ShapeDef {
+                Dot,
+                Circle { radius: u32 },
+                Line(i16, i16),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn big_endian_enum() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(big_endian)]
+            enum Message {
+                Ping,
+                Data { id: u32, payload: Vec<u8> },
+                Pair(i16, String),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn discriminator_enum() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            #[borsh(discriminator = [1, 2, 3, 4])]
+            enum Message {
+                Ping,
+                Data { id: u32 },
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn pad_enum_fields() {
+        let item_enum: ItemEnum = syn::parse2(quote! {
+            enum Frame {
+                Data(u8, #[borsh(pad = 3)] u32),
+                Ack {
+                    #[borsh(reserved = 2)]
+                    seq: u16,
+                },
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_enum, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
 }
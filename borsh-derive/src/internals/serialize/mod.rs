@@ -3,7 +3,10 @@ use quote::quote;
 use std::convert::TryFrom;
 use syn::{Expr, ExprPath, Generics, Ident, Index, Path};
 
-use super::generics;
+use super::{
+    attributes::{field, item},
+    generics,
+};
 
 pub mod enums;
 pub mod structs;
@@ -30,6 +33,55 @@ impl GenericsOutput {
     }
 }
 
+/// `#[borsh(discriminator)]`: implementation of `Discriminator` trait for the item
+/// and the statement, which writes the discriminator before the item's body
+fn discriminator_output(
+    discriminator: Option<Vec<u8>>,
+    name: &Ident,
+    generics: &Generics,
+    cratename: &Path,
+) -> (TokenStream2, TokenStream2) {
+    let bytes = match discriminator {
+        Some(bytes) => bytes,
+        None => return (TokenStream2::new(), TokenStream2::new()),
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let discriminator_impl = quote! {
+        impl #impl_generics #cratename::discriminator::Discriminator for #name #ty_generics #where_clause {
+            const DISCRIMINATOR: &'static [u8] = &[#(#bytes),*];
+        }
+    };
+    let write_discriminator = quote! {
+        writer.write_all(<Self as #cratename::discriminator::Discriminator>::DISCRIMINATOR)?;
+    };
+    (discriminator_impl, write_discriminator)
+}
+
+/// `#[borsh(pad = ...)]` or `#[borsh(reserved = ...)]`: the statement, which writes
+/// zero bytes before the field
+fn padding_output(parsed: &field::Attributes, cratename: &Path) -> TokenStream2 {
+    match parsed.padding_len() {
+        Some(len) => quote! {
+            #cratename::padding::write_zeros(writer, #len)?;
+        },
+        None => TokenStream2::new(),
+    }
+}
+
+/// `#[borsh(pad_after = ...)]` or `#[borsh(reserved_after = ...)]`: the statement, which writes
+/// zero bytes after the last field of the struct
+fn trailing_padding_output(
+    trailing: Option<item::TrailingPadding>,
+    cratename: &Path,
+) -> TokenStream2 {
+    match trailing {
+        Some(item::TrailingPadding { len, .. }) => quote! {
+            #cratename::padding::write_zeros(writer, #len)?;
+        },
+        None => TokenStream2::new(),
+    }
+}
+
 pub enum FieldId {
     Struct(Ident),
     StructUnnamed(Index),
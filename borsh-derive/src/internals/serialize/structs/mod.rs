@@ -3,43 +3,151 @@ use quote::quote;
 use syn::{Fields, ItemStruct, Path};
 
 use crate::internals::{
-    attributes::{field, BoundType},
+    attributes::{field, item, BoundType},
     generics, serialize,
 };
 
 pub fn process(input: &ItemStruct, cratename: Path) -> syn::Result<TokenStream2> {
+    let attributes = item::Attributes::parse(&input.attrs)?;
+    if let Some(remote) = item::contains_remote(&attributes) {
+        return process_remote(input, &attributes, &remote, cratename);
+    }
     let name = &input.ident;
     let generics = generics::without_defaults(&input.generics);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let mut where_clause = generics::default_where(where_clause);
     let mut body = TokenStream2::new();
     let mut generics_output = serialize::GenericsOutput::new(&generics);
+    let big_endian = item::contains_big_endian(&attributes);
+    let (discriminator_impl, write_discriminator) = serialize::discriminator_output(
+        item::contains_discriminator(&attributes, name),
+        name,
+        &generics,
+        &cratename,
+    );
     match &input.fields {
         Fields::Named(fields) => {
             for field in &fields.named {
                 let field_id = serialize::FieldId::Struct(field.ident.clone().unwrap());
 
-                process_field(field, field_id, &cratename, &mut generics_output, &mut body)?;
+                process_field(
+                    field,
+                    field_id,
+                    &cratename,
+                    big_endian,
+                    &mut generics_output,
+                    &mut body,
+                )?;
             }
         }
         Fields::Unnamed(fields) => {
             for (field_idx, field) in fields.unnamed.iter().enumerate() {
                 let field_id = serialize::FieldId::new_struct_unnamed(field_idx)?;
 
-                process_field(field, field_id, &cratename, &mut generics_output, &mut body)?;
+                process_field(
+                    field,
+                    field_id,
+                    &cratename,
+                    big_endian,
+                    &mut generics_output,
+                    &mut body,
+                )?;
             }
         }
         Fields::Unit => {}
     }
+    body.extend(serialize::trailing_padding_output(
+        item::contains_trailing_padding(&attributes),
+        &cratename,
+    ));
     generics_output.extend(&mut where_clause, &cratename);
 
     Ok(quote! {
         impl #impl_generics #cratename::ser::BorshSerialize for #name #ty_generics #where_clause {
             fn serialize<__W: #cratename::io::Write>(&self, writer: &mut __W) -> ::core::result::Result<(), #cratename::io::Error> {
+                #write_discriminator
+                #body
+                Ok(())
+            }
+        }
+        #discriminator_impl
+    })
+}
+
+/// `#[borsh(remote = "...")]` generates `serialize` function, which serializes fields of the
+/// remote type, destructured with the pattern of the annotated struct
+fn process_remote(
+    input: &ItemStruct,
+    attributes: &item::Attributes,
+    remote: &Path,
+    cratename: Path,
+) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let generics = generics::without_defaults(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut where_clause = generics::default_where(where_clause);
+    let mut header = TokenStream2::new();
+    let mut body = TokenStream2::new();
+    let mut generics_output = serialize::GenericsOutput::new(&generics);
+    let big_endian = item::contains_big_endian(attributes);
+    let (discriminator_impl, write_discriminator) = serialize::discriminator_output(
+        item::contains_discriminator(attributes, name),
+        name,
+        &generics,
+        &cratename,
+    );
+    let pattern = match &input.fields {
+        Fields::Named(fields) => {
+            for field in &fields.named {
+                let field_id = serialize::FieldId::Enum(field.ident.clone().unwrap());
+                let skip = field::Attributes::parse(&field.attrs)?.skip;
+                header.extend(field_id.enum_variant_header(skip));
+                process_field(
+                    field,
+                    field_id,
+                    &cratename,
+                    big_endian,
+                    &mut generics_output,
+                    &mut body,
+                )?;
+            }
+            quote! { #remote { #header.. } }
+        }
+        Fields::Unnamed(fields) => {
+            for (field_idx, field) in fields.unnamed.iter().enumerate() {
+                let field_id = serialize::FieldId::new_enum_unnamed(field_idx)?;
+                let skip = field::Attributes::parse(&field.attrs)?.skip;
+                header.extend(field_id.enum_variant_header(skip));
+                process_field(
+                    field,
+                    field_id,
+                    &cratename,
+                    big_endian,
+                    &mut generics_output,
+                    &mut body,
+                )?;
+            }
+            quote! { #remote( #header ) }
+        }
+        Fields::Unit => quote! { _ },
+    };
+    body.extend(serialize::trailing_padding_output(
+        item::contains_trailing_padding(attributes),
+        &cratename,
+    ));
+    generics_output.extend(&mut where_clause, &cratename);
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #vis fn serialize<__W: #cratename::io::Write>(value: &#remote #ty_generics, writer: &mut __W) -> ::core::result::Result<(), #cratename::io::Error> {
+                let #pattern = value;
+                #write_discriminator
                 #body
                 Ok(())
             }
         }
+        #discriminator_impl
     })
 }
 
@@ -47,17 +155,20 @@ fn process_field(
     field: &syn::Field,
     field_id: serialize::FieldId,
     cratename: &Path,
+    big_endian: bool,
     generics: &mut serialize::GenericsOutput,
     body: &mut TokenStream2,
 ) -> syn::Result<()> {
-    let parsed = field::Attributes::parse(&field.attrs)?;
+    let parsed = field::Attributes::parse(&field.attrs)?.inherit_big_endian(big_endian);
     let needs_bounds_derive = parsed.needs_bounds_derive(BoundType::Serialize);
 
     generics
         .overrides
         .extend(parsed.collect_bounds(BoundType::Serialize));
+    body.extend(serialize::padding_output(&parsed, cratename));
     if !parsed.skip {
-        let delta = field_id.serialize_output(cratename, parsed.serialize_with);
+        let delta =
+            field_id.serialize_output(cratename, parsed.serialize_override(&field.ty, cratename)?);
         body.extend(delta);
 
         if needs_bounds_derive {
@@ -299,4 +410,308 @@ mod tests {
         };
         local_insta_assert_debug_snapshot!(err);
     }
+
+    #[test]
+    fn check_length_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(length = u8)]
+                x: Vec<String>,
+                #[borsh(length = u16)]
+                y: BTreeMap<u64, Vec<u8>>,
+                z: u64,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_length_unsupported_type() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(length = u8)]
+                x: u64,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename());
+
+        let err = match actual {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn check_varint_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(varint)]
+                x: u64,
+                #[borsh(varint)]
+                y: Vec<i32>,
+                #[borsh(varint)]
+                z: Option<String>,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_varint_unsupported_type() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(varint)]
+                x: u8,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename());
+
+        let err = match actual {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn check_max_len_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(max_len = 16)]
+                x: Vec<Vec<u8>>,
+                #[borsh(max_len = MAX_NAME_LEN, length = u8)]
+                y: Option<String>,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_max_len_unsupported_type() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(max_len = 16, varint)]
+                x: u64,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename());
+
+        let err = match actual {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn remote_struct() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(remote = "other::Config")]
+            struct ConfigDef<T> {
+                name: String,
+                #[borsh(skip)]
+                cache: Vec<u8>,
+                value: T,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_with_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A<K, V> {
+                #[borsh(with = "third_party_impl")]
+                x: ThirdParty<K, V>,
+                y: u64,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_big_endian_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(big_endian)]
+                x: u64,
+                #[borsh(big_endian, length = u8)]
+                y: Vec<f32>,
+                #[borsh(big_endian)]
+                z: Option<i16>,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_big_endian_container_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(big_endian)]
+            struct A {
+                x: u32,
+                y: String,
+                z: BTreeMap<String, u64>,
+                #[borsh(varint)]
+                w: u64,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_big_endian_container_unsupported_field() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(big_endian)]
+            struct A {
+                x: u32,
+                bytes: [u8; 4],
+                flags: (bool, Option<String>),
+                y: [u32; 4],
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename());
+
+        let err = match actual {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn check_big_endian_unsupported_type() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct A {
+                #[borsh(big_endian)]
+                x: String,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename());
+
+        let err = match actual {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn check_discriminator_attr() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(discriminator)]
+            struct Counter<T> {
+                value: T,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_discriminator_custom_bytes_remote() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(discriminator = [0xca, 0xfe], remote = "other::Counter")]
+            struct CounterDef {
+                value: u8,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_pad_and_reserved_attrs() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            struct Header {
+                version: u8,
+                #[borsh(pad = 3)]
+                length: u32,
+                #[borsh(reserved = RESERVED_LEN, skip)]
+                cache: u64,
+                #[borsh(reserved = 4)]
+                _reserved: (),
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_trailing_padding_attrs() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(reserved_after = 4)]
+            struct Header {
+                version: u8,
+                #[borsh(pad = 3)]
+                length: u32,
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_pad_after_on_unit_struct() {
+        let item_struct: ItemStruct = syn::parse2(quote! {
+            #[borsh(pad_after = PAD_LEN)]
+            struct Filler;
+        })
+        .unwrap();
+
+        let actual = process(&item_struct, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
 }
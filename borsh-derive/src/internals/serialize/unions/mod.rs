@@ -1,6 +1,114 @@
 use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
 use syn::{ItemUnion, Path};
 
-pub fn process(_input: &ItemUnion, _cratename: Path) -> syn::Result<TokenStream2> {
-    unimplemented!()
+use crate::internals::unions;
+
+pub fn process(input: &ItemUnion, cratename: Path) -> syn::Result<TokenStream2> {
+    unions::check(input)?;
+    let name = &input.ident;
+    let layout_assertions = unions::layout_assertions(input, &cratename);
+
+    Ok(quote! {
+        #layout_assertions
+        impl #cratename::ser::BorshSerialize for #name {
+            fn serialize<__W: #cratename::io::Write>(&self, writer: &mut __W) -> ::core::result::Result<(), #cratename::io::Error> {
+                // SAFETY: every field of the union is plain old data and spans all of its bytes,
+                // so all of them are initialized, whichever field was written last
+                let bytes = unsafe {
+                    ::core::slice::from_raw_parts(
+                        self as *const Self as *const u8,
+                        ::core::mem::size_of::<Self>(),
+                    )
+                };
+                writer.write_all(bytes)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::internals::test_helpers::{
+        default_cratename, local_insta_assert_debug_snapshot, local_insta_assert_snapshot,
+        pretty_print_syn_str,
+    };
+
+    use super::*;
+
+    #[test]
+    fn simple_union() {
+        let item_union: ItemUnion = syn::parse2(quote! {
+            #[repr(C)]
+            union A {
+                x: u32,
+                y: [u8; 4],
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_union, default_cratename()).unwrap();
+
+        local_insta_assert_snapshot!(pretty_print_syn_str(&actual).unwrap());
+    }
+
+    #[test]
+    fn check_union_without_repr_c() {
+        let item_union: ItemUnion = syn::parse2(quote! {
+            union A {
+                x: u32,
+                y: [u8; 4],
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_union, default_cratename());
+
+        let err = match actual {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn check_generic_union() {
+        let item_union: ItemUnion = syn::parse2(quote! {
+            #[repr(C)]
+            union A<T: Copy> {
+                x: T,
+                y: [u8; 4],
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_union, default_cratename());
+
+        let err = match actual {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
+
+    #[test]
+    fn check_union_field_attr() {
+        let item_union: ItemUnion = syn::parse2(quote! {
+            #[repr(C)]
+            union A {
+                #[borsh(skip)]
+                x: u32,
+                y: [u8; 4],
+            }
+        })
+        .unwrap();
+
+        let actual = process(&item_union, default_cratename());
+
+        let err = match actual {
+            Ok(..) => unreachable!("expecting error here"),
+            Err(err) => err,
+        };
+        local_insta_assert_debug_snapshot!(err);
+    }
 }
@@ -0,0 +1,74 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{punctuated::Punctuated, ItemUnion, Meta, Path, Token};
+
+use crate::internals::attributes::BORSH;
+
+/// Unions are (de)serialized as their raw bytes, which is only sound if
+/// all of their fields lie at offset 0 (`#[repr(C)]`), and their types
+/// are known without any substitution of generic parameters.
+pub fn check(input: &ItemUnion) -> syn::Result<()> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "borsh doesn't support generic unions",
+        ));
+    }
+    if !is_repr_c(input)? {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "borsh only supports `#[repr(C)]` unions",
+        ));
+    }
+    for field in &input.fields.named {
+        if let Some(attr) = field.attrs.iter().find(|attr| attr.path() == BORSH) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`borsh` attributes are not supported on fields of unions",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn is_repr_c(input: &ItemUnion) -> syn::Result<bool> {
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        if nested.iter().any(|meta| meta.path().is_ident("C")) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Compile-time assertions, which make reading raw bytes of the union (and writing them)
+/// sound: every field is plain old data (so that any bytes are its valid value),
+/// and every field spans all bytes of the union (so that all of them are initialized).
+pub fn layout_assertions(input: &ItemUnion, cratename: &Path) -> TokenStream2 {
+    let name = &input.ident;
+    let field_types = input.fields.named.iter().map(|field| &field.ty);
+    let size_message = format!(
+        "all fields of union `{}` must be of the same size as the union itself",
+        name
+    );
+    quote! {
+        const _: () = {
+            const fn assert_pod<T: #cratename::__private::Pod>() {}
+            #(
+                assert_pod::<#field_types>();
+                assert!(
+                    ::core::mem::size_of::<#field_types>() == ::core::mem::size_of::<#name>(),
+                    #size_message
+                );
+            )*
+            assert!(
+                cfg!(target_endian = "little"),
+                "borsh only supports unions on little-endian targets"
+            );
+        };
+    }
+}
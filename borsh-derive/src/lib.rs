@@ -7,8 +7,6 @@
 
 extern crate proc_macro;
 use proc_macro::TokenStream;
-#[cfg(feature = "schema")]
-use proc_macro2::Span;
 use syn::{DeriveInput, Error, ItemEnum, ItemStruct, ItemUnion, Path};
 
 ///  by convention, local to borsh-derive crate, imports from proc_macro (1) are not allowed in `internals` module or in any of its submodules.
@@ -103,11 +101,8 @@ pub fn borsh_schema(input: TokenStream) -> TokenStream {
         schema::structs::process(&input, cratename)
     } else if let Ok(input) = syn::parse::<ItemEnum>(input.clone()) {
         schema::enums::process(&input, cratename)
-    } else if syn::parse::<ItemUnion>(input).is_ok() {
-        Err(syn::Error::new(
-            Span::call_site(),
-            "Borsh schema does not support unions yet.",
-        ))
+    } else if let Ok(input) = syn::parse::<ItemUnion>(input) {
+        schema::unions::process(&input, cratename)
     } else {
         // Derive macros can only be defined on structs, enums, and unions.
         unreachable!()
@@ -0,0 +1,267 @@
+//! Collections with bounded length.
+//!
+//! [`BoundedVec`] and [`BoundedString`] are serialized exactly as `Vec<T>` and `String`,
+//! but their deserialization fails as soon as the length prefix is read, if it's out of
+//! `MIN..=MAX` range, without allocating any memory for the elements.
+//! Their schema is a [`Definition::Sequence`](crate::schema::Definition::Sequence)
+//! with `length_range: MIN..=MAX`, so that their maximal serialized size is finite.
+//!
+//! ```
+//! use borsh::bounded::BoundedVec;
+//! use core::convert::TryFrom;
+//!
+//! let vec = BoundedVec::<u8, 1, 4>::try_from(vec![1, 2]).unwrap();
+//! let encoded = borsh::to_vec(&vec).unwrap();
+//! assert_eq!(encoded, borsh::to_vec(&vec![1u8, 2]).unwrap());
+//!
+//! assert!(BoundedVec::<u8, 1, 4>::try_from(vec![]).is_err());
+//! assert!(borsh::from_slice::<BoundedVec<u8, 1, 4>>(&[5, 0, 0, 0, 1, 2, 3, 4, 5]).is_err());
+//! ```
+//!
+//! For fields of plain collection types, `#[borsh(max_len = N)]` field attribute
+//! (which expands to [`MaxLen`](crate::length::MaxLen) encoding) may be used instead.
+use core::convert::TryFrom;
+use core::ops::Deref;
+
+use crate::__private::maybestd::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use crate::de::{hint, ContextFree};
+use crate::error::check_zst;
+use crate::io::{Error, ErrorKind, Read, Result, Write};
+use crate::{BorshDeserialize, BorshSerialize};
+
+fn check_bounds<const MIN: usize, const MAX: usize>(len: usize) -> Result<()> {
+    if (MIN..=MAX).contains(&len) {
+        Ok(())
+    } else {
+        Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Collection length {} is out of bounds {}..={}",
+                len, MIN, MAX
+            ),
+        ))
+    }
+}
+
+fn deserialize_length<R: Read, const MIN: usize, const MAX: usize>(reader: &mut R) -> Result<u32> {
+    let len = u32::deserialize_reader(reader)?;
+    let len_usize = usize::try_from(len).map_err(|_| ErrorKind::InvalidData)?;
+    check_bounds::<MIN, MAX>(len_usize)?;
+    Ok(len)
+}
+
+/// `Vec<T>` with length in `MIN..=MAX` range.
+///
+/// Construct with [`TryFrom<Vec<T>>`], which returns the vector back, if its length is out of bounds.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedVec<T, const MIN: usize, const MAX: usize>(Vec<T>);
+
+impl<T, const MIN: usize, const MAX: usize> BoundedVec<T, MIN, MAX> {
+    /// Returns elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Returns the underlying vector.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> TryFrom<Vec<T>> for BoundedVec<T, MIN, MAX> {
+    type Error = Vec<T>;
+
+    fn try_from(vec: Vec<T>) -> core::result::Result<Self, Self::Error> {
+        if (MIN..=MAX).contains(&vec.len()) {
+            Ok(Self(vec))
+        } else {
+            Err(vec)
+        }
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> From<BoundedVec<T, MIN, MAX>> for Vec<T> {
+    fn from(vec: BoundedVec<T, MIN, MAX>) -> Self {
+        vec.0
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> Deref for BoundedVec<T, MIN, MAX> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> AsRef<[T]> for BoundedVec<T, MIN, MAX> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T: BorshSerialize, const MIN: usize, const MAX: usize> BorshSerialize
+    for BoundedVec<T, MIN, MAX>
+{
+    #[inline]
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.0.serialize(writer)
+    }
+}
+
+impl<T: BorshDeserialize, const MIN: usize, const MAX: usize> BorshDeserialize
+    for BoundedVec<T, MIN, MAX>
+{
+    #[inline]
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        check_zst::<T>()?;
+
+        let len = deserialize_length::<R, MIN, MAX>(reader)?;
+        if len == 0 {
+            Ok(Self(Vec::new()))
+        } else if let Some(vec_bytes) = T::vec_from_reader(len, reader)? {
+            Ok(Self(vec_bytes))
+        } else {
+            // TODO(16): return capacity allocation when we can safely do that.
+            let mut result = Vec::with_capacity(hint::cautious::<T>(len));
+            for _ in 0..len {
+                result.push(T::deserialize_reader(reader)?);
+            }
+            Ok(Self(result))
+        }
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> ContextFree for BoundedVec<T, MIN, MAX> {}
+
+/// `String` with length (in bytes) in `MIN..=MAX` range.
+///
+/// Construct with [`TryFrom<String>`], which returns the string back, if its length is out of bounds.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedString<const MIN: usize, const MAX: usize>(String);
+
+impl<const MIN: usize, const MAX: usize> BoundedString<MIN, MAX> {
+    /// Returns the string as a `&str`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the underlying string.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> TryFrom<String> for BoundedString<MIN, MAX> {
+    type Error = String;
+
+    fn try_from(string: String) -> core::result::Result<Self, Self::Error> {
+        if (MIN..=MAX).contains(&string.len()) {
+            Ok(Self(string))
+        } else {
+            Err(string)
+        }
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> From<BoundedString<MIN, MAX>> for String {
+    fn from(string: BoundedString<MIN, MAX>) -> Self {
+        string.0
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> Deref for BoundedString<MIN, MAX> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> AsRef<str> for BoundedString<MIN, MAX> {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> BorshSerialize for BoundedString<MIN, MAX> {
+    #[inline]
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.0.serialize(writer)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> BorshDeserialize for BoundedString<MIN, MAX> {
+    #[inline]
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let len = deserialize_length::<R, MIN, MAX>(reader)?;
+        let bytes = if len == 0 {
+            Vec::new()
+        } else {
+            u8::vec_from_reader(len, reader)?.expect("`u8::vec_from_reader` always returns `Some`")
+        };
+        String::from_utf8(bytes).map(Self).map_err(|err| {
+            let msg = err.to_string();
+            Error::new(ErrorKind::InvalidData, msg)
+        })
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> ContextFree for BoundedString<MIN, MAX> {}
+
+#[cfg(feature = "unstable__schema")]
+mod schema {
+    use super::{BoundedString, BoundedVec};
+    use crate::__private::maybestd::{collections::BTreeMap, format};
+    use crate::schema::{Declaration, Definition, DefinitionsCollector};
+    use crate::BorshSchema;
+
+    fn length_range<const MIN: usize, const MAX: usize>() -> core::ops::RangeInclusive<u64> {
+        let max_len = *Definition::DEFAULT_LENGTH_RANGE.end();
+        (MIN as u64).min(max_len)..=(MAX as u64).min(max_len)
+    }
+
+    impl<T: BorshSchema, const MIN: usize, const MAX: usize> BorshSchema for BoundedVec<T, MIN, MAX> {
+        fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+            DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+        }
+
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+            let definition = Definition::Sequence {
+                length_width: Definition::DEFAULT_LENGTH_WIDTH,
+                length_range: length_range::<MIN, MAX>(),
+                elements: T::declaration(),
+            };
+            collector.add_definition(Self::declaration(), definition);
+            T::collect_definitions(collector);
+        }
+
+        fn declaration() -> Declaration {
+            format!("BoundedVec<{}, {}, {}>", T::declaration(), MIN, MAX)
+        }
+    }
+
+    impl<const MIN: usize, const MAX: usize> BorshSchema for BoundedString<MIN, MAX> {
+        fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+            DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+        }
+
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+            let definition = Definition::Sequence {
+                length_width: Definition::DEFAULT_LENGTH_WIDTH,
+                length_range: length_range::<MIN, MAX>(),
+                elements: u8::declaration(),
+            };
+            collector.add_definition(Self::declaration(), definition);
+            u8::collect_definitions(collector);
+        }
+
+        fn declaration() -> Declaration {
+            format!("BoundedString<{}, {}>", MIN, MAX)
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use core::marker::PhantomData;
+
+use crate::__private::maybestd::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, LinkedList, VecDeque},
+    format,
+    string::String,
+    vec::Vec,
+};
+use crate::error::check_zst;
+use crate::io::{Error, ErrorKind, Read, Result};
+
+use super::{array_try_from_fn, hint, BorshDeserialize, ERROR_NOT_ALL_BYTES_READ};
+
+/// A data-structure that can be de-serialized from binary format by NBOR,
+/// given a context of type `Ctx`, e.g. an interner, a protocol version or a registry of type ids.
+///
+/// Any [BorshDeserialize] type, which is marked with [ContextFree], implements it for any `Ctx`,
+/// ignoring the context.
+/// [Vec], [Option], arrays, [BTreeMap], `HashMap` and tuples implement it for the `Ctx`
+/// of their elements, passing the context on to each of them.
+/// [Box] implements it only if its contents are [ContextFree].
+///
+/// `#[derive(BorshDeserialize)]` with `#[borsh(context = Type)]` implements it instead of
+/// [BorshDeserialize].
+///
+/// ```
+/// use borsh::de::BorshDeserializeWith;
+/// use borsh::io::{Read, Result};
+///
+/// struct Interner(Vec<String>);
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Symbol(usize);
+///
+/// impl BorshDeserializeWith<Interner> for Symbol {
+///     fn deserialize_with_context<R: Read>(reader: &mut R, ctx: &mut Interner) -> Result<Self> {
+///         let name = String::deserialize_with_context(reader, ctx)?;
+///         let index = match ctx.0.iter().position(|interned| *interned == name) {
+///             Some(index) => index,
+///             None => {
+///                 ctx.0.push(name);
+///                 ctx.0.len() - 1
+///             }
+///         };
+///         Ok(Symbol(index))
+///     }
+/// }
+///
+/// let data = borsh::to_vec(&vec!["a", "b", "a"]).unwrap();
+/// let mut interner = Interner(vec![]);
+/// let symbols = Vec::<Symbol>::try_from_slice_with_context(&data, &mut interner).unwrap();
+/// assert_eq!(symbols, vec![Symbol(0), Symbol(1), Symbol(0)]);
+/// assert_eq!(interner.0, vec!["a".to_string(), "b".to_string()]);
+/// ```
+pub trait BorshDeserializeWith<Ctx: ?Sized>: Sized {
+    fn deserialize_with_context<R: Read>(reader: &mut R, ctx: &mut Ctx) -> Result<Self>;
+
+    /// Deserialize this instance from a slice of bytes, given a context.
+    fn try_from_slice_with_context(v: &[u8], ctx: &mut Ctx) -> Result<Self> {
+        let mut v_mut = v;
+        let result = Self::deserialize_with_context(&mut v_mut, ctx)?;
+        if !v_mut.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData, ERROR_NOT_ALL_BYTES_READ));
+        }
+        Ok(result)
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    fn vec_from_reader_with_context<R: Read>(
+        len: u32,
+        reader: &mut R,
+    ) -> Result<Option<Vec<Self>>> {
+        let _ = len;
+        let _ = reader;
+        Ok(None)
+    }
+}
+
+/// Marker of [BorshDeserialize] types, which don't need any context to be deserialized,
+/// and thus implement [BorshDeserializeWith] for any `Ctx`.
+///
+/// `#[derive(BorshDeserialize)]` implements it, unless `#[borsh(context = Type)]` is specified.
+/// Types with a manual [BorshDeserialize] implementation don't implement it, and have to
+/// add an empty `impl ContextFree for MyType {}` to be used as fields of types
+/// with `#[borsh(context = Type)]`.
+///
+/// It isn't implemented for [Vec], [Option], arrays, [BTreeMap], `HashMap` and tuples,
+/// which implement [BorshDeserializeWith] by passing the context on to their elements.
+pub trait ContextFree {}
+
+impl<T, Ctx> BorshDeserializeWith<Ctx> for T
+where
+    T: BorshDeserialize + ContextFree,
+    Ctx: ?Sized,
+{
+    #[inline]
+    fn deserialize_with_context<R: Read>(reader: &mut R, _ctx: &mut Ctx) -> Result<Self> {
+        T::deserialize_reader(reader)
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    fn vec_from_reader_with_context<R: Read>(
+        len: u32,
+        reader: &mut R,
+    ) -> Result<Option<Vec<Self>>> {
+        T::vec_from_reader(len, reader)
+    }
+}
+
+macro_rules! impl_context_free {
+    ($($type: ty),*) => {
+        $(impl ContextFree for $type {})*
+    };
+}
+
+impl_context_free!(
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    bool,
+    String,
+    (),
+    core::ops::RangeFull,
+    core::num::NonZeroI8,
+    core::num::NonZeroI16,
+    core::num::NonZeroI32,
+    core::num::NonZeroI64,
+    core::num::NonZeroI128,
+    core::num::NonZeroU8,
+    core::num::NonZeroU16,
+    core::num::NonZeroU32,
+    core::num::NonZeroU64,
+    core::num::NonZeroU128,
+    core::num::NonZeroUsize
+);
+
+#[cfg(feature = "ascii")]
+impl_context_free!(ascii::AsciiString, ascii::AsciiChar);
+
+#[cfg(feature = "bytes")]
+impl_context_free!(bytes::Bytes, bytes::BytesMut);
+
+#[cfg(feature = "bson")]
+impl_context_free!(bson::oid::ObjectId);
+
+#[cfg(feature = "std")]
+impl_context_free!(
+    std::net::SocketAddr,
+    std::net::IpAddr,
+    std::net::SocketAddrV4,
+    std::net::SocketAddrV6,
+    std::net::Ipv4Addr,
+    std::net::Ipv6Addr
+);
+
+impl<T, E> ContextFree for core::result::Result<T, E> {}
+impl<T: ?Sized + ToOwned> ContextFree for Cow<'_, T> {}
+impl<T> ContextFree for VecDeque<T> {}
+impl<T> ContextFree for LinkedList<T> {}
+impl<T> ContextFree for BTreeSet<T> {}
+// `Box` is `#[fundamental]`, so it can't pass the context on to its contents alongside the blanket
+// impl above, it is only context-free if its contents are
+impl<T: ?Sized + ContextFree> ContextFree for Box<T> {}
+impl<T: ContextFree> ContextFree for [T] {}
+impl ContextFree for str {}
+impl<T: ?Sized> ContextFree for PhantomData<T> {}
+impl<T> ContextFree for core::cell::Cell<T> {}
+impl<T> ContextFree for core::cell::RefCell<T> {}
+impl<T> ContextFree for core::ops::Range<T> {}
+impl<T> ContextFree for core::ops::RangeInclusive<T> {}
+impl<T> ContextFree for core::ops::RangeFrom<T> {}
+impl<T> ContextFree for core::ops::RangeTo<T> {}
+impl<T> ContextFree for core::ops::RangeToInclusive<T> {}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> ContextFree for indexmap::IndexMap<K, V, S> {}
+#[cfg(feature = "indexmap")]
+impl<T, S> ContextFree for indexmap::IndexSet<T, S> {}
+
+#[cfg(feature = "rc")]
+impl<T: ?Sized> ContextFree for crate::__private::maybestd::rc::Rc<T> {}
+#[cfg(feature = "rc")]
+impl<T: ?Sized> ContextFree for crate::__private::maybestd::sync::Arc<T> {}
+
+#[cfg(hash_collections)]
+impl<T, H> ContextFree for crate::__private::maybestd::collections::HashSet<T, H> {}
+
+impl<T, Ctx> BorshDeserializeWith<Ctx> for Vec<T>
+where
+    T: BorshDeserializeWith<Ctx>,
+    Ctx: ?Sized,
+{
+    #[inline]
+    fn deserialize_with_context<R: Read>(reader: &mut R, ctx: &mut Ctx) -> Result<Self> {
+        check_zst::<T>()?;
+
+        let len = u32::deserialize_reader(reader)?;
+        if len == 0 {
+            Ok(Vec::new())
+        } else if let Some(vec_bytes) = T::vec_from_reader_with_context(len, reader)? {
+            Ok(vec_bytes)
+        } else {
+            // TODO(16): return capacity allocation when we can safely do that.
+            let mut result = Vec::with_capacity(hint::cautious::<T>(len));
+            for _ in 0..len {
+                result.push(T::deserialize_with_context(reader, ctx)?);
+            }
+            Ok(result)
+        }
+    }
+}
+
+impl<T, Ctx, const N: usize> BorshDeserializeWith<Ctx> for [T; N]
+where
+    T: BorshDeserializeWith<Ctx>,
+    Ctx: ?Sized,
+{
+    #[inline]
+    fn deserialize_with_context<R: Read>(reader: &mut R, ctx: &mut Ctx) -> Result<Self> {
+        array_try_from_fn(|| T::deserialize_with_context(reader, ctx))
+    }
+}
+
+impl<T, Ctx> BorshDeserializeWith<Ctx> for Option<T>
+where
+    T: BorshDeserializeWith<Ctx>,
+    Ctx: ?Sized,
+{
+    #[inline]
+    fn deserialize_with_context<R: Read>(reader: &mut R, ctx: &mut Ctx) -> Result<Self> {
+        let flag = u8::deserialize_reader(reader)?;
+        if flag == 0 {
+            Ok(None)
+        } else if flag == 1 {
+            Ok(Some(T::deserialize_with_context(reader, ctx)?))
+        } else {
+            let msg = format!(
+                "Invalid Option representation: {}. The first byte must be 0 or 1",
+                flag
+            );
+
+            Err(Error::new(ErrorKind::InvalidData, msg))
+        }
+    }
+}
+
+/// Checks that keys of entries are in ascending order, like
+/// [BorshDeserialize] implementations of maps do with `de_strict_order` feature.
+#[cfg(feature = "de_strict_order")]
+fn check_keys_order<K: Ord, V>(entries: &[(K, V)]) -> Result<()> {
+    for pair in entries.windows(2) {
+        let [(a_k, _a_v), (b_k, _b_v)] = pair else {
+            unreachable!("`windows` always return a slice of length 2 or nothing");
+        };
+        if !a_k.cmp(b_k).is_lt() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                super::ERROR_WRONG_ORDER_OF_KEYS,
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl<K, V, Ctx> BorshDeserializeWith<Ctx> for BTreeMap<K, V>
+where
+    K: BorshDeserializeWith<Ctx> + Ord,
+    V: BorshDeserializeWith<Ctx>,
+    Ctx: ?Sized,
+{
+    #[inline]
+    fn deserialize_with_context<R: Read>(reader: &mut R, ctx: &mut Ctx) -> Result<Self> {
+        check_zst::<K>()?;
+        let vec = <Vec<(K, V)>>::deserialize_with_context(reader, ctx)?;
+
+        #[cfg(feature = "de_strict_order")]
+        check_keys_order(&vec)?;
+
+        Ok(vec.into_iter().collect::<BTreeMap<K, V>>())
+    }
+}
+
+#[cfg(hash_collections)]
+impl<K, V, H, Ctx> BorshDeserializeWith<Ctx>
+    for crate::__private::maybestd::collections::HashMap<K, V, H>
+where
+    K: BorshDeserializeWith<Ctx> + Eq + core::hash::Hash + Ord,
+    V: BorshDeserializeWith<Ctx>,
+    H: core::hash::BuildHasher + Default,
+    Ctx: ?Sized,
+{
+    #[inline]
+    fn deserialize_with_context<R: Read>(reader: &mut R, ctx: &mut Ctx) -> Result<Self> {
+        check_zst::<K>()?;
+        let vec = <Vec<(K, V)>>::deserialize_with_context(reader, ctx)?;
+
+        #[cfg(feature = "de_strict_order")]
+        check_keys_order(&vec)?;
+
+        Ok(vec.into_iter().collect())
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident)+) => {
+      impl<$($name,)+ Ctx> BorshDeserializeWith<Ctx> for ($($name,)+)
+      where $($name: BorshDeserializeWith<Ctx>,)+ Ctx: ?Sized,
+      {
+        #[inline]
+        fn deserialize_with_context<R: Read>(reader: &mut R, ctx: &mut Ctx) -> Result<Self> {
+            Ok(($($name::deserialize_with_context(reader, ctx)?,)+))
+        }
+      }
+    };
+}
+
+impl_tuple!(T0);
+impl_tuple!(T0 T1);
+impl_tuple!(T0 T1 T2);
+impl_tuple!(T0 T1 T2 T3);
+impl_tuple!(T0 T1 T2 T3 T4);
+impl_tuple!(T0 T1 T2 T3 T4 T5);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18);
+impl_tuple!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15 T16 T17 T18 T19);
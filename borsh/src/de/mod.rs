@@ -21,10 +21,14 @@ use crate::io::{Error, ErrorKind, Read, Result};
 
 use crate::error::check_zst;
 
-mod hint;
+mod context;
+pub(crate) mod hint;
 
-const ERROR_NOT_ALL_BYTES_READ: &str = "Not all bytes read";
-const ERROR_UNEXPECTED_LENGTH_OF_INPUT: &str = "Unexpected length of input";
+pub use crate::endian::BigEndian;
+pub use context::{BorshDeserializeWith, ContextFree};
+
+pub(crate) const ERROR_NOT_ALL_BYTES_READ: &str = "Not all bytes read";
+pub(crate) const ERROR_UNEXPECTED_LENGTH_OF_INPUT: &str = "Unexpected length of input";
 const ERROR_OVERFLOW_ON_MACHINE_WITH_32_BIT_ISIZE: &str = "Overflow on machine with 32 bit isize";
 const ERROR_OVERFLOW_ON_MACHINE_WITH_32_BIT_USIZE: &str = "Overflow on machine with 32 bit usize";
 const ERROR_INVALID_ZERO_VALUE: &str = "Expected a non-zero value";
@@ -61,6 +65,17 @@ pub trait BorshDeserialize: Sized {
         }
     }
 
+    /// Number of bytes of every serialized value, if the type is read with
+    /// [BorshDeserialize::from_fixed_bytes] as a leading field of derived `deserialize`.
+    #[doc(hidden)]
+    const FIXED_SIZE: Option<usize> = None;
+
+    #[inline]
+    #[doc(hidden)]
+    fn from_fixed_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::try_from_slice(bytes)
+    }
+
     #[inline]
     #[doc(hidden)]
     fn vec_from_reader<R: Read>(len: u32, reader: &mut R) -> Result<Option<Vec<Self>>> {
@@ -77,6 +92,13 @@ pub trait BorshDeserialize: Sized {
     }
 }
 
+/// A data-structure that de-serializes values of type `T` from binary format with a custom encoding.
+///
+/// Counterpart of [`crate::ser::BorshSerializeAs`].
+pub trait BorshDeserializeAs<T> {
+    fn deserialize_as<R: Read>(reader: &mut R) -> Result<T>;
+}
+
 /// Additional methods offered on enums which is used by `[derive(BorshDeserialize)]`.
 pub trait EnumExt: BorshDeserialize {
     /// Deserialises given variant of an enum from the reader.
@@ -135,7 +157,7 @@ pub trait EnumExt: BorshDeserialize {
     fn deserialize_variant<R: Read>(reader: &mut R, tag: u8) -> Result<Self>;
 }
 
-fn unexpected_eof_to_unexpected_length_of_input(e: Error) -> Error {
+pub(crate) fn unexpected_eof_to_unexpected_length_of_input(e: Error) -> Error {
     if e.kind() == ErrorKind::UnexpectedEof {
         Error::new(ErrorKind::InvalidData, ERROR_UNEXPECTED_LENGTH_OF_INPUT)
     } else {
@@ -153,6 +175,20 @@ impl BorshDeserialize for u8 {
         Ok(buf[0])
     }
 
+    const FIXED_SIZE: Option<usize> = Some(1);
+
+    #[inline]
+    #[doc(hidden)]
+    fn from_fixed_bytes(bytes: &[u8]) -> Result<Self> {
+        match bytes {
+            [byte] => Ok(*byte),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                ERROR_UNEXPECTED_LENGTH_OF_INPUT,
+            )),
+        }
+    }
+
     #[inline]
     #[doc(hidden)]
     fn vec_from_reader<R: Read>(len: u32, reader: &mut R) -> Result<Option<Vec<Self>>> {
@@ -206,6 +242,17 @@ macro_rules! impl_for_integer {
                 let res = $type::from_le_bytes(buf.try_into().unwrap());
                 Ok(res)
             }
+
+            const FIXED_SIZE: Option<usize> = Some(size_of::<$type>());
+
+            #[inline]
+            #[doc(hidden)]
+            fn from_fixed_bytes(bytes: &[u8]) -> Result<Self> {
+                let buf = bytes.try_into().map_err(|_| {
+                    Error::new(ErrorKind::InvalidData, ERROR_UNEXPECTED_LENGTH_OF_INPUT)
+                })?;
+                Ok($type::from_le_bytes(buf))
+            }
         }
     };
 }
@@ -771,60 +818,65 @@ where
     }
 }
 
+/// Fills an array with the values returned by `f`, dropping the already read elements on error.
+pub(super) fn array_try_from_fn<T, const N: usize>(f: impl FnMut() -> Result<T>) -> Result<[T; N]> {
+    struct ArrayDropGuard<T, const N: usize> {
+        buffer: [MaybeUninit<T>; N],
+        init_count: usize,
+    }
+    impl<T, const N: usize> Drop for ArrayDropGuard<T, N> {
+        fn drop(&mut self) {
+            let init_range = &mut self.buffer[..self.init_count];
+            // SAFETY: Elements up to self.init_count have been initialized. Assumes this value
+            //         is only incremented in `fill_buffer`, which writes the element before
+            //         increasing the init_count.
+            unsafe {
+                core::ptr::drop_in_place(init_range as *mut _ as *mut [T]);
+            };
+        }
+    }
+    impl<T, const N: usize> ArrayDropGuard<T, N> {
+        unsafe fn transmute_to_array(mut self) -> [T; N] {
+            debug_assert_eq!(self.init_count, N);
+            // Set init_count to 0 so that the values do not get dropped twice.
+            self.init_count = 0;
+            // SAFETY: This cast is required because `mem::transmute` does not work with
+            //         const generics https://github.com/rust-lang/rust/issues/61956. This
+            //         array is guaranteed to be initialized by this point.
+            core::ptr::read(&self.buffer as *const _ as *const [T; N])
+        }
+        fn fill_buffer(&mut self, mut f: impl FnMut() -> Result<T>) -> Result<()> {
+            // TODO: replace with `core::array::try_from_fn` when stabilized to avoid manually
+            // dropping uninitialized values through the guard drop.
+            for elem in self.buffer.iter_mut() {
+                elem.write(f()?);
+                self.init_count += 1;
+            }
+            Ok(())
+        }
+    }
+
+    let mut result = ArrayDropGuard {
+        buffer: unsafe { MaybeUninit::uninit().assume_init() },
+        init_count: 0,
+    };
+
+    result.fill_buffer(f)?;
+
+    // SAFETY: The elements up to `i` have been initialized in `fill_buffer`.
+    Ok(unsafe { result.transmute_to_array() })
+}
+
 impl<T, const N: usize> BorshDeserialize for [T; N]
 where
     T: BorshDeserialize,
 {
     #[inline]
     fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
-        struct ArrayDropGuard<T, const N: usize> {
-            buffer: [MaybeUninit<T>; N],
-            init_count: usize,
-        }
-        impl<T, const N: usize> Drop for ArrayDropGuard<T, N> {
-            fn drop(&mut self) {
-                let init_range = &mut self.buffer[..self.init_count];
-                // SAFETY: Elements up to self.init_count have been initialized. Assumes this value
-                //         is only incremented in `fill_buffer`, which writes the element before
-                //         increasing the init_count.
-                unsafe {
-                    core::ptr::drop_in_place(init_range as *mut _ as *mut [T]);
-                };
-            }
-        }
-        impl<T, const N: usize> ArrayDropGuard<T, N> {
-            unsafe fn transmute_to_array(mut self) -> [T; N] {
-                debug_assert_eq!(self.init_count, N);
-                // Set init_count to 0 so that the values do not get dropped twice.
-                self.init_count = 0;
-                // SAFETY: This cast is required because `mem::transmute` does not work with
-                //         const generics https://github.com/rust-lang/rust/issues/61956. This
-                //         array is guaranteed to be initialized by this point.
-                core::ptr::read(&self.buffer as *const _ as *const [T; N])
-            }
-            fn fill_buffer(&mut self, mut f: impl FnMut() -> Result<T>) -> Result<()> {
-                // TODO: replace with `core::array::try_from_fn` when stabilized to avoid manually
-                // dropping uninitialized values through the guard drop.
-                for elem in self.buffer.iter_mut() {
-                    elem.write(f()?);
-                    self.init_count += 1;
-                }
-                Ok(())
-            }
-        }
-
         if let Some(arr) = T::array_from_reader(reader)? {
             Ok(arr)
         } else {
-            let mut result = ArrayDropGuard {
-                buffer: unsafe { MaybeUninit::uninit().assume_init() },
-                init_count: 0,
-            };
-
-            result.fill_buffer(|| T::deserialize_reader(reader))?;
-
-            // SAFETY: The elements up to `i` have been initialized in `fill_buffer`.
-            Ok(unsafe { result.transmute_to_array() })
+            array_try_from_fn(|| T::deserialize_reader(reader))
         }
     }
 }
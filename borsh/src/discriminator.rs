@@ -0,0 +1,102 @@
+//! Type discriminators, i.e. fixed byte prefixes identifying the type of serialized value.
+//!
+//! `#[borsh(discriminator)]` item attribute makes derived `BorshSerialize` write
+//! [`Discriminator::DISCRIMINATOR`] bytes before the value, and derived `BorshDeserialize`
+//! verify them, failing with [`ERROR_DISCRIMINATOR_MISMATCH`] error on a mismatch.
+//!
+//! Default discriminator is the first 8 bytes of SHA-256 hash of `account:<TypeName>`,
+//! which is compatible with account discriminators of Anchor framework.
+//! A custom one may be specified with `#[borsh(discriminator = [1, 2, 3, 4])]`.
+//!
+//! ```
+//! use borsh::discriminator::Discriminator;
+//! use borsh::{BorshDeserialize, BorshSerialize};
+//!
+//! /// derive is only available if borsh is built with `features = ["derive"]`
+//! # #[cfg(feature = "derive")]
+//! #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+//! #[borsh(discriminator = [0xca, 0xfe])]
+//! struct Counter {
+//!     value: u8,
+//! }
+//!
+//! # #[cfg(feature = "derive")]
+//! # {
+//! assert_eq!(Counter::DISCRIMINATOR, &[0xca, 0xfe]);
+//! let encoded = borsh::to_vec(&Counter { value: 1 }).unwrap();
+//! assert_eq!(encoded, vec![0xca, 0xfe, 1]);
+//!
+//! let err = borsh::from_slice::<Counter>(&[0xca, 0xff, 1]).unwrap_err();
+//! assert_eq!(err.to_string(), borsh::discriminator::ERROR_DISCRIMINATOR_MISMATCH);
+//! # }
+//! ```
+use crate::io::{Error, ErrorKind, Read, Result};
+use crate::BorshDeserialize;
+
+pub const ERROR_DISCRIMINATOR_MISMATCH: &str =
+    "Discriminator of the serialized value doesn't match";
+
+/// Types, which are prefixed with a discriminator when serialized.
+///
+/// Implemented by `#[derive(BorshSerialize)]` for items with `#[borsh(discriminator)]` attribute.
+pub trait Discriminator {
+    /// Bytes, written before the serialized value.
+    const DISCRIMINATOR: &'static [u8];
+}
+
+/// Reads the discriminator from `reader` and checks that it's equal to `expected`.
+///
+/// Used by `#[derive(BorshDeserialize)]` for items with `#[borsh(discriminator)]` attribute.
+pub fn check<R: Read>(reader: &mut R, expected: &[u8]) -> Result<()> {
+    let mut matches = true;
+    for &byte in expected {
+        // the whole discriminator is consumed, regardless of the mismatch position
+        matches &= u8::deserialize_reader(reader)? == byte;
+    }
+    if !matches {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            ERROR_DISCRIMINATOR_MISMATCH,
+        ));
+    }
+    Ok(())
+}
+
+/// Adds definition of a type with `discriminator` prefix.
+///
+/// The type is defined as a [`Definition::Tuple`](crate::schema::Definition::Tuple) of
+/// `Discriminator<[...]>` (fixed-length sequence of `u8`, with bytes of discriminator in its name)
+/// and `<declaration>Body`, defined with `body` definition.
+///
+/// Used by `#[derive(BorshSchema)]` for items with `#[borsh(discriminator)]` attribute.
+#[cfg(feature = "unstable__schema")]
+pub fn add_definition(
+    declaration: crate::schema::Declaration,
+    discriminator: &[u8],
+    body: crate::schema::Definition,
+    collector: &mut crate::schema::DefinitionsCollector<'_>,
+) {
+    use crate::__private::maybestd::{format, vec};
+    use crate::schema::Definition;
+    use crate::BorshSchema;
+
+    let discriminator_declaration = format!("Discriminator<{:?}>", discriminator);
+    let body_declaration = format!("{}Body", declaration);
+    collector.add_definition(
+        declaration,
+        Definition::Tuple {
+            elements: vec![discriminator_declaration.clone(), body_declaration.clone()],
+        },
+    );
+    let len = discriminator.len() as u64;
+    collector.add_definition(
+        discriminator_declaration,
+        Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range: len..=len,
+            elements: u8::declaration(),
+        },
+    );
+    collector.add_definition(body_declaration, body);
+    u8::collect_definitions(collector);
+}
@@ -0,0 +1,215 @@
+//! Numbers in big-endian byte order.
+//!
+//! Borsh encodes integers and floats in little-endian byte order. [`BigEndian<T>`] wraps
+//! any integer or float primitive `T` and (de)serializes it in big-endian byte order instead,
+//! which is common for hashes, network protocols and keys meant to be sorted bytewise.
+//!
+//! Besides being usable as a field type, `BigEndian<T>` implements
+//! [`BorshSerializeAs<T>`]/[`BorshDeserializeAs<T>`] for the wrapped primitive,
+//! which is what `#[borsh(big_endian)]` field and container attribute expands to.
+//!
+//! Schema declaration of `BigEndian<T>` is `BigEndian<T>` (e.g. `BigEndian<u32>`), distinct
+//! from the declaration of `T`, so that tools reading the schema don't misdecode it.
+//!
+//! Length prefixes of collections and enum tags are never affected and stay little-endian.
+//!
+//! ```
+//! use borsh::endian::BigEndian;
+//!
+//! assert_eq!(borsh::to_vec(&BigEndian(0x0102_0304u32)).unwrap(), vec![1, 2, 3, 4]);
+//! assert_eq!(
+//!     borsh::from_slice::<BigEndian<i16>>(&[0xff, 0xfe]).unwrap(),
+//!     BigEndian(-2i16)
+//! );
+//! ```
+use crate::de::{BorshDeserializeAs, ContextFree};
+use crate::io::{Error, ErrorKind, Read, Result, Write};
+use crate::ser::BorshSerializeAs;
+use crate::{BorshDeserialize, BorshSerialize};
+
+/// Integer or float primitive `T`, (de)serialized in big-endian byte order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BigEndian<T>(pub T);
+
+impl<T> ContextFree for BigEndian<T> {}
+
+impl<T> From<T> for BigEndian<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+macro_rules! impl_common {
+    ($type: ident) => {
+        impl From<BigEndian<$type>> for $type {
+            fn from(value: BigEndian<$type>) -> Self {
+                value.0
+            }
+        }
+
+        impl BorshSerializeAs<$type> for BigEndian<$type> {
+            #[inline]
+            fn serialize_as<W: Write>(source: &$type, writer: &mut W) -> Result<()> {
+                BigEndian(*source).serialize(writer)
+            }
+        }
+
+        impl BorshDeserializeAs<$type> for BigEndian<$type> {
+            #[inline]
+            fn deserialize_as<R: Read>(reader: &mut R) -> Result<$type> {
+                BigEndian::<$type>::deserialize_reader(reader).map(|value| value.0)
+            }
+        }
+    };
+}
+
+macro_rules! impl_for_integer {
+    ($($type: ident),*) => {
+        $(
+            impl BorshSerialize for BigEndian<$type> {
+                #[inline]
+                fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                    writer.write_all(&self.0.to_be_bytes())
+                }
+            }
+
+            impl BorshDeserialize for BigEndian<$type> {
+                #[inline]
+                fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                    let bytes = <[u8; core::mem::size_of::<$type>()]>::deserialize_reader(reader)?;
+                    Ok(Self($type::from_be_bytes(bytes)))
+                }
+            }
+
+            impl_common!($type);
+        )*
+    };
+}
+
+impl_for_integer!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+// NaNs are rejected for the same portability reasons as in little-endian floats.
+macro_rules! impl_for_float {
+    ($($type: ident),*) => {
+        $(
+            impl BorshSerialize for BigEndian<$type> {
+                #[inline]
+                fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                    if self.0.is_nan() {
+                        return Err(Error::new(
+                            ErrorKind::InvalidData,
+                            "For portability reasons we do not allow to serialize NaNs.",
+                        ));
+                    }
+                    writer.write_all(&self.0.to_bits().to_be_bytes())
+                }
+            }
+
+            impl BorshDeserialize for BigEndian<$type> {
+                #[inline]
+                fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                    let bytes = <[u8; core::mem::size_of::<$type>()]>::deserialize_reader(reader)?;
+                    let value = $type::from_be_bytes(bytes);
+                    if value.is_nan() {
+                        return Err(Error::new(
+                            ErrorKind::InvalidData,
+                            "For portability reasons we do not allow to deserialize NaNs.",
+                        ));
+                    }
+                    Ok(Self(value))
+                }
+            }
+
+            impl_common!($type);
+        )*
+    };
+}
+
+impl_for_float!(f32, f64);
+
+#[cfg(feature = "unstable__schema")]
+mod schema {
+    use super::BigEndian;
+    use crate::__private::maybestd::{collections::BTreeMap, format};
+    use crate::schema::{BorshSchemaAs, Declaration, Definition, DefinitionsCollector};
+    use crate::BorshSchema;
+
+    macro_rules! impl_schema {
+        ($($type: ident),*) => {
+            $(
+                impl BorshSchema for BigEndian<$type> {
+                    fn add_definitions_recursively(
+                        definitions: &mut BTreeMap<Declaration, Definition>,
+                    ) {
+                        DefinitionsCollector::collect_or_panic(
+                            definitions,
+                            <Self as BorshSchema>::collect_definitions,
+                        );
+                    }
+
+                    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+                        let definition =
+                            Definition::Primitive(core::mem::size_of::<$type>() as u8);
+                        collector.add_definition(<Self as BorshSchema>::declaration(), definition);
+                    }
+
+                    fn declaration() -> Declaration {
+                        format!("BigEndian<{}>", <$type as BorshSchema>::declaration())
+                    }
+                }
+
+                impl BorshSchemaAs<$type> for BigEndian<$type> {
+                    fn add_definitions_recursively(
+                        definitions: &mut BTreeMap<Declaration, Definition>,
+                    ) {
+                        <Self as BorshSchema>::add_definitions_recursively(definitions);
+                    }
+
+                    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+                        <Self as BorshSchema>::collect_definitions(collector);
+                    }
+
+                    fn declaration() -> Declaration {
+                        <Self as BorshSchema>::declaration()
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_schema!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::__private::maybestd::vec;
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(crate::to_vec(&BigEndian(0x0102u16)).unwrap(), vec![1, 2]);
+        assert_eq!(crate::to_vec(&BigEndian(-1i64)).unwrap(), vec![0xff; 8]);
+        assert_eq!(
+            crate::from_slice::<BigEndian<u128>>(
+                &crate::to_vec(&BigEndian(u128::MAX - 1)).unwrap()
+            )
+            .unwrap(),
+            BigEndian(u128::MAX - 1)
+        );
+        assert!(crate::from_slice::<BigEndian<u32>>(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_floats() {
+        assert_eq!(
+            crate::to_vec(&BigEndian(1.0f32)).unwrap(),
+            vec![0x3f, 0x80, 0x00, 0x00]
+        );
+        assert_eq!(
+            crate::from_slice::<BigEndian<f64>>(&2.5f64.to_be_bytes()).unwrap(),
+            BigEndian(2.5)
+        );
+        assert!(crate::to_vec(&BigEndian(f64::NAN)).is_err());
+        assert!(crate::from_slice::<BigEndian<f32>>(&f32::NAN.to_be_bytes()).is_err());
+    }
+}
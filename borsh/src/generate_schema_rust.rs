@@ -0,0 +1,23 @@
+//! Generate Rust types for `BorshSchemaContainer`, serialized into the file, given as the first
+//! argument, or for `BorshSchemaContainer` itself, and print them.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+use borsh::schema::{rust, BorshSchemaContainer};
+use borsh::schema_container_of;
+use std::{env, fs};
+
+fn main() {
+    let container = match env::args().nth(1) {
+        Some(path) => {
+            let data = fs::read(path).expect("Failed to read file");
+            borsh::from_slice::<BorshSchemaContainer>(&data)
+                .expect("Failed to deserialize BorshSchemaContainer")
+        }
+        None => schema_container_of::<BorshSchemaContainer>(),
+    };
+
+    match rust::generate(&container) {
+        Ok(generated) => print!("{}", generated),
+        Err(err) => panic!("Failed to generate Rust: {}", err),
+    }
+}
@@ -8,7 +8,7 @@ use std::io::Write;
 fn main() {
     let container = schema_container_of::<borsh::schema::BorshSchemaContainer>();
 
-    println!("{:#?}", container);
+    println!("{}", container);
 
     let data = borsh::to_vec(&container).expect("Failed to serialize BorshSchemaContainer");
     let mut file = File::create("schema_schema.dat").expect("Failed to create file");
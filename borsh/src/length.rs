@@ -0,0 +1,693 @@
+//! Custom widths of length prefixes of dynamically sized collections.
+//!
+//! By default borsh prefixes `Vec`, `String`, sets and maps with their length encoded as `u32`.
+//! Marker types of this module implement [`BorshSerializeAs`], [`BorshDeserializeAs`]
+//! (and `BorshSchemaAs` with `unstable__schema` feature) to (de)serialize such collections
+//! with a `u8`, `u16`, `u32` or `u64` length prefix instead.
+//!
+//! They are what `#[borsh(length = ...)]` field attribute expands to
+//! (with [`MaxLen<L, N>`](MaxLen) in place of `L` for the outermost collection,
+//! if `#[borsh(max_len = N)]` is also present):
+//!
+//! | field type                  | encoding                              |
+//! |-----------------------------|---------------------------------------|
+//! | `Vec<Vec<T>>`               | `Sequence<L, Sequence<L, Same>>`      |
+//! | `String`                    | `Sequence<L>`                         |
+//! | `BTreeMap<String, Vec<T>>`  | `Map<L, Sequence<L>, Sequence<L>>`    |
+//! | `Option<Vec<T>>`            | `Option<Sequence<L>>`                 |
+//!
+//! ```
+//! use borsh::length::Sequence;
+//! use borsh::ser::BorshSerializeAs;
+//! use borsh::de::BorshDeserializeAs;
+//!
+//! let value = vec![1u32, 2];
+//! let mut buf = vec![];
+//! <Sequence<u8> as BorshSerializeAs<Vec<u32>>>::serialize_as(&value, &mut buf).unwrap();
+//! assert_eq!(buf, vec![2, 1, 0, 0, 0, 2, 0, 0, 0]);
+//!
+//! let decoded = <Sequence<u8> as BorshDeserializeAs<Vec<u32>>>::deserialize_as(&mut buf.as_slice()).unwrap();
+//! assert_eq!(decoded, value);
+//! ```
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+
+use crate::__private::maybestd::{
+    collections::{BTreeMap, BTreeSet, LinkedList, VecDeque},
+    string::{String, ToString},
+    vec::Vec,
+};
+use crate::de::{hint, BorshDeserializeAs};
+use crate::error::check_zst;
+use crate::io::{Error, ErrorKind, Read, Result, Write};
+use crate::ser::BorshSerializeAs;
+use crate::{BorshDeserialize, BorshSerialize};
+
+const ERROR_LENGTH_OVERFLOW: &str = "Collection length does not fit into its length prefix";
+const ERROR_LENGTH_EXCEEDS_MAX: &str = "Collection length exceeds its maximal length";
+const ERROR_LENGTH_OVERFLOW_USIZE: &str = "Collection length does not fit into usize";
+#[cfg(feature = "de_strict_order")]
+const ERROR_WRONG_ORDER_OF_KEYS: &str = "keys were not serialized in ascending order";
+
+/// Integer type used as length prefix of a collection.
+pub trait LengthWidth {
+    /// Number of bytes taken by the length prefix.
+    const WIDTH: u8;
+    /// Maximal length which can be represented.
+    const MAX: u64;
+    /// Name of the type, used in schema declarations.
+    const NAME: &'static str;
+
+    /// Writes `len` as length prefix, failing with [`ErrorKind::InvalidData`] if it doesn't fit.
+    fn serialize_length<W: Write>(len: usize, writer: &mut W) -> Result<()>;
+
+    /// Reads length prefix.
+    fn deserialize_length<R: Read>(reader: &mut R) -> Result<usize>;
+
+    /// Appends parameters, which distinguish declarations of collections with this length prefix
+    /// from declarations of collections with the default `u32` one.
+    #[cfg(feature = "unstable__schema")]
+    fn declaration_params(params: &mut Vec<crate::schema::Declaration>) {
+        if Self::WIDTH != crate::schema::Definition::DEFAULT_LENGTH_WIDTH {
+            params.push(crate::__private::maybestd::format!(
+                "length = {}",
+                Self::NAME
+            ));
+        }
+    }
+
+    /// Adds definition of a sequence of at most `max_len` `elements` with this length prefix.
+    #[cfg(feature = "unstable__schema")]
+    fn add_sequence_definition(
+        declaration: crate::schema::Declaration,
+        elements: crate::schema::Declaration,
+        max_len: u64,
+        collector: &mut crate::schema::DefinitionsCollector<'_>,
+    ) {
+        let definition = crate::schema::Definition::Sequence {
+            length_width: Self::WIDTH,
+            length_range: 0..=max_len,
+            elements,
+        };
+        collector.add_definition(declaration, definition);
+    }
+}
+
+macro_rules! impl_length_width {
+    ($type: ident) => {
+        impl LengthWidth for $type {
+            const WIDTH: u8 = core::mem::size_of::<$type>() as u8;
+            const MAX: u64 = $type::MAX as u64;
+            const NAME: &'static str = stringify!($type);
+
+            #[inline]
+            fn serialize_length<W: Write>(len: usize, writer: &mut W) -> Result<()> {
+                $type::try_from(len)
+                    .map_err(|_| Error::new(ErrorKind::InvalidData, ERROR_LENGTH_OVERFLOW))?
+                    .serialize(writer)
+            }
+
+            #[inline]
+            #[allow(clippy::unnecessary_fallible_conversions)]
+            fn deserialize_length<R: Read>(reader: &mut R) -> Result<usize> {
+                let len = $type::deserialize_reader(reader)?;
+                usize::try_from(len)
+                    .map_err(|_| Error::new(ErrorKind::InvalidData, ERROR_LENGTH_OVERFLOW_USIZE))
+            }
+        }
+    };
+}
+
+impl_length_width!(u8);
+impl_length_width!(u16);
+impl_length_width!(u32);
+impl_length_width!(u64);
+
+/// Length prefix `L`, which additionally limits length of a collection to `N`.
+///
+/// Both serialization and deserialization fail with [`ErrorKind::InvalidData`]
+/// on longer collections, the latter one before allocating memory for elements.
+/// This is what `#[borsh(max_len = N)]` field attribute expands to.
+pub struct MaxLen<L, const N: usize>(PhantomData<L>);
+
+impl<L: LengthWidth, const N: usize> LengthWidth for MaxLen<L, N> {
+    const WIDTH: u8 = L::WIDTH;
+    const MAX: u64 = if (N as u64) < L::MAX {
+        N as u64
+    } else {
+        L::MAX
+    };
+    const NAME: &'static str = L::NAME;
+
+    #[inline]
+    fn serialize_length<W: Write>(len: usize, writer: &mut W) -> Result<()> {
+        if len > N {
+            return Err(Error::new(ErrorKind::InvalidData, ERROR_LENGTH_EXCEEDS_MAX));
+        }
+        L::serialize_length(len, writer)
+    }
+
+    #[inline]
+    fn deserialize_length<R: Read>(reader: &mut R) -> Result<usize> {
+        let len = L::deserialize_length(reader)?;
+        if len > N {
+            return Err(Error::new(ErrorKind::InvalidData, ERROR_LENGTH_EXCEEDS_MAX));
+        }
+        Ok(len)
+    }
+
+    #[cfg(feature = "unstable__schema")]
+    fn declaration_params(params: &mut Vec<crate::schema::Declaration>) {
+        L::declaration_params(params);
+        params.push(crate::__private::maybestd::format!("max_len = {}", N));
+    }
+
+    #[cfg(feature = "unstable__schema")]
+    fn add_sequence_definition(
+        declaration: crate::schema::Declaration,
+        elements: crate::schema::Declaration,
+        max_len: u64,
+        collector: &mut crate::schema::DefinitionsCollector<'_>,
+    ) {
+        L::add_sequence_definition(declaration, elements, max_len.min(Self::MAX), collector);
+    }
+}
+
+/// Encoding which (de)serializes a value with its own [`BorshSerialize`]/[`BorshDeserialize`] impl.
+pub struct Same;
+
+/// Encoding of a vec-like collection, a set or a `String` with `L` length prefix,
+/// where elements are encoded with `E`.
+pub struct Sequence<L, E = Same>(PhantomData<(L, E)>);
+
+/// Encoding of a map with `L` length prefix, where keys are encoded with `K`
+/// and values are encoded with `V`.
+pub struct Map<L, K = Same, V = Same>(PhantomData<(L, K, V)>);
+
+impl<T: BorshSerialize + ?Sized> BorshSerializeAs<T> for Same {
+    #[inline]
+    fn serialize_as<W: Write>(source: &T, writer: &mut W) -> Result<()> {
+        source.serialize(writer)
+    }
+}
+
+impl<T: BorshDeserialize> BorshDeserializeAs<T> for Same {
+    #[inline]
+    fn deserialize_as<R: Read>(reader: &mut R) -> Result<T> {
+        T::deserialize_reader(reader)
+    }
+}
+
+impl<T, E> BorshSerializeAs<Option<T>> for Option<E>
+where
+    E: BorshSerializeAs<T>,
+{
+    #[inline]
+    fn serialize_as<W: Write>(source: &Option<T>, writer: &mut W) -> Result<()> {
+        match source {
+            None => 0u8.serialize(writer),
+            Some(value) => {
+                1u8.serialize(writer)?;
+                E::serialize_as(value, writer)
+            }
+        }
+    }
+}
+
+impl<T, E> BorshDeserializeAs<Option<T>> for Option<E>
+where
+    E: BorshDeserializeAs<T>,
+{
+    #[inline]
+    fn deserialize_as<R: Read>(reader: &mut R) -> Result<Option<T>> {
+        let flag: u8 = BorshDeserialize::deserialize_reader(reader)?;
+        match flag {
+            0 => Ok(None),
+            1 => Ok(Some(E::deserialize_as(reader)?)),
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                crate::__private::maybestd::format!(
+                    "Invalid Option representation: {}. The first byte must be 0 or 1",
+                    flag
+                ),
+            )),
+        }
+    }
+}
+
+fn serialize_elements<'a, L, E, T, W>(
+    len: usize,
+    elements: impl Iterator<Item = &'a T>,
+    writer: &mut W,
+) -> Result<()>
+where
+    L: LengthWidth,
+    E: BorshSerializeAs<T>,
+    T: 'a,
+    W: Write,
+{
+    check_zst::<T>()?;
+    L::serialize_length(len, writer)?;
+    for element in elements {
+        E::serialize_as(element, writer)?;
+    }
+    Ok(())
+}
+
+fn deserialize_elements<L, E, T, R>(reader: &mut R) -> Result<Vec<T>>
+where
+    L: LengthWidth,
+    E: BorshDeserializeAs<T>,
+    R: Read,
+{
+    check_zst::<T>()?;
+    let len = L::deserialize_length(reader)?;
+    // TODO(16): return capacity allocation when we can safely do that.
+    let mut result =
+        Vec::with_capacity(hint::cautious::<T>(u32::try_from(len).unwrap_or(u32::MAX)));
+    for _ in 0..len {
+        result.push(E::deserialize_as(reader)?);
+    }
+    Ok(result)
+}
+
+#[cfg(feature = "de_strict_order")]
+fn check_strict_order<T, K: Ord>(elements: &[T], key: impl Fn(&T) -> &K) -> Result<()> {
+    // TODO: replace with `is_sorted` api when stabilizes https://github.com/rust-lang/rust/issues/53485
+    for pair in elements.windows(2) {
+        if key(&pair[0]) >= key(&pair[1]) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                ERROR_WRONG_ORDER_OF_KEYS,
+            ));
+        }
+    }
+    Ok(())
+}
+
+macro_rules! impl_for_vec_like {
+    ($collection: ident) => {
+        impl<L, E, T> BorshSerializeAs<$collection<T>> for Sequence<L, E>
+        where
+            L: LengthWidth,
+            E: BorshSerializeAs<T>,
+        {
+            #[inline]
+            fn serialize_as<W: Write>(source: &$collection<T>, writer: &mut W) -> Result<()> {
+                serialize_elements::<L, E, T, W>(source.len(), source.iter(), writer)
+            }
+        }
+
+        impl<L, E, T> BorshDeserializeAs<$collection<T>> for Sequence<L, E>
+        where
+            L: LengthWidth,
+            E: BorshDeserializeAs<T>,
+        {
+            #[inline]
+            fn deserialize_as<R: Read>(reader: &mut R) -> Result<$collection<T>> {
+                Ok(deserialize_elements::<L, E, T, R>(reader)?
+                    .into_iter()
+                    .collect())
+            }
+        }
+    };
+}
+
+impl_for_vec_like!(Vec);
+impl_for_vec_like!(VecDeque);
+impl_for_vec_like!(LinkedList);
+
+impl<L: LengthWidth> BorshSerializeAs<String> for Sequence<L> {
+    #[inline]
+    fn serialize_as<W: Write>(source: &String, writer: &mut W) -> Result<()> {
+        L::serialize_length(source.len(), writer)?;
+        writer.write_all(source.as_bytes())
+    }
+}
+
+impl<L: LengthWidth> BorshDeserializeAs<String> for Sequence<L> {
+    #[inline]
+    fn deserialize_as<R: Read>(reader: &mut R) -> Result<String> {
+        let bytes = deserialize_elements::<L, Same, u8, R>(reader)?;
+        String::from_utf8(bytes).map_err(|err| {
+            let msg = err.to_string();
+            Error::new(ErrorKind::InvalidData, msg)
+        })
+    }
+}
+
+impl<L, E, T> BorshSerializeAs<BTreeSet<T>> for Sequence<L, E>
+where
+    L: LengthWidth,
+    E: BorshSerializeAs<T>,
+{
+    #[inline]
+    fn serialize_as<W: Write>(source: &BTreeSet<T>, writer: &mut W) -> Result<()> {
+        serialize_elements::<L, E, T, W>(source.len(), source.iter(), writer)
+    }
+}
+
+impl<L, E, T> BorshDeserializeAs<BTreeSet<T>> for Sequence<L, E>
+where
+    L: LengthWidth,
+    E: BorshDeserializeAs<T>,
+    T: Ord,
+{
+    #[inline]
+    fn deserialize_as<R: Read>(reader: &mut R) -> Result<BTreeSet<T>> {
+        let vec = deserialize_elements::<L, E, T, R>(reader)?;
+        #[cfg(feature = "de_strict_order")]
+        check_strict_order(&vec, |element| element)?;
+        Ok(vec.into_iter().collect())
+    }
+}
+
+impl<L, K, V, KE, VE> BorshSerializeAs<BTreeMap<K, V>> for Map<L, KE, VE>
+where
+    L: LengthWidth,
+    KE: BorshSerializeAs<K>,
+    VE: BorshSerializeAs<V>,
+{
+    #[inline]
+    fn serialize_as<W: Write>(source: &BTreeMap<K, V>, writer: &mut W) -> Result<()> {
+        check_zst::<K>()?;
+        // NOTE: BTreeMap iterates over the entries that are sorted by key, so the serialization
+        // result will be consistent without a need to sort the entries as we do for HashMap
+        // serialization.
+        L::serialize_length(source.len(), writer)?;
+        for (key, value) in source {
+            KE::serialize_as(key, writer)?;
+            VE::serialize_as(value, writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<L, K, V, KE, VE> BorshDeserializeAs<BTreeMap<K, V>> for Map<L, KE, VE>
+where
+    L: LengthWidth,
+    KE: BorshDeserializeAs<K>,
+    VE: BorshDeserializeAs<V>,
+    K: Ord,
+{
+    #[inline]
+    fn deserialize_as<R: Read>(reader: &mut R) -> Result<BTreeMap<K, V>> {
+        let vec = deserialize_entries::<L, KE, VE, K, V, R>(reader)?;
+        #[cfg(feature = "de_strict_order")]
+        check_strict_order(&vec, |(key, _value)| key)?;
+        Ok(vec.into_iter().collect())
+    }
+}
+
+fn deserialize_entries<L, KE, VE, K, V, R>(reader: &mut R) -> Result<Vec<(K, V)>>
+where
+    L: LengthWidth,
+    KE: BorshDeserializeAs<K>,
+    VE: BorshDeserializeAs<V>,
+    R: Read,
+{
+    check_zst::<K>()?;
+    let len = L::deserialize_length(reader)?;
+    // TODO(16): return capacity allocation when we can safely do that.
+    let mut result = Vec::with_capacity(hint::cautious::<(K, V)>(
+        u32::try_from(len).unwrap_or(u32::MAX),
+    ));
+    for _ in 0..len {
+        let key = KE::deserialize_as(reader)?;
+        let value = VE::deserialize_as(reader)?;
+        result.push((key, value));
+    }
+    Ok(result)
+}
+
+#[cfg(hash_collections)]
+mod hashes {
+    use core::hash::{BuildHasher, Hash};
+
+    use super::{
+        deserialize_elements, deserialize_entries, serialize_elements, LengthWidth, Map, Sequence,
+    };
+    use crate::__private::maybestd::collections::{HashMap, HashSet};
+    use crate::__private::maybestd::vec::Vec;
+    use crate::de::BorshDeserializeAs;
+    use crate::error::check_zst;
+    use crate::io::{Read, Result, Write};
+    use crate::ser::BorshSerializeAs;
+
+    impl<L, E, T, H> BorshSerializeAs<HashSet<T, H>> for Sequence<L, E>
+    where
+        L: LengthWidth,
+        E: BorshSerializeAs<T>,
+        T: Ord,
+        H: BuildHasher,
+    {
+        #[inline]
+        fn serialize_as<W: Write>(source: &HashSet<T, H>, writer: &mut W) -> Result<()> {
+            let mut vec = source.iter().collect::<Vec<_>>();
+            vec.sort();
+            serialize_elements::<L, E, T, W>(vec.len(), vec.into_iter(), writer)
+        }
+    }
+
+    impl<L, E, T, H> BorshDeserializeAs<HashSet<T, H>> for Sequence<L, E>
+    where
+        L: LengthWidth,
+        E: BorshDeserializeAs<T>,
+        T: Eq + Hash + Ord,
+        H: BuildHasher + Default,
+    {
+        #[inline]
+        fn deserialize_as<R: Read>(reader: &mut R) -> Result<HashSet<T, H>> {
+            let vec = deserialize_elements::<L, E, T, R>(reader)?;
+            #[cfg(feature = "de_strict_order")]
+            super::check_strict_order(&vec, |element| element)?;
+            Ok(vec.into_iter().collect())
+        }
+    }
+
+    impl<L, K, V, KE, VE, H> BorshSerializeAs<HashMap<K, V, H>> for Map<L, KE, VE>
+    where
+        L: LengthWidth,
+        KE: BorshSerializeAs<K>,
+        VE: BorshSerializeAs<V>,
+        K: Ord,
+        H: BuildHasher,
+    {
+        #[inline]
+        fn serialize_as<W: Write>(source: &HashMap<K, V, H>, writer: &mut W) -> Result<()> {
+            check_zst::<K>()?;
+
+            let mut vec = source.iter().collect::<Vec<_>>();
+            vec.sort_by(|a, b| a.0.cmp(b.0));
+            L::serialize_length(vec.len(), writer)?;
+            for (key, value) in vec {
+                KE::serialize_as(key, writer)?;
+                VE::serialize_as(value, writer)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl<L, K, V, KE, VE, H> BorshDeserializeAs<HashMap<K, V, H>> for Map<L, KE, VE>
+    where
+        L: LengthWidth,
+        KE: BorshDeserializeAs<K>,
+        VE: BorshDeserializeAs<V>,
+        K: Eq + Hash + Ord,
+        H: BuildHasher + Default,
+    {
+        #[inline]
+        fn deserialize_as<R: Read>(reader: &mut R) -> Result<HashMap<K, V, H>> {
+            let vec = deserialize_entries::<L, KE, VE, K, V, R>(reader)?;
+            #[cfg(feature = "de_strict_order")]
+            super::check_strict_order(&vec, |(key, _value)| key)?;
+            Ok(vec.into_iter().collect())
+        }
+    }
+}
+
+#[cfg(feature = "unstable__schema")]
+mod schema {
+    use super::{LengthWidth, Map, Same, Sequence};
+    use crate::__private::maybestd::{
+        collections::{BTreeMap, BTreeSet, LinkedList, VecDeque},
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    use crate::schema::{BorshSchemaAs, Declaration, Definition, DefinitionsCollector};
+    use crate::BorshSchema;
+
+    /// Declaration of `name<params>` collection with `L` length prefix.
+    ///
+    /// `u32` prefix is the default one, so declarations coincide with those of
+    /// [`BorshSchema`] impls in that case.
+    fn declaration<L: LengthWidth>(name: &str, params: &[Declaration]) -> Declaration {
+        let mut params = params.to_vec();
+        L::declaration_params(&mut params);
+        if params.is_empty() {
+            name.into()
+        } else {
+            format!("{}<{}>", name, params.join(", "))
+        }
+    }
+
+    impl<T: BorshSchema + ?Sized> BorshSchemaAs<T> for Same {
+        fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+            T::add_definitions_recursively(definitions);
+        }
+
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+            T::collect_definitions(collector);
+        }
+
+        fn declaration() -> Declaration {
+            T::declaration()
+        }
+    }
+
+    impl<T, E> BorshSchemaAs<Option<T>> for Option<E>
+    where
+        E: BorshSchemaAs<T>,
+    {
+        fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+            DefinitionsCollector::collect_or_panic(
+                definitions,
+                <Self as BorshSchemaAs<Option<T>>>::collect_definitions,
+            );
+        }
+
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+            let definition = Definition::Enum {
+                tag_width: 1,
+                variants: vec![
+                    (0u8 as i64, "None".to_string(), <()>::declaration()),
+                    (1u8 as i64, "Some".to_string(), E::declaration()),
+                ],
+            };
+            collector.add_definition(
+                <Self as BorshSchemaAs<Option<T>>>::declaration(),
+                definition,
+            );
+            <()>::collect_definitions(collector);
+            E::collect_definitions(collector);
+        }
+
+        fn declaration() -> Declaration {
+            format!(r#"Option<{}>"#, E::declaration())
+        }
+    }
+
+    macro_rules! impl_for_sequence {
+        ($collection: ident) => {
+            impl<L, E, T> BorshSchemaAs<$collection<T>> for Sequence<L, E>
+            where
+                L: LengthWidth,
+                E: BorshSchemaAs<T>,
+            {
+                fn add_definitions_recursively(
+                    definitions: &mut BTreeMap<Declaration, Definition>,
+                ) {
+                    DefinitionsCollector::collect_or_panic(
+                        definitions,
+                        <Self as BorshSchemaAs<$collection<T>>>::collect_definitions,
+                    );
+                }
+
+                fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+                    L::add_sequence_definition(
+                        <Self as BorshSchemaAs<$collection<T>>>::declaration(),
+                        E::declaration(),
+                        L::MAX,
+                        collector,
+                    );
+                    E::collect_definitions(collector);
+                }
+
+                fn declaration() -> Declaration {
+                    declaration::<L>(stringify!($collection), &[E::declaration()])
+                }
+            }
+        };
+    }
+
+    impl_for_sequence!(Vec);
+    impl_for_sequence!(VecDeque);
+    impl_for_sequence!(LinkedList);
+    impl_for_sequence!(BTreeSet);
+    #[cfg(hash_collections)]
+    impl_for_sequence!(HashSet);
+
+    #[cfg(hash_collections)]
+    use crate::__private::maybestd::collections::{HashMap, HashSet};
+
+    impl<L: LengthWidth> BorshSchemaAs<String> for Sequence<L> {
+        fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+            DefinitionsCollector::collect_or_panic(
+                definitions,
+                <Self as BorshSchemaAs<String>>::collect_definitions,
+            );
+        }
+
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+            L::add_sequence_definition(
+                <Self as BorshSchemaAs<String>>::declaration(),
+                u8::declaration(),
+                L::MAX,
+                collector,
+            );
+            u8::collect_definitions(collector);
+        }
+
+        fn declaration() -> Declaration {
+            declaration::<L>("String", &[])
+        }
+    }
+
+    macro_rules! impl_for_map {
+        ($map: ident) => {
+            impl<L, K, V, KE, VE> BorshSchemaAs<$map<K, V>> for Map<L, KE, VE>
+            where
+                L: LengthWidth,
+                KE: BorshSchemaAs<K>,
+                VE: BorshSchemaAs<V>,
+            {
+                fn add_definitions_recursively(
+                    definitions: &mut BTreeMap<Declaration, Definition>,
+                ) {
+                    DefinitionsCollector::collect_or_panic(
+                        definitions,
+                        <Self as BorshSchemaAs<$map<K, V>>>::collect_definitions,
+                    );
+                }
+
+                fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+                    let entry = format!("({}, {})", KE::declaration(), VE::declaration());
+                    L::add_sequence_definition(
+                        <Self as BorshSchemaAs<$map<K, V>>>::declaration(),
+                        entry.clone(),
+                        L::MAX,
+                        collector,
+                    );
+                    collector.add_definition(
+                        entry,
+                        Definition::Tuple {
+                            elements: vec![KE::declaration(), VE::declaration()],
+                        },
+                    );
+                    KE::collect_definitions(collector);
+                    VE::collect_definitions(collector);
+                }
+
+                fn declaration() -> Declaration {
+                    declaration::<L>(stringify!($map), &[KE::declaration(), VE::declaration()])
+                }
+            }
+        };
+    }
+
+    impl_for_map!(BTreeMap);
+    #[cfg(hash_collections)]
+    impl_for_map!(HashMap);
+}
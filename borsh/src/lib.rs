@@ -16,7 +16,12 @@ pub use borsh_derive::BorshDeserialize;
 #[cfg(feature = "derive")]
 pub use borsh_derive::BorshSerialize;
 
+pub mod bounded;
 pub mod de;
+pub mod discriminator;
+pub mod endian;
+pub mod length;
+pub mod padding;
 
 // See `hash_collections` alias definition in build.rs
 /// Module is available if borsh is built with `features = ["unstable__schema"]`.
@@ -25,14 +30,18 @@ pub mod schema;
 #[cfg(feature = "unstable__schema")]
 pub(crate) mod schema_helpers;
 pub mod ser;
+pub mod varint;
+pub mod with;
 
 pub use de::BorshDeserialize;
+pub use de::BorshDeserializeWith;
 pub use de::{from_reader, from_slice};
 #[cfg(feature = "unstable__schema")]
 pub use schema::BorshSchema;
 #[cfg(feature = "unstable__schema")]
 pub use schema_helpers::{
-    max_serialized_size, schema_container_of, try_from_slice_with_schema, try_to_vec_with_schema,
+    from_slice_with_schema_hash, max_serialized_size, min_serialized_size, schema_container_of,
+    to_vec_with_schema_hash, try_from_slice_with_schema, try_to_vec_with_schema,
 };
 pub use ser::helpers::{object_length, to_vec, to_writer};
 pub use ser::BorshSerialize;
@@ -59,6 +68,70 @@ pub mod io {
 
 #[doc(hidden)]
 pub mod __private {
+    /// Types without padding, for which any bytes of their size are a valid value.
+    ///
+    /// `#[derive(BorshSerialize, BorshDeserialize)]` on a union requires all of its fields
+    /// to implement this trait, as the union is (de)serialized as raw bytes.
+    /// Floats aren't implementors, as raw bytes would bypass rejection of NaN.
+    ///
+    /// # Safety
+    ///
+    /// Implementors must have no padding bytes and no invalid bit patterns.
+    pub unsafe trait Pod: Copy + 'static {}
+
+    macro_rules! impl_pod {
+        ($($type: ty),*) => {
+            $(unsafe impl Pod for $type {})*
+        };
+    }
+
+    impl_pod!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+    unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+    /// Leading fields of a struct, which derived `BorshDeserialize::deserialize` reads with
+    /// a single length check, as long as their types have `BorshDeserialize::FIXED_SIZE`.
+    pub struct FixedPrefix<'a> {
+        bytes: &'a [u8],
+        open: bool,
+    }
+
+    impl<'a> FixedPrefix<'a> {
+        /// Splits the bytes of leading fields of `sizes`, up to the first `None`,
+        /// off the front of `buf`.
+        #[inline]
+        pub fn take(buf: &mut &'a [u8], sizes: &[Option<usize>]) -> crate::io::Result<Self> {
+            let len = sizes.iter().map_while(|size| *size).sum::<usize>();
+            if buf.len() < len {
+                return Err(crate::io::Error::new(
+                    crate::io::ErrorKind::InvalidData,
+                    crate::de::ERROR_UNEXPECTED_LENGTH_OF_INPUT,
+                ));
+            }
+            let (bytes, rest) = buf.split_at(len);
+            *buf = rest;
+            Ok(Self { bytes, open: true })
+        }
+
+        /// Reads the next field from the prefix, or from `buf`, once the prefix is over.
+        #[inline]
+        pub fn read<T: crate::BorshDeserialize>(
+            &mut self,
+            buf: &mut &[u8],
+        ) -> crate::io::Result<T> {
+            match T::FIXED_SIZE {
+                Some(size) if self.open => {
+                    let (bytes, rest) = self.bytes.split_at(size);
+                    self.bytes = rest;
+                    T::from_fixed_bytes(bytes)
+                }
+                _ => {
+                    self.open = false;
+                    T::deserialize(buf)
+                }
+            }
+        }
+    }
 
     /// A facade around all the types we need from the `std`, and `alloc`
     /// crates. This avoids elaborate import wrangling having to happen in every
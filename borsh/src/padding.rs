@@ -0,0 +1,152 @@
+//! Padding and reserved bytes, i.e. fixed-size regions of zero bytes without a value of their own.
+//!
+//! `#[borsh(pad = N)]` and `#[borsh(reserved = N)]` field attributes make derives write `N` zero bytes
+//! before the annotated field. On deserialization padding bytes are ignored, while reserved bytes
+//! are verified to be zero, failing with [`ERROR_RESERVED_NOT_ZERO`] error otherwise.
+//!
+//! Trailing bytes are described with `#[borsh(pad_after = N)]` and `#[borsh(reserved_after = N)]`
+//! struct attributes, which write `N` zero bytes after the last field.
+//!
+//! ```
+//! use borsh::{BorshDeserialize, BorshSerialize};
+//!
+//! /// derive is only available if borsh is built with `features = ["derive"]`
+//! # #[cfg(feature = "derive")]
+//! #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+//! #[borsh(reserved_after = 2)]
+//! struct Header {
+//!     version: u8,
+//!     #[borsh(pad = 3)]
+//!     length: u32,
+//! }
+//!
+//! # #[cfg(feature = "derive")]
+//! # {
+//! let header = Header { version: 1, length: 2 };
+//! let encoded = borsh::to_vec(&header).unwrap();
+//! assert_eq!(encoded, vec![1, 0, 0, 0, 2, 0, 0, 0, 0, 0]);
+//!
+//! // padding is ignored
+//! let decoded = borsh::from_slice::<Header>(&[1, 0xff, 0xff, 0xff, 2, 0, 0, 0, 0, 0]).unwrap();
+//! assert_eq!(decoded, header);
+//!
+//! // reserved bytes have to be zero
+//! let err = borsh::from_slice::<Header>(&[1, 0, 0, 0, 2, 0, 0, 0, 0, 1]).unwrap_err();
+//! assert_eq!(err.to_string(), borsh::padding::ERROR_RESERVED_NOT_ZERO);
+//! # }
+//! ```
+use crate::de::unexpected_eof_to_unexpected_length_of_input;
+use crate::io::{Error, ErrorKind, Read, Result, Write};
+
+pub const ERROR_RESERVED_NOT_ZERO: &str = "Reserved bytes are not zero";
+
+const ZEROS: [u8; 64] = [0; 64];
+
+/// Writes `len` zero bytes.
+///
+/// Used by `#[derive(BorshSerialize)]` for fields with `#[borsh(pad = ...)]`
+/// or `#[borsh(reserved = ...)]` attribute, and structs with `#[borsh(pad_after = ...)]`
+/// or `#[borsh(reserved_after = ...)]` attribute.
+pub fn write_zeros<W: Write>(writer: &mut W, mut len: usize) -> Result<()> {
+    while len > 0 {
+        let chunk = core::cmp::min(len, ZEROS.len());
+        writer.write_all(&ZEROS[..chunk])?;
+        len -= chunk;
+    }
+    Ok(())
+}
+
+/// Reads `len` bytes, ignoring their values.
+///
+/// Used by `#[derive(BorshDeserialize)]` for fields with `#[borsh(pad = ...)]` attribute
+/// and structs with `#[borsh(pad_after = ...)]` attribute.
+pub fn skip<R: Read>(reader: &mut R, len: usize) -> Result<()> {
+    read_chunks(reader, len, |_chunk| {})
+}
+
+/// Reads `len` bytes and checks that all of them are zero.
+///
+/// Used by `#[derive(BorshDeserialize)]` for fields with `#[borsh(reserved = ...)]` attribute
+/// and structs with `#[borsh(reserved_after = ...)]` attribute.
+pub fn check_zeros<R: Read>(reader: &mut R, len: usize) -> Result<()> {
+    let mut zeros = true;
+    // the whole region is consumed, regardless of the position of a non-zero byte
+    read_chunks(reader, len, |chunk| {
+        zeros &= chunk.iter().all(|byte| *byte == 0);
+    })?;
+    if !zeros {
+        return Err(Error::new(ErrorKind::InvalidData, ERROR_RESERVED_NOT_ZERO));
+    }
+    Ok(())
+}
+
+/// Reads `len` bytes into a stack buffer, passing each chunk of them to `f`.
+fn read_chunks<R: Read>(reader: &mut R, mut len: usize, mut f: impl FnMut(&[u8])) -> Result<()> {
+    let mut buf = ZEROS;
+    while len > 0 {
+        let chunk = &mut buf[..core::cmp::min(len, ZEROS.len())];
+        reader
+            .read_exact(chunk)
+            .map_err(unexpected_eof_to_unexpected_length_of_input)?;
+        f(chunk);
+        len -= chunk.len();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::__private::maybestd::{vec, vec::Vec};
+
+    #[test]
+    fn test_write_zeros() {
+        let mut buf = Vec::new();
+        write_zeros(&mut buf, 100).unwrap();
+        assert_eq!(buf, [0u8; 100]);
+
+        let mut buf = Vec::new();
+        write_zeros(&mut buf, 0).unwrap();
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_read() {
+        let mut input: &[u8] = &[1, 2, 0, 0, 3];
+        skip(&mut input, 2).unwrap();
+        check_zeros(&mut input, 2).unwrap();
+        assert_eq!(input, &[3]);
+
+        let mut input: &[u8] = &[0, 1, 0, 7];
+        let err = check_zeros(&mut input, 3).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(input, &[7]);
+
+        let mut input: &[u8] = &[0];
+        assert!(skip(&mut input, 2).is_err());
+    }
+
+    #[test]
+    fn test_read_chunks() {
+        let mut data = vec![0u8; 200];
+        data.push(9);
+        let mut input = data.as_slice();
+        check_zeros(&mut input, 200).unwrap();
+        assert_eq!(input, &[9]);
+
+        // a non-zero byte in a later chunk
+        data[150] = 1;
+        let mut input = data.as_slice();
+        let err = check_zeros(&mut input, 200).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(input, &[9]);
+
+        let mut input = data.as_slice();
+        skip(&mut input, 200).unwrap();
+        assert_eq!(input, &[9]);
+
+        let mut input = &data[..100];
+        let err = skip(&mut input, 130).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
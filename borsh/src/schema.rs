@@ -10,6 +10,15 @@
 //! * `Declaration` is used to describe the type identifier, e.g. `HashMap<u64, String>`;
 //! * `Definition` is used to describe the structure of the type;
 //! * `BorshSchemaContainer` is used to store all declarations and definitions that are needed to work with a single type.
+//! * `Value` is a value of any type, decoded from serialized blob by `BorshSchemaContainer::deserialize_value` using only the schema.
+//!   With `serde_json` feature, `BorshSchemaContainer::serialize_json` and `BorshSchemaContainer::deserialize_json`
+//!   convert between JSON and serialized blobs the same way, and `BorshSchemaContainer::to_json_schema`
+//!   describes such JSON with [JSON Schema](https://json-schema.org/).
+//! * `compat::check` compares two versions of a schema and reports whether blobs of the old one deserialize with the new one.
+//! * `typescript::generate` generates TypeScript type declarations and [borsh-js](https://github.com/near/borsh-js) schemas.
+//! * `rust::generate` generates Rust types with the derives, which reproduce the schema.
+//! * `BorshSchemaContainer` is displayed as a human-readable IDL, e.g. `struct Foo { a: u32, b: "Vec<u8>" }`,
+//!   which can be parsed back with `str::parse`.
 
 #![allow(dead_code)] // Unclear why rust check complains on fields of `Definition` variants.
 use crate as borsh; // For `#[derive(BorshSerialize, BorshDeserialize)]`.
@@ -28,9 +37,22 @@ use core::borrow::Borrow;
 use core::cmp::Ord;
 use core::marker::PhantomData;
 
+pub mod compat;
 mod container_ext;
-
-pub use container_ext::{SchemaContainerValidateError, SchemaMaxSerializedSizeError};
+mod idl;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "serde_json")]
+mod json_schema;
+pub mod rust;
+pub mod typescript;
+mod value;
+
+pub use container_ext::{
+    SchemaContainerValidateError, SchemaMaxSerializedSizeError, SchemaMinSerializedSizeError,
+};
+pub use idl::SchemaParseError;
+pub use value::Value;
 
 /// The type that we use to represent the declaration of the Borsh type.
 pub type Declaration = String;
@@ -69,7 +91,7 @@ pub enum Definition {
     /// * `BoundedVec<LO, HI, T>` → `length_width: 4, length_range: LO..=HI`;
     /// * `PascalString` → `length_width: 1, length_range: 0..=255`;
     /// * `Ipv4Packet` → `length_width: 0, length_range: 20..=65536` or
-    /// * `VarInt<u32>` → `length_width: 0, length_range: 1..=5`.
+    /// * [`VarU32`](crate::varint::VarU32) → `length_width: 0, length_range: 1..=5`.
     Sequence {
         /// How many bytes does the length tag occupy.
         ///
@@ -170,12 +192,51 @@ impl BorshSchemaContainer {
     }
 
     /// generate [BorshSchemaContainer] for type `T`
+    ///
+    /// # Panics
+    ///
+    /// If two different types, which `T` consists of, have the same declaration,
+    /// see [BorshSchemaContainer::try_for_type] for a non-panicking counterpart.
     pub fn for_type<T: BorshSchema + ?Sized>() -> Self {
         let mut definitions = Default::default();
         T::add_definitions_recursively(&mut definitions);
         Self::new(T::declaration(), definitions)
     }
 
+    /// generate [BorshSchemaContainer] for type `T`, returning an error instead of panicking
+    /// if two different types, which `T` consists of, have the same declaration.
+    ///
+    /// Such types can be told apart with `#[borsh(schema(name = "..."))]` attribute
+    /// of `BorshSchema` derive.
+    ///
+    /// Definitions are added with [BorshSchema::collect_definitions], so conflicts within
+    /// types, which don't override it, still panic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use borsh::schema::BorshSchemaContainer;
+    ///
+    /// mod a {
+    ///     #[derive(borsh::BorshSchema)]
+    ///     pub struct Config(pub u8);
+    /// }
+    /// mod b {
+    ///     #[derive(borsh::BorshSchema)]
+    ///     pub struct Config(pub u16);
+    /// }
+    ///
+    /// let err = BorshSchemaContainer::try_for_type::<(a::Config, b::Config)>().unwrap_err();
+    /// assert_eq!(err.declaration, "Config");
+    /// ```
+    pub fn try_for_type<T: BorshSchema + ?Sized>() -> Result<Self, SchemaConflictError> {
+        let mut definitions = BTreeMap::new();
+        let mut collector = DefinitionsCollector::new(&mut definitions);
+        T::collect_definitions(&mut collector);
+        collector.finish()?;
+        Ok(Self::new(T::declaration(), definitions))
+    }
+
     pub fn declaration(&self) -> &Declaration {
         &self.declaration
     }
@@ -242,26 +303,151 @@ where
     }
 }
 
+impl crate::de::ContextFree for BorshSchemaContainer {}
+
+/// Two different definitions of the same declaration, which usually means that two different
+/// types have the same name.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SchemaConflictError {
+    pub declaration: Declaration,
+    /// Definition, which was added first.
+    pub existing: Box<Definition>,
+    /// Definition, which was attempted to be added later.
+    pub conflicting: Box<Definition>,
+}
+
+impl core::fmt::Display for SchemaConflictError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Redefining type schema for {}. Types with the same names are not supported: {:?} != {:?}",
+            self.declaration, self.existing, self.conflicting
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SchemaConflictError {}
+
 /// Helper method to add a single type definition to the map.
+///
+/// # Panics
+///
+/// If a different definition of the same declaration is already present.
+/// Use [DefinitionsCollector::add_definition] to record such conflicts instead.
 pub fn add_definition(
     declaration: Declaration,
     definition: Definition,
     definitions: &mut BTreeMap<Declaration, Definition>,
 ) {
+    if let Err(err) = try_add_definition(declaration, definition, definitions) {
+        panic_on_conflict(err);
+    }
+}
+
+fn panic_on_conflict(err: SchemaConflictError) {
+    assert_eq!(
+        err.existing, err.conflicting,
+        "Redefining type schema for {}. Types with the same names are not supported.",
+        err.declaration
+    );
+}
+
+/// Helper method to add a single type definition to the map, which returns an error
+/// if a different definition of the same declaration is already present.
+pub fn try_add_definition(
+    declaration: Declaration,
+    definition: Definition,
+    definitions: &mut BTreeMap<Declaration, Definition>,
+) -> Result<(), SchemaConflictError> {
     match definitions.entry(declaration) {
         Entry::Occupied(occ) => {
-            let existing_def = occ.get();
-            assert_eq!(
-                existing_def,
-                &definition,
-                "Redefining type schema for {}. Types with the same names are not supported.",
-                occ.key()
-            );
+            if occ.get() != &definition {
+                return Err(SchemaConflictError {
+                    declaration: occ.key().clone(),
+                    existing: Box::new(occ.get().clone()),
+                    conflicting: Box::new(definition),
+                });
+            }
         }
         Entry::Vacant(vac) => {
             vac.insert(definition);
         }
     }
+    Ok(())
+}
+
+/// Map of type definitions, which records conflicting definitions of the same declaration
+/// instead of panicking on them.
+///
+/// Used by [BorshSchemaContainer::try_for_type] through [BorshSchema::collect_definitions].
+pub struct DefinitionsCollector<'a> {
+    definitions: &'a mut BTreeMap<Declaration, Definition>,
+    conflicts: Vec<SchemaConflictError>,
+}
+
+impl<'a> DefinitionsCollector<'a> {
+    pub fn new(definitions: &'a mut BTreeMap<Declaration, Definition>) -> Self {
+        Self {
+            definitions,
+            conflicts: Vec::new(),
+        }
+    }
+
+    /// Runs `collect` on a collector of `definitions`.
+    ///
+    /// # Panics
+    ///
+    /// If `collect` has added conflicting definitions.
+    pub fn collect_or_panic(
+        definitions: &mut BTreeMap<Declaration, Definition>,
+        collect: impl FnOnce(&mut DefinitionsCollector<'_>),
+    ) {
+        let mut collector = DefinitionsCollector::new(definitions);
+        collect(&mut collector);
+        if let Err(err) = collector.finish() {
+            panic_on_conflict(err);
+        }
+    }
+
+    /// Adds a single type definition, keeping the existing one and recording a conflict
+    /// if a different definition of the same declaration is already present.
+    pub fn add_definition(&mut self, declaration: Declaration, definition: Definition) {
+        if let Err(err) = try_add_definition(declaration, definition, self.definitions) {
+            self.conflicts.push(err);
+        }
+    }
+
+    /// Adds definitions, which are collected separately, e.g. by
+    /// [BorshSchema::add_definitions_recursively] into a scratch map, recording conflicts
+    /// with the already present ones.
+    pub fn add_definitions(&mut self, definitions: BTreeMap<Declaration, Definition>) {
+        for (declaration, definition) in definitions {
+            self.add_definition(declaration, definition);
+        }
+    }
+
+    pub fn definitions(&self) -> &BTreeMap<Declaration, Definition> {
+        self.definitions
+    }
+
+    /// Access to the map for [BorshSchema::add_definitions_recursively] implementations,
+    /// which panic on conflicts instead of recording them.
+    pub fn definitions_mut(&mut self) -> &mut BTreeMap<Declaration, Definition> {
+        self.definitions
+    }
+
+    pub fn conflicts(&self) -> &[SchemaConflictError] {
+        &self.conflicts
+    }
+
+    /// Returns the first recorded conflict, if any.
+    pub fn finish(self) -> Result<(), SchemaConflictError> {
+        match self.conflicts.into_iter().next() {
+            Some(conflict) => Err(conflict),
+            None => Ok(()),
+        }
+    }
 }
 
 /// The declaration and the definition of the type that can be used to (de)serialize Borsh without
@@ -271,10 +457,43 @@ pub trait BorshSchema {
     /// Type definition partially explains how to serialize/deserialize a type.
     fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>);
 
+    /// Same as [BorshSchema::add_definitions_recursively], but records conflicting definitions
+    /// in `collector` instead of panicking.
+    ///
+    /// The default implementation runs [BorshSchema::add_definitions_recursively] on a scratch
+    /// map and records conflicts of its definitions with the collected ones, so only conflicts
+    /// between definitions of this type itself still panic.
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+        let mut definitions = BTreeMap::new();
+        Self::add_definitions_recursively(&mut definitions);
+        collector.add_definitions(definitions);
+    }
+
     /// Get the name of the type without brackets.
     fn declaration() -> Declaration;
 }
 
+/// The declaration and the definition of values of type `T`, (de)serialized with a custom encoding.
+///
+/// Counterpart of [`crate::ser::BorshSerializeAs`] and [`crate::de::BorshDeserializeAs`].
+pub trait BorshSchemaAs<T: ?Sized> {
+    /// Recursively, using DFS, add type definitions required for this encoding of `T`.
+    fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>);
+
+    /// Same as [BorshSchemaAs::add_definitions_recursively], but records conflicting definitions
+    /// in `collector` instead of panicking.
+    ///
+    /// The default implementation is the same as the one of [BorshSchema::collect_definitions].
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+        let mut definitions = BTreeMap::new();
+        Self::add_definitions_recursively(&mut definitions);
+        collector.add_definitions(definitions);
+    }
+
+    /// Get the name of this encoding of `T`.
+    fn declaration() -> Declaration;
+}
+
 impl BorshSchema for BorshSchemaContainer
 where
     Declaration: BorshSchema,
@@ -284,6 +503,10 @@ where
         "BorshSchemaContainer".to_string()
     }
     fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+    }
+
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
         let fields = Fields::NamedFields(<[_]>::into_vec(Box::new([
             (
                 "declaration".to_string(),
@@ -295,15 +518,9 @@ where
             ),
         ])));
         let definition = Definition::Struct { fields };
-        add_definition(
-            <Self as BorshSchema>::declaration(),
-            definition,
-            definitions,
-        );
-        <Declaration as BorshSchema>::add_definitions_recursively(definitions);
-        <BTreeMap<Declaration, Definition> as BorshSchema>::add_definitions_recursively(
-            definitions,
-        );
+        collector.add_definition(<Self as BorshSchema>::declaration(), definition);
+        <Declaration as BorshSchema>::collect_definitions(collector);
+        <BTreeMap<Declaration, Definition> as BorshSchema>::collect_definitions(collector);
     }
 }
 impl<T> BorshSchema for Box<T>
@@ -314,6 +531,10 @@ where
         T::add_definitions_recursively(definitions);
     }
 
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+        T::collect_definitions(collector);
+    }
+
     fn declaration() -> Declaration {
         T::declaration()
     }
@@ -327,6 +548,10 @@ where
         T::add_definitions_recursively(definitions);
     }
 
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+        T::collect_definitions(collector);
+    }
+
     fn declaration() -> Declaration {
         T::declaration()
     }
@@ -340,6 +565,10 @@ where
         T::add_definitions_recursively(definitions);
     }
 
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+        T::collect_definitions(collector);
+    }
+
     fn declaration() -> Declaration {
         T::declaration()
     }
@@ -352,7 +581,7 @@ pub mod rc {
     //! [alloc::rc::Rc](std::rc::Rc) and [alloc::sync::Arc](std::sync::Arc).
     use crate::BorshSchema;
 
-    use super::{Declaration, Definition};
+    use super::{Declaration, Definition, DefinitionsCollector};
     use crate::__private::maybestd::collections::BTreeMap;
     use crate::__private::maybestd::{rc::Rc, sync::Arc};
 
@@ -364,6 +593,10 @@ pub mod rc {
             T::add_definitions_recursively(definitions);
         }
 
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+            T::collect_definitions(collector);
+        }
+
         fn declaration() -> Declaration {
             T::declaration()
         }
@@ -377,6 +610,10 @@ pub mod rc {
             T::add_definitions_recursively(definitions);
         }
 
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+            T::collect_definitions(collector);
+        }
+
         fn declaration() -> Declaration {
             T::declaration()
         }
@@ -392,6 +629,10 @@ where
         <T::Owned as BorshSchema>::add_definitions_recursively(definitions);
     }
 
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+        <T::Owned as BorshSchema>::collect_definitions(collector);
+    }
+
     fn declaration() -> Declaration {
         <T::Owned as BorshSchema>::declaration()
     }
@@ -403,8 +644,12 @@ macro_rules! impl_for_renamed_primitives {
         impl BorshSchema for $ty {
             #[inline]
             fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+                DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+            }
+
+            fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
                 let definition = Definition::Primitive($size);
-                add_definition(Self::declaration(), definition, definitions);
+                collector.add_definition(Self::declaration(), definition);
             }
             #[inline]
             fn declaration() -> Declaration { stringify!($name).into() }
@@ -417,8 +662,12 @@ macro_rules! impl_for_renamed_primitives {
         impl BorshSchema for $ty {
             #[inline]
             fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+                DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+            }
+
+            fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
                 let definition = Definition::Primitive($size);
-                add_definition(Self::declaration(), definition, definitions);
+                collector.add_definition(Self::declaration(), definition);
             }
             #[inline]
             fn declaration() -> Declaration { $name.into() }
@@ -458,6 +707,10 @@ impl BorshSchema for String {
     fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
         str::add_definitions_recursively(definitions);
     }
+
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+        str::collect_definitions(collector);
+    }
     #[inline]
     fn declaration() -> Declaration {
         str::declaration()
@@ -467,13 +720,17 @@ impl BorshSchema for String {
 impl BorshSchema for str {
     #[inline]
     fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+    }
+
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
         let definition = Definition::Sequence {
             length_width: Definition::DEFAULT_LENGTH_WIDTH,
             length_range: Definition::DEFAULT_LENGTH_RANGE,
             elements: u8::declaration(),
         };
-        add_definition(Self::declaration(), definition, definitions);
-        u8::add_definitions_recursively(definitions);
+        collector.add_definition(Self::declaration(), definition);
+        u8::collect_definitions(collector);
     }
     #[inline]
     fn declaration() -> Declaration {
@@ -489,7 +746,7 @@ pub mod ascii {
     //! some types from [ascii](::ascii) crate.
     use crate::BorshSchema;
 
-    use super::{add_definition, Declaration, Definition};
+    use super::{Declaration, Definition, DefinitionsCollector};
     use crate::__private::maybestd::collections::BTreeMap;
 
     impl BorshSchema for ascii::AsciiString {
@@ -497,6 +754,10 @@ pub mod ascii {
         fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
             ascii::AsciiStr::add_definitions_recursively(definitions);
         }
+
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+            ascii::AsciiStr::collect_definitions(collector);
+        }
         #[inline]
         fn declaration() -> Declaration {
             ascii::AsciiStr::declaration()
@@ -506,13 +767,17 @@ pub mod ascii {
     impl BorshSchema for ascii::AsciiStr {
         #[inline]
         fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+            DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+        }
+
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
             let definition = Definition::Sequence {
                 length_width: Definition::DEFAULT_LENGTH_WIDTH,
                 length_range: Definition::DEFAULT_LENGTH_RANGE,
                 elements: ascii::AsciiChar::declaration(),
             };
-            add_definition(Self::declaration(), definition, definitions);
-            ascii::AsciiChar::add_definitions_recursively(definitions);
+            collector.add_definition(Self::declaration(), definition);
+            ascii::AsciiChar::collect_definitions(collector);
         }
         #[inline]
         fn declaration() -> Declaration {
@@ -523,7 +788,11 @@ pub mod ascii {
     impl BorshSchema for ascii::AsciiChar {
         #[inline]
         fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
-            add_definition(Self::declaration(), Definition::Primitive(1), definitions);
+            DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+        }
+
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+            collector.add_definition(Self::declaration(), Definition::Primitive(1));
         }
         #[inline]
         fn declaration() -> Declaration {
@@ -535,9 +804,13 @@ pub mod ascii {
 impl BorshSchema for core::ops::RangeFull {
     #[inline]
     fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+    }
+
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
         let fields = Fields::Empty;
         let def = Definition::Struct { fields };
-        add_definition(Self::declaration(), def, definitions);
+        collector.add_definition(Self::declaration(), def);
     }
     #[inline]
     fn declaration() -> Declaration {
@@ -549,13 +822,17 @@ macro_rules! impl_for_range {
     ($type:ident, $($name:ident),*) => {
         impl<T: BorshSchema> BorshSchema for core::ops::$type<T> {
             fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+                DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+            }
+
+            fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
                 let decl = T::declaration();
                 let fields = Fields::NamedFields(vec![$(
                     (FieldName::from(stringify!($name)), decl.clone())
                 ),*]);
                 let def = Definition::Struct { fields };
-                add_definition(Self::declaration(), def, definitions);
-                T::add_definitions_recursively(definitions);
+                collector.add_definition(Self::declaration(), def);
+                T::collect_definitions(collector);
             }
             fn declaration() -> Declaration {
                 format!("{}<{}>", stringify!($type), T::declaration())
@@ -575,6 +852,10 @@ where
     T: BorshSchema,
 {
     fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+    }
+
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
         use core::convert::TryFrom;
         let length = u64::try_from(N).unwrap();
         let definition = Definition::Sequence {
@@ -582,8 +863,8 @@ where
             length_range: length..=length,
             elements: T::declaration(),
         };
-        add_definition(Self::declaration(), definition, definitions);
-        T::add_definitions_recursively(definitions);
+        collector.add_definition(Self::declaration(), definition);
+        T::collect_definitions(collector);
     }
     fn declaration() -> Declaration {
         format!(r#"[{}; {}]"#, T::declaration(), N)
@@ -595,6 +876,10 @@ where
     T: BorshSchema,
 {
     fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+    }
+
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
         let definition = Definition::Enum {
             tag_width: 1,
             variants: vec![
@@ -602,9 +887,9 @@ where
                 (1u8 as i64, "Some".to_string(), T::declaration()),
             ],
         };
-        add_definition(Self::declaration(), definition, definitions);
-        T::add_definitions_recursively(definitions);
-        <()>::add_definitions_recursively(definitions);
+        collector.add_definition(Self::declaration(), definition);
+        T::collect_definitions(collector);
+        <()>::collect_definitions(collector);
     }
 
     fn declaration() -> Declaration {
@@ -618,6 +903,10 @@ where
     E: BorshSchema,
 {
     fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+    }
+
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
         let definition = Definition::Enum {
             tag_width: 1,
             variants: vec![
@@ -625,9 +914,9 @@ where
                 (0u8 as i64, "Err".to_string(), E::declaration()),
             ],
         };
-        add_definition(Self::declaration(), definition, definitions);
-        T::add_definitions_recursively(definitions);
-        E::add_definitions_recursively(definitions);
+        collector.add_definition(Self::declaration(), definition);
+        T::collect_definitions(collector);
+        E::collect_definitions(collector);
     }
 
     fn declaration() -> Declaration {
@@ -642,13 +931,17 @@ macro_rules! impl_for_vec_like_collection {
             T: BorshSchema,
         {
             fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+                DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+            }
+
+            fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
                 let definition = Definition::Sequence {
                     length_width: Definition::DEFAULT_LENGTH_WIDTH,
                     length_range: Definition::DEFAULT_LENGTH_RANGE,
                     elements: T::declaration(),
                 };
-                add_definition(Self::declaration(), definition, definitions);
-                T::add_definitions_recursively(definitions);
+                collector.add_definition(Self::declaration(), definition);
+                T::collect_definitions(collector);
             }
 
             fn declaration() -> Declaration {
@@ -667,13 +960,17 @@ where
     T: BorshSchema,
 {
     fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+    }
+
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
         let definition = Definition::Sequence {
             length_width: Definition::DEFAULT_LENGTH_WIDTH,
             length_range: Definition::DEFAULT_LENGTH_RANGE,
             elements: T::declaration(),
         };
-        add_definition(Self::declaration(), definition, definitions);
-        T::add_definitions_recursively(definitions);
+        collector.add_definition(Self::declaration(), definition);
+        T::collect_definitions(collector);
     }
 
     fn declaration() -> Declaration {
@@ -689,7 +986,7 @@ where
 pub mod hashes {
     use crate::BorshSchema;
 
-    use super::{add_definition, Declaration, Definition};
+    use super::{Declaration, Definition, DefinitionsCollector};
     use crate::__private::maybestd::collections::BTreeMap;
 
     use crate::__private::maybestd::collections::{HashMap, HashSet};
@@ -706,13 +1003,17 @@ pub mod hashes {
         V: BorshSchema,
     {
         fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+            DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+        }
+
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
             let definition = Definition::Sequence {
                 length_width: Definition::DEFAULT_LENGTH_WIDTH,
                 length_range: Definition::DEFAULT_LENGTH_RANGE,
                 elements: <(K, V)>::declaration(),
             };
-            add_definition(Self::declaration(), definition, definitions);
-            <(K, V)>::add_definitions_recursively(definitions);
+            collector.add_definition(Self::declaration(), definition);
+            <(K, V)>::collect_definitions(collector);
         }
 
         fn declaration() -> Declaration {
@@ -725,13 +1026,17 @@ pub mod hashes {
         T: BorshSchema,
     {
         fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+            DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+        }
+
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
             let definition = Definition::Sequence {
                 length_width: Definition::DEFAULT_LENGTH_WIDTH,
                 length_range: Definition::DEFAULT_LENGTH_RANGE,
                 elements: <T>::declaration(),
             };
-            add_definition(Self::declaration(), definition, definitions);
-            <T>::add_definitions_recursively(definitions);
+            collector.add_definition(Self::declaration(), definition);
+            <T>::collect_definitions(collector);
         }
 
         fn declaration() -> Declaration {
@@ -746,13 +1051,17 @@ where
     V: BorshSchema,
 {
     fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+    }
+
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
         let definition = Definition::Sequence {
             length_width: Definition::DEFAULT_LENGTH_WIDTH,
             length_range: Definition::DEFAULT_LENGTH_RANGE,
             elements: <(K, V)>::declaration(),
         };
-        add_definition(Self::declaration(), definition, definitions);
-        <(K, V)>::add_definitions_recursively(definitions);
+        collector.add_definition(Self::declaration(), definition);
+        <(K, V)>::collect_definitions(collector);
     }
 
     fn declaration() -> Declaration {
@@ -765,13 +1074,17 @@ where
     T: BorshSchema,
 {
     fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+    }
+
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
         let definition = Definition::Sequence {
             length_width: Definition::DEFAULT_LENGTH_WIDTH,
             length_range: Definition::DEFAULT_LENGTH_RANGE,
             elements: <T>::declaration(),
         };
-        add_definition(Self::declaration(), definition, definitions);
-        <T>::add_definitions_recursively(definitions);
+        collector.add_definition(Self::declaration(), definition);
+        <T>::collect_definitions(collector);
     }
 
     fn declaration() -> Declaration {
@@ -786,6 +1099,10 @@ impl<T> BorshSchema for PhantomData<T> {
         <()>::add_definitions_recursively(definitions);
     }
 
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+        <()>::collect_definitions(collector);
+    }
+
     fn declaration() -> Declaration {
         <()>::declaration()
     }
@@ -798,12 +1115,16 @@ macro_rules! impl_tuple {
         $($name: BorshSchema),+
     {
         fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+            DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+        }
+
+        fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
             let elements = vec![$($name::declaration()),+];
 
             let definition = Definition::Tuple { elements };
-            add_definition(Self::declaration(), definition, definitions);
+            collector.add_definition(Self::declaration(), definition);
             $(
-                $name::add_definitions_recursively(definitions);
+                $name::collect_definitions(collector);
             )+
         }
 
@@ -877,6 +1198,10 @@ impl BorshSchema for std::net::Ipv4Addr {
         <ip_addr_std_derive_impl::Ipv4Addr>::add_definitions_recursively(definitions);
     }
 
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+        <ip_addr_std_derive_impl::Ipv4Addr>::collect_definitions(collector);
+    }
+
     fn declaration() -> Declaration {
         ip_addr_std_derive_impl::Ipv4Addr::declaration()
     }
@@ -888,6 +1213,10 @@ impl BorshSchema for std::net::Ipv6Addr {
         <ip_addr_std_derive_impl::Ipv6Addr>::add_definitions_recursively(definitions);
     }
 
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+        <ip_addr_std_derive_impl::Ipv6Addr>::collect_definitions(collector);
+    }
+
     fn declaration() -> Declaration {
         ip_addr_std_derive_impl::Ipv6Addr::declaration()
     }
@@ -899,6 +1228,10 @@ impl BorshSchema for std::net::IpAddr {
         <ip_addr_std_derive_impl::IpAddr>::add_definitions_recursively(definitions);
     }
 
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+        <ip_addr_std_derive_impl::IpAddr>::collect_definitions(collector);
+    }
+
     fn declaration() -> Declaration {
         ip_addr_std_derive_impl::IpAddr::declaration()
     }
@@ -0,0 +1,401 @@
+//! Compatibility check between two versions of a schema, e.g. of a type before and after
+//! an upgrade, which tells whether blobs serialized with the old version still deserialize
+//! with the new one.
+//!
+//! # Example
+//!
+//! ```
+//! use borsh::schema::{compat, BorshSchemaContainer};
+//!
+//! let old = BorshSchemaContainer::for_type::<(u8, Option<u16>)>();
+//! let new = BorshSchemaContainer::for_type::<(u8, Option<u32>)>();
+//! let report = compat::check(&old, &new);
+//! assert_eq!(report.compatibility(), compat::Compatibility::Incompatible);
+//! assert_eq!(
+//!     report.differences()[0].to_string(),
+//!     "`(u8, Option<u16>).1::Some`: `u16` changed to `u32`"
+//! );
+//! ```
+
+use core::fmt;
+use core::ops::RangeInclusive;
+
+use super::{
+    BorshSchemaContainer, Declaration, Definition, DiscriminantValue, Fields, VariantName,
+};
+use crate::__private::maybestd::{
+    collections::BTreeSet,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Kind of a difference between two schemas, ordered from the least to the most severe.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Compatibility {
+    /// Both schemas describe the same wire format with the same names.
+    Identical,
+    /// Both schemas describe the same wire format, but some fields or variants are renamed.
+    Renamed,
+    /// The new schema accepts every blob of the old one, but not vice versa, e.g. enum variants
+    /// are appended or length range of a sequence is relaxed.
+    Extended,
+    /// Some blobs of the old schema don't deserialize with the new one, or deserialize
+    /// into different values.
+    Incompatible,
+}
+
+/// A single difference between two schemas.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Difference {
+    /// Path to the differing value from the root of the old schema, e.g. `Drawing.shapes[]::Circle.radius`,
+    /// where `.` accesses a field, `[]` an element of a sequence and `::` a variant of an enum.
+    pub path: String,
+    /// Severity of the difference, never [`Compatibility::Identical`].
+    pub compatibility: Compatibility,
+    /// Human-readable explanation of the difference.
+    pub description: String,
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.path, self.description)
+    }
+}
+
+/// Result of [`check`].
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Report {
+    differences: Vec<Difference>,
+}
+
+impl Report {
+    /// The most severe of the differences, [`Compatibility::Identical`] if there are none.
+    pub fn compatibility(&self) -> Compatibility {
+        self.differences
+            .iter()
+            .map(|difference| difference.compatibility)
+            .max()
+            .unwrap_or(Compatibility::Identical)
+    }
+
+    /// Whether every blob of the old schema deserializes with the new one into the same value.
+    pub fn is_backward_compatible(&self) -> bool {
+        self.compatibility() < Compatibility::Incompatible
+    }
+
+    /// All differences found, in the order of traversal of the old schema.
+    ///
+    /// A definition, which is reachable by several paths, is compared once,
+    /// so that its differences are reported only at the first of these paths.
+    pub fn differences(&self) -> &[Difference] {
+        &self.differences
+    }
+}
+
+/// Compares definitions of `old` and `new` schemas, starting from their declarations.
+///
+/// Names of the declarations don't take part in comparison, as they aren't present in
+/// serialized blobs, so renaming a type is not a difference. A struct with a single field
+/// is considered the same as the type of the field, and all zero-sized definitions are
+/// considered the same.
+pub fn check(old: &BorshSchemaContainer, new: &BorshSchemaContainer) -> Report {
+    let mut checker = Checker {
+        old,
+        new,
+        visited: BTreeSet::new(),
+        report: Report::default(),
+    };
+    checker.compare(
+        old.declaration(),
+        new.declaration(),
+        old.declaration().clone(),
+    );
+    checker.report
+}
+
+/// Wire layout of a definition, where tuples and structs are the same.
+enum Layout<'a> {
+    Primitive(u8),
+    Sequence {
+        length_width: u8,
+        length_range: &'a RangeInclusive<u64>,
+        elements: &'a Declaration,
+    },
+    /// Fields of a struct or elements of a tuple, with names of the former.
+    Product(Vec<(Option<&'a str>, &'a Declaration)>),
+    Enum {
+        tag_width: u8,
+        variants: &'a [(DiscriminantValue, VariantName, Declaration)],
+    },
+}
+
+impl<'a> Layout<'a> {
+    fn new(definition: &'a Definition) -> Self {
+        match definition {
+            Definition::Primitive(size) => Layout::Primitive(*size),
+            Definition::Sequence {
+                length_width,
+                length_range,
+                elements,
+            } => Layout::Sequence {
+                length_width: *length_width,
+                length_range,
+                elements,
+            },
+            Definition::Tuple { elements } => {
+                Layout::Product(elements.iter().map(|element| (None, element)).collect())
+            }
+            Definition::Struct { fields } => Layout::Product(match fields {
+                Fields::NamedFields(fields) => fields
+                    .iter()
+                    .map(|(name, field)| (Some(name.as_str()), field))
+                    .collect(),
+                Fields::UnnamedFields(fields) => fields.iter().map(|field| (None, field)).collect(),
+                Fields::Empty => Vec::new(),
+            }),
+            Definition::Enum {
+                tag_width,
+                variants,
+            } => Layout::Enum {
+                tag_width: *tag_width,
+                variants,
+            },
+        }
+    }
+
+    fn is_zero_size(&self) -> bool {
+        match self {
+            Layout::Primitive(size) => *size == 0,
+            Layout::Product(fields) => fields.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Type of the single field of a struct or a tuple.
+    fn newtype(&self) -> Option<&'a Declaration> {
+        match self {
+            Layout::Product(fields) if fields.len() == 1 => Some(fields[0].1),
+            _ => None,
+        }
+    }
+}
+
+fn field_name(name: Option<&str>, index: usize) -> String {
+    match name {
+        Some(name) => name.to_string(),
+        None => index.to_string(),
+    }
+}
+
+fn format_range(range: &RangeInclusive<u64>) -> String {
+    format!("{}..={}", range.start(), range.end())
+}
+
+struct Checker<'a> {
+    old: &'a BorshSchemaContainer,
+    new: &'a BorshSchemaContainer,
+    /// Pairs of declarations, which are already compared, so that recursive types terminate.
+    visited: BTreeSet<(&'a str, &'a str)>,
+    report: Report,
+}
+
+impl<'a> Checker<'a> {
+    fn report(&mut self, path: &str, compatibility: Compatibility, description: String) {
+        self.report.differences.push(Difference {
+            path: path.to_string(),
+            compatibility,
+            description,
+        });
+    }
+
+    /// Compares the single field of a struct on either side with the other side, keeping the
+    /// differences only if they are compatible, so that a struct wrapping another struct or a
+    /// tuple is the same as the wrapped type, while other changes of the number of fields are
+    /// reported as such.
+    fn compare_newtype(
+        &mut self,
+        old: &'a Declaration,
+        new: &'a Declaration,
+        old_layout: &Layout<'a>,
+        new_layout: &Layout<'a>,
+        path: &str,
+    ) -> bool {
+        let (old, new) = match (old_layout.newtype(), new_layout.newtype()) {
+            (Some(old_field), _) => (old_field, new),
+            (_, Some(new_field)) => (old, new_field),
+            _ => return false,
+        };
+        let visited = self.visited.clone();
+        let reported = self.report.differences.len();
+        self.compare(old, new, path.to_string());
+        let compatible = self.report.differences[reported..]
+            .iter()
+            .all(|difference| difference.compatibility != Compatibility::Incompatible);
+        if !compatible {
+            self.visited = visited;
+            self.report.differences.truncate(reported);
+        }
+        compatible
+    }
+
+    fn compare(&mut self, old: &'a Declaration, new: &'a Declaration, path: String) {
+        if !self.visited.insert((old, new)) {
+            return;
+        }
+        let (old_definition, new_definition) =
+            match (self.old.get_definition(old), self.new.get_definition(new)) {
+                (Some(old_definition), Some(new_definition)) => (old_definition, new_definition),
+                (None, _) => {
+                    let description = format!("missing definition of `{}` in old schema", old);
+                    return self.report(&path, Compatibility::Incompatible, description);
+                }
+                (_, None) => {
+                    let description = format!("missing definition of `{}` in new schema", new);
+                    return self.report(&path, Compatibility::Incompatible, description);
+                }
+            };
+        let old_layout = Layout::new(old_definition);
+        let new_layout = Layout::new(new_definition);
+        if old_layout.is_zero_size() && new_layout.is_zero_size() {
+            return;
+        }
+
+        match (&old_layout, &new_layout) {
+            (Layout::Primitive(old_size), Layout::Primitive(new_size)) => {
+                if old_size != new_size || old != new {
+                    let description = format!("`{}` changed to `{}`", old, new);
+                    self.report(&path, Compatibility::Incompatible, description);
+                }
+            }
+            (
+                Layout::Sequence {
+                    length_width: old_width,
+                    length_range: old_range,
+                    elements: old_elements,
+                },
+                Layout::Sequence {
+                    length_width: new_width,
+                    length_range: new_range,
+                    elements: new_elements,
+                },
+            ) => {
+                if old_width != new_width {
+                    let description = format!(
+                        "length width of `{}` changed from {} to {}",
+                        old, old_width, new_width
+                    );
+                    return self.report(&path, Compatibility::Incompatible, description);
+                }
+                if old_range != new_range {
+                    let relaxed = *old_width != Definition::ARRAY_LENGTH_WIDTH
+                        && new_range.contains(old_range.start())
+                        && new_range.contains(old_range.end());
+                    let (compatibility, verb) = if relaxed {
+                        (Compatibility::Extended, "relaxed")
+                    } else {
+                        (Compatibility::Incompatible, "changed")
+                    };
+                    let description = format!(
+                        "length range of `{}` {} from {} to {}",
+                        old,
+                        verb,
+                        format_range(old_range),
+                        format_range(new_range)
+                    );
+                    self.report(&path, compatibility, description);
+                }
+                self.compare(old_elements, new_elements, format!("{}[]", path));
+            }
+            (Layout::Product(old_fields), Layout::Product(new_fields)) => {
+                if old_fields.len() != new_fields.len() {
+                    if self.compare_newtype(old, new, &old_layout, &new_layout, &path) {
+                        return;
+                    }
+                    let description = format!(
+                        "number of fields of `{}` changed from {} to {}",
+                        old,
+                        old_fields.len(),
+                        new_fields.len()
+                    );
+                    return self.report(&path, Compatibility::Incompatible, description);
+                }
+                for (index, ((old_name, old_field), (new_name, new_field))) in
+                    old_fields.iter().zip(new_fields).enumerate()
+                {
+                    let old_name = field_name(*old_name, index);
+                    let new_name = field_name(*new_name, index);
+                    let path = format!("{}.{}", path, old_name);
+                    if old_name != new_name {
+                        let description = format!("field `{}` renamed to `{}`", old_name, new_name);
+                        self.report(&path, Compatibility::Renamed, description);
+                    }
+                    self.compare(old_field, new_field, path);
+                }
+            }
+            (
+                Layout::Enum {
+                    tag_width: old_width,
+                    variants: old_variants,
+                },
+                Layout::Enum {
+                    tag_width: new_width,
+                    variants: new_variants,
+                },
+            ) => {
+                if old_width != new_width {
+                    let description = format!(
+                        "tag width of `{}` changed from {} to {}",
+                        old, old_width, new_width
+                    );
+                    return self.report(&path, Compatibility::Incompatible, description);
+                }
+                for (discriminant, old_name, old_variant) in old_variants.iter() {
+                    let path = format!("{}::{}", path, old_name);
+                    let new_variant = new_variants
+                        .iter()
+                        .find(|(new_discriminant, _, _)| new_discriminant == discriminant);
+                    match new_variant {
+                        Some((_, new_name, new_variant)) => {
+                            if old_name != new_name {
+                                let description =
+                                    format!("variant `{}` renamed to `{}`", old_name, new_name);
+                                self.report(&path, Compatibility::Renamed, description);
+                            }
+                            self.compare(old_variant, new_variant, path);
+                        }
+                        None => {
+                            let description = format!(
+                                "variant `{}` with discriminant {} removed",
+                                old_name, discriminant
+                            );
+                            self.report(&path, Compatibility::Incompatible, description);
+                        }
+                    }
+                }
+                for (discriminant, new_name, _) in new_variants.iter() {
+                    if old_variants
+                        .iter()
+                        .all(|(old_discriminant, _, _)| old_discriminant != discriminant)
+                    {
+                        let description = format!(
+                            "variant `{}` with discriminant {} appended",
+                            new_name, discriminant
+                        );
+                        self.report(&path, Compatibility::Extended, description);
+                    }
+                }
+            }
+            _ => {
+                if let Some(old_field) = old_layout.newtype() {
+                    return self.compare(old_field, new, path);
+                }
+                if let Some(new_field) = new_layout.newtype() {
+                    return self.compare(old, new_field, path);
+                }
+                let description = format!("`{}` changed to `{}`", old, new);
+                self.report(&path, Compatibility::Incompatible, description);
+            }
+        }
+    }
+}
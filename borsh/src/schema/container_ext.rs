@@ -2,7 +2,11 @@ use super::{BorshSchemaContainer, Declaration, Definition, Fields};
 
 pub use max_size::Error as SchemaMaxSerializedSizeError;
 use max_size::{is_zero_size, ZeroSizeError};
+pub(super) use min_size::min_serialized_size_of;
+pub use min_size::Error as SchemaMinSerializedSizeError;
 pub use validate::Error as SchemaContainerValidateError;
 
+mod fingerprint;
 mod max_size;
+mod min_size;
 mod validate;
@@ -0,0 +1,194 @@
+use super::{BorshSchemaContainer, Declaration, Definition, Fields};
+use crate::__private::maybestd::{collections::BTreeMap, format, vec, vec::Vec};
+use crate::io::{Result, Write};
+use crate::BorshSerialize;
+
+impl BorshSchemaContainer {
+    /// Stable 64-bit hash of the schema, which can be used to tell whether two schemas
+    /// describe the same format without comparing them in full.
+    ///
+    /// The hash is computed over a canonical form of the container, where declarations of
+    /// all non-primitive types are replaced with their index in the depth-first traversal
+    /// from the root declaration, so it doesn't depend on names of types, e.g. on how names of
+    /// generic types are formatted, nor on definitions, which are unreachable from the root.
+    /// Names of primitive types, fields and variants do take part in the hash, as well as
+    /// declarations of strings, varints and discriminators, whose encoding isn't fully described
+    /// by their definitions, e.g. `VarU32` and `VarI32` are both defined as sequences of `u8`.
+    ///
+    /// The hash is [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/) of borsh-serialized
+    /// canonical container. It is not cryptographic and shouldn't be relied upon to tell apart
+    /// schemas crafted to collide.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use borsh::schema::BorshSchemaContainer;
+    ///
+    /// #[derive(borsh::BorshSchema)]
+    /// struct A(u8, Vec<u32>);
+    /// #[derive(borsh::BorshSchema)]
+    /// struct B(u8, Vec<u32>);
+    ///
+    /// let a = BorshSchemaContainer::for_type::<A>();
+    /// assert_eq!(a.fingerprint(), BorshSchemaContainer::for_type::<B>().fingerprint());
+    /// assert_ne!(a.fingerprint(), BorshSchemaContainer::for_type::<(u8, Vec<i32>)>().fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a::default();
+        self.canonicalize()
+            .serialize(&mut hasher)
+            .expect("writing to a hasher can't fail");
+        hasher.0
+    }
+
+    fn canonicalize(&self) -> BorshSchemaContainer {
+        let mut names = BTreeMap::new();
+        let mut stack = vec![self.declaration()];
+        while let Some(declaration) = stack.pop() {
+            if names.contains_key(declaration) {
+                continue;
+            }
+            let definition = match self.get_definition(declaration) {
+                Some(definition) => definition,
+                None => continue,
+            };
+            let name = match definition {
+                Definition::Primitive(_) => declaration.clone(),
+                _ if is_significant(declaration) => declaration.clone(),
+                _ => format!("#{}", names.len()),
+            };
+            names.insert(declaration, name);
+            let mut children = children(definition);
+            children.reverse();
+            stack.extend(children);
+        }
+
+        let rename = |declaration: &Declaration| {
+            names
+                .get(declaration)
+                .cloned()
+                .unwrap_or_else(|| declaration.clone())
+        };
+        let definitions = names
+            .iter()
+            .map(|(declaration, name)| {
+                let definition = rename_children(&self.definitions[*declaration], rename);
+                (name.clone(), definition)
+            })
+            .collect();
+        BorshSchemaContainer::new(rename(self.declaration()), definitions)
+    }
+}
+
+/// Declarations of types, which are told apart by name only, as their definitions coincide
+/// with those of other types: a `String` is a `Vec<u8>`, which is valid UTF-8,
+/// a varint is a sequence of `u8`, interpreted differently depending on its type, and a discriminator
+/// is a fixed-length sequence of `u8`, whose expected bytes are in its name.
+const SIGNIFICANT_DECLARATIONS: &[&str] = &[
+    "String",
+    "AsciiString",
+    "VarU16",
+    "VarU32",
+    "VarU64",
+    "VarU128",
+    "VarI16",
+    "VarI32",
+    "VarI64",
+    "VarI128",
+];
+
+/// Prefixes of parameterized significant declarations, see [SIGNIFICANT_DECLARATIONS].
+const SIGNIFICANT_PREFIXES: &[&str] = &["String<", "BoundedString<", "Discriminator<"];
+
+fn is_significant(declaration: &Declaration) -> bool {
+    SIGNIFICANT_DECLARATIONS.contains(&declaration.as_str())
+        || SIGNIFICANT_PREFIXES
+            .iter()
+            .any(|prefix| declaration.starts_with(prefix))
+}
+
+fn children(definition: &Definition) -> Vec<&Declaration> {
+    match definition {
+        Definition::Primitive(_) => Vec::new(),
+        Definition::Sequence { elements, .. } => vec![elements],
+        Definition::Tuple { elements } => elements.iter().collect(),
+        Definition::Enum { variants, .. } => variants
+            .iter()
+            .map(|(_, _, declaration)| declaration)
+            .collect(),
+        Definition::Struct { fields } => match fields {
+            Fields::NamedFields(fields) => fields.iter().map(|(_, field)| field).collect(),
+            Fields::UnnamedFields(fields) => fields.iter().collect(),
+            Fields::Empty => Vec::new(),
+        },
+    }
+}
+
+fn rename_children(
+    definition: &Definition,
+    rename: impl Fn(&Declaration) -> Declaration,
+) -> Definition {
+    match definition {
+        Definition::Primitive(size) => Definition::Primitive(*size),
+        Definition::Sequence {
+            length_width,
+            length_range,
+            elements,
+        } => Definition::Sequence {
+            length_width: *length_width,
+            length_range: length_range.clone(),
+            elements: rename(elements),
+        },
+        Definition::Tuple { elements } => Definition::Tuple {
+            elements: elements.iter().map(rename).collect(),
+        },
+        Definition::Enum {
+            tag_width,
+            variants,
+        } => Definition::Enum {
+            tag_width: *tag_width,
+            variants: variants
+                .iter()
+                .map(|(discriminant, name, declaration)| {
+                    (*discriminant, name.clone(), rename(declaration))
+                })
+                .collect(),
+        },
+        Definition::Struct { fields } => Definition::Struct {
+            fields: match fields {
+                Fields::NamedFields(fields) => Fields::NamedFields(
+                    fields
+                        .iter()
+                        .map(|(name, field)| (name.clone(), rename(field)))
+                        .collect(),
+                ),
+                Fields::UnnamedFields(fields) => {
+                    Fields::UnnamedFields(fields.iter().map(rename).collect())
+                }
+                Fields::Empty => Fields::Empty,
+            },
+        },
+    }
+}
+
+/// 64-bit FNV-1a hasher.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Write for Fnv1a {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        for byte in buf {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
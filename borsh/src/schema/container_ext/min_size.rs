@@ -0,0 +1,194 @@
+use super::{BorshSchemaContainer, Declaration, Definition, Fields};
+use crate::__private::maybestd::{string::ToString, vec::Vec};
+
+impl BorshSchemaContainer {
+    /// Returns the smallest possible size of a serialised object based solely on its type,
+    /// so that buffers, which are shorter, can be rejected without deserializing them.
+    ///
+    /// Sequences are assumed to have the least number of elements and enums the smallest
+    /// variant. Recursive types are sized by their smallest non-recursive values, e.g.
+    /// `None` of `Option<Box<Self>>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use borsh::schema::BorshSchemaContainer;
+    ///
+    /// let schema = BorshSchemaContainer::for_type::<(u8, Vec<u64>, Option<u32>)>();
+    /// assert_eq!(Ok(1 + 4 + 1), schema.min_serialized_size());
+    ///
+    /// #[derive(borsh::BorshSchema)]
+    /// enum List {
+    ///     Cons(u64, Box<List>),
+    ///     Nil,
+    /// }
+    ///
+    /// let schema = BorshSchemaContainer::for_type::<List>();
+    /// assert_eq!(Ok(1), schema.min_serialized_size());
+    /// ```
+    pub fn min_serialized_size(&self) -> Result<usize, Error> {
+        min_serialized_size_of(self.declaration(), self)
+    }
+
+    /// Returns the size of every serialised object of the type, if it's the same for all of them,
+    /// i.e. the type contains no sequences of variable length or enums with variants of
+    /// different sizes.
+    ///
+    /// `None` is also returned, if the size overflows `usize` or the schema lacks definitions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use borsh::schema::BorshSchemaContainer;
+    ///
+    /// let schema = BorshSchemaContainer::for_type::<(u8, [u32; 2], Result<u16, [u8; 2]>)>();
+    /// assert_eq!(Some(1 + 8 + 3), schema.fixed_size());
+    ///
+    /// let schema = BorshSchemaContainer::for_type::<Option<u8>>();
+    /// assert_eq!(None, schema.fixed_size());
+    /// ```
+    pub fn fixed_size(&self) -> Option<usize> {
+        let min = self.min_serialized_size().ok()?;
+        let max = self.max_serialized_size().ok()?;
+        if min == max {
+            Some(min)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether all serialised objects of the type have the same size,
+    /// see [`BorshSchemaContainer::fixed_size`].
+    pub fn is_fixed_size(&self) -> bool {
+        self.fixed_size().is_some()
+    }
+}
+
+/// Possible error when calculating theoretical minimum size of encoded type `T`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// The theoretical minimum size of the encoded value overflows `usize`.
+    ///
+    /// This may happen for nested arrays such as `[[u64; u32::MAX as usize]; u32::MAX as usize]`.
+    Overflow,
+
+    /// The type has no values of finite size, as it's recursive without an exit.
+    ///
+    /// Simple type in which this triggers is `struct Rec(Box<Rec>)`.
+    Recursive,
+
+    /// Some of the declared types were lacking definition making it impossible
+    /// to calculate the size.
+    MissingDefinition(Declaration),
+}
+
+/// Minimum size of serialised values of `declaration`, which is defined in `schema`,
+/// see [`BorshSchemaContainer::min_serialized_size`].
+pub(in crate::schema) fn min_serialized_size_of(
+    declaration: &str,
+    schema: &BorshSchemaContainer,
+) -> Result<usize, Error> {
+    let mut stack = Vec::new();
+    min_serialized_size_impl(declaration, schema, &mut stack)?.ok_or(Error::Recursive)
+}
+
+/// Implementation of [`BorshSchemaContainer::min_serialized_size`].
+///
+/// Returns `None` if all values of the type contain a declaration on the `stack`. The smallest
+/// value of a recursive type doesn't contain itself, as replacing the outer value with the nested
+/// one only makes it smaller, so such paths can be skipped.
+fn min_serialized_size_impl<'a>(
+    declaration: &'a str,
+    schema: &'a BorshSchemaContainer,
+    stack: &mut Vec<&'a str>,
+) -> Result<Option<usize>, Error> {
+    use core::convert::TryFrom;
+
+    fn add(x: usize, y: usize) -> Result<usize, Error> {
+        x.checked_add(y).ok_or(Error::Overflow)
+    }
+
+    /// Calculates min serialised size of a tuple with given members.
+    fn tuple<'a>(
+        elements: impl core::iter::IntoIterator<Item = &'a Declaration>,
+        schema: &'a BorshSchemaContainer,
+        stack: &mut Vec<&'a str>,
+    ) -> Result<Option<usize>, Error> {
+        let mut sum: usize = 0;
+        for el in elements {
+            match min_serialized_size_impl(el, schema, stack)? {
+                Some(size) => sum = add(sum, size)?,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(sum))
+    }
+
+    if stack.contains(&declaration) {
+        return Ok(None);
+    }
+    stack.push(declaration);
+
+    let res = match schema.get_definition(declaration).ok_or(declaration) {
+        Ok(Definition::Primitive(size)) => Some(usize::from(*size)),
+        Ok(Definition::Sequence {
+            length_width,
+            length_range,
+            elements,
+        }) => {
+            // Assume sequence has the minimum number of elements.
+            let min_len = *length_range.start();
+            let size = if min_len == 0 {
+                Some(0)
+            } else {
+                match min_serialized_size_impl(elements, schema, stack)? {
+                    Some(0) => Some(0),
+                    Some(size) => {
+                        let min_len = usize::try_from(min_len).map_err(|_| Error::Overflow)?;
+                        Some(min_len.checked_mul(size).ok_or(Error::Overflow)?)
+                    }
+                    None => None,
+                }
+            };
+            match size {
+                Some(size) => Some(add(size, usize::from(*length_width))?),
+                None => None,
+            }
+        }
+
+        Ok(Definition::Enum {
+            tag_width,
+            variants,
+        }) => {
+            let mut sizes = Vec::with_capacity(variants.len());
+            for (_, _, variant) in variants {
+                sizes.extend(min_serialized_size_impl(variant, schema, stack)?);
+            }
+            // Untagged enums, i.e. unions, are as large as their largest variant.
+            let size = if *tag_width == 0 {
+                sizes.into_iter().max()
+            } else {
+                sizes.into_iter().min()
+            };
+            match size {
+                Some(size) => Some(add(size, usize::from(*tag_width))?),
+                None => None,
+            }
+        }
+
+        // Tuples and structs sum sizes of all the members.
+        Ok(Definition::Tuple { elements }) => tuple(elements, schema, stack)?,
+        Ok(Definition::Struct { fields }) => match fields {
+            Fields::NamedFields(fields) => {
+                tuple(fields.iter().map(|(_, field)| field), schema, stack)?
+            }
+            Fields::UnnamedFields(fields) => tuple(fields, schema, stack)?,
+            Fields::Empty => Some(0),
+        },
+
+        Err(declaration) => return Err(Error::MissingDefinition(declaration.to_string())),
+    };
+
+    stack.pop();
+    Ok(res)
+}
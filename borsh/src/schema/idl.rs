@@ -0,0 +1,561 @@
+//! Textual representation of [`BorshSchemaContainer`], which is printed by its `Display`
+//! implementation and parsed back by its `FromStr` implementation.
+//!
+//! ```text
+//! root Shape;
+//!
+//! enum Shape: u8 {
+//!     Circle(ShapeCircle) = 0,
+//!     Polygon(ShapePolygon) = 1,
+//! }
+//!
+//! struct ShapeCircle {
+//!     radius: f32,
+//! }
+//!
+//! struct ShapePolygon("Vec<(u8, u8)>");
+//!
+//! type "(u8, u8)" = (u8, u8);
+//!
+//! type "Vec<(u8, u8)>" = seq<u32> "(u8, u8)";
+//!
+//! type f32 = primitive(4);
+//!
+//! type u8 = primitive(1);
+//! ```
+//!
+//! Declarations and names of fields and variants, which are not identifiers or paths,
+//! are quoted. Widths of lengths of sequences and of tags of enums are `u8`, `u16`, `u32`
+//! and `u64` for 1, 2, 4 and 8 bytes and plain numbers of bytes otherwise, e.g. `seq<0, 2..=2> u8`
+//! for `[u8; 2]`. The length range of a sequence is omitted, if it's the default one.
+//! `//` starts a comment till the end of line.
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+use super::{BorshSchemaContainer, Declaration, Definition, Fields};
+use crate::__private::maybestd::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Error of parsing [`BorshSchemaContainer`] from its textual representation.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SchemaParseError {
+    /// Line of the error, starting from 1.
+    pub line: usize,
+    /// Column of the error in characters, starting from 1.
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}:{}", self.message, self.line, self.column)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SchemaParseError {}
+
+/// Declaration or name of a field or a variant, quoted if it's not an identifier or a path.
+struct Name<'a>(&'a str);
+
+impl Name<'_> {
+    fn is_identifier(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars
+            .next()
+            .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+}
+
+impl fmt::Display for Name<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.split("::").all(Self::is_identifier) {
+            f.write_str(self.0)
+        } else {
+            write!(f, "{:?}", self.0)
+        }
+    }
+}
+
+/// Width of a length or a tag, which is an unsigned integer type for the usual widths.
+struct Width(u8);
+
+impl fmt::Display for Width {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            1 => f.write_str("u8"),
+            2 => f.write_str("u16"),
+            4 => f.write_str("u32"),
+            8 => f.write_str("u64"),
+            width => write!(f, "{}", width),
+        }
+    }
+}
+
+impl fmt::Display for BorshSchemaContainer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "root {};", Name(self.declaration()))?;
+        for (declaration, definition) in self.definitions() {
+            writeln!(f)?;
+            let declaration = Name(declaration);
+            match definition {
+                Definition::Primitive(size) => {
+                    writeln!(f, "type {} = primitive({});", declaration, size)?
+                }
+                Definition::Sequence {
+                    length_width,
+                    length_range,
+                    elements,
+                } => {
+                    write!(f, "type {} = seq<{}", declaration, Width(*length_width))?;
+                    if *length_range != Definition::DEFAULT_LENGTH_RANGE {
+                        write!(f, ", {}..={}", length_range.start(), length_range.end())?;
+                    }
+                    writeln!(f, "> {};", Name(elements))?;
+                }
+                Definition::Tuple { elements } => {
+                    write!(f, "type {} = (", declaration)?;
+                    write_list(f, elements)?;
+                    writeln!(f, ");")?;
+                }
+                Definition::Enum {
+                    tag_width,
+                    variants,
+                } => {
+                    writeln!(f, "enum {}: {} {{", declaration, Width(*tag_width))?;
+                    for (discriminant, name, variant) in variants {
+                        writeln!(
+                            f,
+                            "    {}({}) = {},",
+                            Name(name),
+                            Name(variant),
+                            discriminant
+                        )?;
+                    }
+                    writeln!(f, "}}")?;
+                }
+                Definition::Struct { fields } => match fields {
+                    Fields::NamedFields(fields) => {
+                        writeln!(f, "struct {} {{", declaration)?;
+                        for (name, field) in fields {
+                            writeln!(f, "    {}: {},", Name(name), Name(field))?;
+                        }
+                        writeln!(f, "}}")?;
+                    }
+                    Fields::UnnamedFields(fields) => {
+                        write!(f, "struct {}(", declaration)?;
+                        write_list(f, fields)?;
+                        writeln!(f, ");")?;
+                    }
+                    Fields::Empty => writeln!(f, "struct {};", declaration)?,
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_list(f: &mut fmt::Formatter<'_>, declarations: &[Declaration]) -> fmt::Result {
+    for (index, declaration) in declarations.iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", Name(declaration))?;
+    }
+    Ok(())
+}
+
+impl FromStr for BorshSchemaContainer {
+    type Err = SchemaParseError;
+
+    /// Parses the container from its textual representation, as printed by `Display`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use borsh::schema::BorshSchemaContainer;
+    ///
+    /// let schema = BorshSchemaContainer::for_type::<Option<(u8, String)>>();
+    /// assert_eq!(schema.to_string().parse::<BorshSchemaContainer>(), Ok(schema));
+    ///
+    /// let err = "root A; struct A { a: u8 }; struct A;"
+    ///     .parse::<BorshSchemaContainer>()
+    ///     .unwrap_err();
+    /// assert_eq!(err.to_string(), "expected `struct`, `enum`, `type` or `root`, found `;` at 1:27");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = lex(s)?;
+        Parser { tokens, index: 0 }.parse()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Int(i128),
+    Punct(&'static str),
+    Eof,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(ident) => write!(f, "`{}`", ident),
+            Token::Str(string) => write!(f, "string {:?}", string),
+            Token::Int(int) => write!(f, "`{}`", int),
+            Token::Punct(punct) => write!(f, "`{}`", punct),
+            Token::Eof => f.write_str("end of input"),
+        }
+    }
+}
+
+/// Line and column of a token, starting from 1.
+type Position = (usize, usize);
+
+fn error<T>(position: Position, message: String) -> Result<T, SchemaParseError> {
+    Err(SchemaParseError {
+        line: position.0,
+        column: position.1,
+        message,
+    })
+}
+
+const PUNCTS: &[&str] = &[
+    "::", "..=", "{", "}", "(", ")", "<", ">", ",", ";", ":", "=",
+];
+
+fn lex(s: &str) -> Result<Vec<(Token, Position)>, SchemaParseError> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut line_start = 0;
+    let mut rest = s;
+    loop {
+        let offset = s.len() - rest.len();
+        let position = (line, s[line_start..offset].chars().count() + 1);
+        let c = match rest.chars().next() {
+            Some(c) => c,
+            None => {
+                tokens.push((Token::Eof, position));
+                return Ok(tokens);
+            }
+        };
+
+        if c == '\n' {
+            line += 1;
+            line_start = offset + 1;
+            rest = &rest[1..];
+        } else if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if rest.starts_with("//") {
+            rest = &rest[rest.find('\n').unwrap_or(rest.len())..];
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            tokens.push((Token::Ident(rest[..len].to_string()), position));
+            rest = &rest[len..];
+        } else if c.is_ascii_digit()
+            || (c == '-' && rest[1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            let len = 1 + rest[1..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len() - 1);
+            let int = match rest[..len].parse() {
+                Ok(int) => int,
+                Err(_) => return error(position, format!("`{}` is too large", &rest[..len])),
+            };
+            tokens.push((Token::Int(int), position));
+            rest = &rest[len..];
+        } else if c == '"' {
+            let (string, len) = lex_string(rest, position)?;
+            tokens.push((Token::Str(string), position));
+            rest = &rest[len..];
+        } else if let Some(punct) = PUNCTS.iter().find(|punct| rest.starts_with(**punct)) {
+            tokens.push((Token::Punct(punct), position));
+            rest = &rest[punct.len()..];
+        } else {
+            return error(position, format!("unexpected character {:?}", c));
+        }
+    }
+}
+
+/// Reads a quoted string with escapes, which `Debug` of strings produces.
+/// Returns the string and the length of its quoted representation.
+fn lex_string(s: &str, position: Position) -> Result<(String, usize), SchemaParseError> {
+    let mut string = String::new();
+    let mut chars = s.char_indices().skip(1);
+    while let Some((index, c)) = chars.next() {
+        let c = match c {
+            '"' => return Ok((string, index + 1)),
+            '\n' => break,
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some('0') => '\0',
+                Some(c @ ('\\' | '"' | '\'')) => c,
+                Some('u') => {
+                    let escape = &s[index + 2..];
+                    let code = escape
+                        .strip_prefix('{')
+                        .and_then(|escape| escape.split('}').next())
+                        .and_then(|code| u32::from_str_radix(code, 16).ok())
+                        .and_then(char::from_u32);
+                    match code {
+                        Some(c) => {
+                            let len = escape.find('}').unwrap_or(0);
+                            chars.nth(len);
+                            c
+                        }
+                        None => return error(position, "invalid unicode escape".to_string()),
+                    }
+                }
+                _ => return error(position, "invalid escape in string".to_string()),
+            },
+            c => c,
+        };
+        string.push(c);
+    }
+    error(position, "unterminated string".to_string())
+}
+
+struct Parser {
+    tokens: Vec<(Token, Position)>,
+    index: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &(Token, Position) {
+        &self.tokens[self.index]
+    }
+
+    fn next(&mut self) -> (Token, Position) {
+        let token = self.tokens[self.index].clone();
+        if token.0 != Token::Eof {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> Result<T, SchemaParseError> {
+        let (token, position) = self.peek();
+        error(*position, format!("expected {}, found {}", expected, token))
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        if matches!(&self.peek().0, Token::Punct(p) if *p == punct) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<(), SchemaParseError> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            self.unexpected(&format!("`{}`", punct))
+        }
+    }
+
+    /// Declaration or name of a field or a variant, which is a string or a path,
+    /// e.g. `a::Config`.
+    fn name(&mut self) -> Result<String, SchemaParseError> {
+        match self.peek().0.clone() {
+            Token::Str(string) => {
+                self.index += 1;
+                Ok(string)
+            }
+            Token::Ident(mut path) => {
+                self.index += 1;
+                while self.eat("::") {
+                    match self.peek().0.clone() {
+                        Token::Ident(ident) => {
+                            self.index += 1;
+                            path.push_str("::");
+                            path.push_str(&ident);
+                        }
+                        _ => return self.unexpected("an identifier"),
+                    }
+                }
+                Ok(path)
+            }
+            _ => self.unexpected("a name"),
+        }
+    }
+
+    fn int<T: TryFrom<i128>>(&mut self) -> Result<T, SchemaParseError> {
+        let (token, position) = self.peek().clone();
+        match token {
+            Token::Int(int) => match T::try_from(int) {
+                Ok(int) => {
+                    self.index += 1;
+                    Ok(int)
+                }
+                Err(_) => error(position, format!("`{}` is out of range", int)),
+            },
+            _ => self.unexpected("an integer"),
+        }
+    }
+
+    fn width(&mut self) -> Result<u8, SchemaParseError> {
+        let width = match &self.peek().0 {
+            Token::Ident(ident) if ident == "u8" => 1,
+            Token::Ident(ident) if ident == "u16" => 2,
+            Token::Ident(ident) if ident == "u32" => 4,
+            Token::Ident(ident) if ident == "u64" => 8,
+            Token::Int(_) => return self.int(),
+            _ => return self.unexpected("a width"),
+        };
+        self.index += 1;
+        Ok(width)
+    }
+
+    /// Comma-separated items with optional trailing comma, till `close` punctuation.
+    fn list<T>(
+        &mut self,
+        close: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, SchemaParseError>,
+    ) -> Result<Vec<T>, SchemaParseError> {
+        let mut items = Vec::new();
+        while !self.eat(close) {
+            items.push(item(self)?);
+            if !self.eat(",") {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn parse(mut self) -> Result<BorshSchemaContainer, SchemaParseError> {
+        let mut root = None;
+        let mut definitions = BTreeMap::new();
+        loop {
+            let (token, position) = self.peek().clone();
+            let keyword = match token {
+                Token::Eof => break,
+                Token::Ident(keyword) => keyword,
+                _ => return self.unexpected("`struct`, `enum`, `type` or `root`"),
+            };
+            self.index += 1;
+            let (declaration, definition) = match keyword.as_str() {
+                "root" => {
+                    if root.is_some() {
+                        return error(position, "duplicate `root`".to_string());
+                    }
+                    root = Some(self.name()?);
+                    self.expect(";")?;
+                    continue;
+                }
+                "struct" => {
+                    let declaration = self.name()?;
+                    let fields = if self.eat(";") {
+                        Fields::Empty
+                    } else if self.eat("(") {
+                        let fields = self.list(")", Self::name)?;
+                        self.expect(";")?;
+                        Fields::UnnamedFields(fields)
+                    } else if self.eat("{") {
+                        Fields::NamedFields(self.list("}", |parser| {
+                            let name = parser.name()?;
+                            parser.expect(":")?;
+                            Ok((name, parser.name()?))
+                        })?)
+                    } else {
+                        return self.unexpected("`;`, `(` or `{`");
+                    };
+                    (declaration, Definition::Struct { fields })
+                }
+                "enum" => {
+                    let declaration = self.name()?;
+                    self.expect(":")?;
+                    let tag_width = self.width()?;
+                    self.expect("{")?;
+                    let variants = self.list("}", |parser| {
+                        let name = parser.name()?;
+                        parser.expect("(")?;
+                        let variant = parser.name()?;
+                        parser.expect(")")?;
+                        parser.expect("=")?;
+                        Ok((parser.int()?, name, variant))
+                    })?;
+                    (
+                        declaration,
+                        Definition::Enum {
+                            tag_width,
+                            variants,
+                        },
+                    )
+                }
+                "type" => {
+                    let declaration = self.name()?;
+                    self.expect("=")?;
+                    let definition = self.definition()?;
+                    self.expect(";")?;
+                    (declaration, definition)
+                }
+                _ => {
+                    self.index -= 1;
+                    return self.unexpected("`struct`, `enum`, `type` or `root`");
+                }
+            };
+            if definitions.contains_key(&declaration) {
+                return error(
+                    position,
+                    format!("duplicate definition of `{}`", declaration),
+                );
+            }
+            definitions.insert(declaration, definition);
+        }
+        match root {
+            Some(root) => Ok(BorshSchemaContainer::new(root, definitions)),
+            None => error(self.peek().1, "missing `root`".to_string()),
+        }
+    }
+
+    /// Definition after `=` of `type` item.
+    fn definition(&mut self) -> Result<Definition, SchemaParseError> {
+        if self.eat("(") {
+            let elements = self.list(")", Self::name)?;
+            return Ok(Definition::Tuple { elements });
+        }
+        match &self.peek().0 {
+            Token::Ident(keyword) if keyword == "primitive" => {
+                self.index += 1;
+                self.expect("(")?;
+                let size = self.int()?;
+                self.expect(")")?;
+                Ok(Definition::Primitive(size))
+            }
+            Token::Ident(keyword) if keyword == "seq" => {
+                self.index += 1;
+                self.expect("<")?;
+                let length_width = self.width()?;
+                let length_range = if self.eat(",") {
+                    let start = self.int()?;
+                    self.expect("..=")?;
+                    start..=self.int()?
+                } else {
+                    Definition::DEFAULT_LENGTH_RANGE
+                };
+                self.expect(">")?;
+                Ok(Definition::Sequence {
+                    length_width,
+                    length_range,
+                    elements: self.name()?,
+                })
+            }
+            _ => self.unexpected("`primitive`, `seq` or `(`"),
+        }
+    }
+}
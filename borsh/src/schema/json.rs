@@ -0,0 +1,644 @@
+use core::convert::TryFrom;
+use core::fmt;
+
+use serde_json::{Map, Number, Value as Json};
+
+use super::value::{invalid_data, is_string, MAX_DEPTH};
+use super::{BorshSchemaContainer, Definition, Fields, Value};
+use crate::__private::maybestd::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use crate::io::{Error, Result};
+use crate::varint::{VarI128, VarI16, VarI32, VarI64, VarU128, VarU16, VarU32, VarU64};
+use crate::BorshSerialize;
+
+impl BorshSchemaContainer {
+    /// Serializes a JSON value into bytes of the container's type, guided only by the schema.
+    ///
+    /// Structs with named fields are JSON objects with the same keys, other structs, tuples
+    /// and sequences are JSON arrays, strings are JSON strings and enums are JSON objects
+    /// with a single key, e.g. `{"Some": 5}`. Variants without a value may also be given by
+    /// their name alone, e.g. `"None"`. Integers are JSON numbers or decimal strings, the latter
+    /// for the values not representable by JSON numbers, e.g. large `u128`.
+    ///
+    /// Errors are qualified with the path of the offending value, e.g. `$.shapes[1].radius`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use borsh::schema::BorshSchemaContainer;
+    ///
+    /// let schema = BorshSchemaContainer::for_type::<(u16, Option<String>)>();
+    /// let json = serde_json::json!([7, {"Some": "borsh"}]);
+    /// assert_eq!(
+    ///     schema.serialize_json(&json).unwrap(),
+    ///     borsh::to_vec(&(7u16, Some("borsh".to_string()))).unwrap()
+    /// );
+    ///
+    /// let err = schema.serialize_json(&serde_json::json!([70000, "None"])).unwrap_err();
+    /// assert_eq!(err.to_string(), "Invalid value at `$[0]`: 70000 is out of range of `u16`");
+    /// ```
+    pub fn serialize_json(&self, value: &Json) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        serialize_json_impl(self.declaration(), self, value, &Path::Root, &mut out, 0)?;
+        Ok(out)
+    }
+
+    /// Deserializes a value of the container's type from `bytes` into JSON, guided only by the
+    /// schema.
+    ///
+    /// This is the inverse of [`BorshSchemaContainer::serialize_json`], built on top of
+    /// [`BorshSchemaContainer::deserialize_value`]. Unit values are `null`, enums are
+    /// `{"Variant": ...}`, sequences of bytes, which are not strings, are arrays of numbers,
+    /// and integers not representable by JSON numbers are decimal strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use borsh::schema::BorshSchemaContainer;
+    ///
+    /// let schema = BorshSchemaContainer::for_type::<(u128, Option<String>)>();
+    /// let bytes = borsh::to_vec(&(u128::MAX, None::<String>)).unwrap();
+    /// assert_eq!(
+    ///     schema.deserialize_json(&bytes).unwrap(),
+    ///     serde_json::json!([u128::MAX.to_string(), {"None": null}])
+    /// );
+    /// ```
+    pub fn deserialize_json(&self, bytes: &[u8]) -> Result<Json> {
+        let value = self.deserialize_value(bytes)?;
+        to_json(value, &Path::Root)
+    }
+}
+
+/// Location of a value inside of a JSON document, formatted lazily, when an error occurs.
+enum Path<'a> {
+    Root,
+    Field(&'a Path<'a>, &'a str),
+    Index(&'a Path<'a>, usize),
+}
+
+impl fmt::Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Path::Root => write!(f, "$"),
+            Path::Field(parent, name) => write!(f, "{}.{}", parent, name),
+            Path::Index(parent, index) => write!(f, "{}[{}]", parent, index),
+        }
+    }
+}
+
+fn error(path: &Path<'_>, msg: impl fmt::Display) -> Error {
+    invalid_data(format!("Invalid value at `{}`: {}", path, msg))
+}
+
+fn mismatch(path: &Path<'_>, expected: &str, declaration: &str, found: &Json) -> Error {
+    let found = match found {
+        Json::Null => "null",
+        Json::Bool(_) => "boolean",
+        Json::Number(_) => "number",
+        Json::String(_) => "string",
+        Json::Array(_) => "array",
+        Json::Object(_) => "object",
+    };
+    error(
+        path,
+        format!(
+            "expected {} for `{}`, found {}",
+            expected, declaration, found
+        ),
+    )
+}
+
+fn out_of_range(path: &Path<'_>, declaration: &str, value: &Json) -> Error {
+    error(
+        path,
+        format!("{} is out of range of `{}`", value, declaration),
+    )
+}
+
+/// Implementation of [`BorshSchemaContainer::serialize_json`].
+fn serialize_json_impl(
+    declaration: &str,
+    schema: &BorshSchemaContainer,
+    value: &Json,
+    path: &Path<'_>,
+    out: &mut Vec<u8>,
+    depth: usize,
+) -> Result<()> {
+    if depth > MAX_DEPTH {
+        return Err(error(
+            path,
+            format!("value of `{}` is nested too deeply", declaration),
+        ));
+    }
+    let depth = depth + 1;
+
+    let definition = schema
+        .get_definition(declaration)
+        .ok_or_else(|| error(path, format!("missing definition of `{}`", declaration)))?;
+    match definition {
+        Definition::Primitive(size) => primitive(declaration, *size, value, path, out),
+        Definition::Sequence {
+            length_width,
+            length_range,
+            elements,
+        } => {
+            if *length_width == Definition::ARRAY_LENGTH_WIDTH
+                && length_range.start() != length_range.end()
+            {
+                if varint(declaration, value, path, out)? {
+                    return Ok(());
+                }
+                return Err(error(
+                    path,
+                    format!(
+                        "length of `{}` is not determined by the schema",
+                        declaration
+                    ),
+                ));
+            }
+            let sequence = Sequence {
+                declaration,
+                length: Length::Width(*length_width),
+                length_range,
+                elements,
+            };
+            sequence.serialize(schema, value, path, out, depth)
+        }
+        Definition::Tuple { elements } => {
+            if let Some(sequence) = Sequence::varint_prefixed(declaration, elements, schema) {
+                return sequence.serialize(schema, value, path, out, depth);
+            }
+            serialize_array(declaration, elements, schema, value, path, out, depth)
+        }
+        Definition::Enum {
+            tag_width,
+            variants,
+        } => {
+            if *tag_width == 0 {
+                return Err(error(
+                    path,
+                    format!(
+                        "variant of untagged `{}` is not determined by the schema",
+                        declaration
+                    ),
+                ));
+            }
+            let (name, body) = match value {
+                Json::String(name) => (name, &Json::Null),
+                Json::Object(map) if map.len() == 1 => map.iter().next().unwrap(),
+                _ => {
+                    return Err(mismatch(
+                        path,
+                        "an object with a single variant",
+                        declaration,
+                        value,
+                    ))
+                }
+            };
+            let (discriminant, _, variant) = variants
+                .iter()
+                .find(|(_, variant_name, _)| variant_name == name)
+                .ok_or_else(|| {
+                    error(
+                        path,
+                        format!("unknown variant `{}` of `{}`", name, declaration),
+                    )
+                })?;
+            write_width(*tag_width, *discriminant as u64, path, out)?;
+            let path = Path::Field(path, name);
+            serialize_json_impl(variant, schema, body, &path, out, depth)
+        }
+        Definition::Struct { fields } => match fields {
+            Fields::NamedFields(fields) => {
+                let map = match value {
+                    Json::Object(map) => map,
+                    _ => return Err(mismatch(path, "an object", declaration, value)),
+                };
+                if let Some(key) = map
+                    .keys()
+                    .find(|key| !fields.iter().any(|(name, _)| name == *key))
+                {
+                    return Err(error(
+                        path,
+                        format!("unknown field `{}` of `{}`", key, declaration),
+                    ));
+                }
+                for (name, field) in fields {
+                    let value = map.get(name).ok_or_else(|| {
+                        error(
+                            path,
+                            format!("missing field `{}` of `{}`", name, declaration),
+                        )
+                    })?;
+                    let path = Path::Field(path, name);
+                    serialize_json_impl(field, schema, value, &path, out, depth)?;
+                }
+                Ok(())
+            }
+            Fields::UnnamedFields(fields) => {
+                serialize_array(declaration, fields, schema, value, path, out, depth)
+            }
+            Fields::Empty => match value {
+                Json::Null => Ok(()),
+                _ => Err(mismatch(path, "null", declaration, value)),
+            },
+        },
+    }
+}
+
+/// Serializes a JSON array of a fixed number of elements of possibly different types.
+fn serialize_array(
+    declaration: &str,
+    elements: &[String],
+    schema: &BorshSchemaContainer,
+    value: &Json,
+    path: &Path<'_>,
+    out: &mut Vec<u8>,
+    depth: usize,
+) -> Result<()> {
+    let values = match value {
+        Json::Array(values) => values,
+        _ => return Err(mismatch(path, "an array", declaration, value)),
+    };
+    if values.len() != elements.len() {
+        return Err(error(
+            path,
+            format!(
+                "expected {} elements for `{}`, found {}",
+                elements.len(),
+                declaration,
+                values.len()
+            ),
+        ));
+    }
+    for (index, (element, value)) in elements.iter().zip(values).enumerate() {
+        let path = Path::Index(path, index);
+        serialize_json_impl(element, schema, value, &path, out, depth)?;
+    }
+    Ok(())
+}
+
+/// Writes `width`-byte wide little-endian unsigned integer, i.e. a length or a tag.
+fn write_width(width: u8, value: u64, path: &Path<'_>, out: &mut Vec<u8>) -> Result<()> {
+    let width = usize::from(width);
+    if width > 8 {
+        return Err(error(
+            path,
+            format!("width {} of a length or a tag is greater than 8", width),
+        ));
+    }
+    out.extend_from_slice(&value.to_le_bytes()[..width]);
+    Ok(())
+}
+
+enum Length {
+    /// Length prefix of a fixed width, zero for arrays.
+    Width(u8),
+    /// `VarU32` length prefix.
+    VarU32,
+}
+
+struct Sequence<'a> {
+    declaration: &'a str,
+    length: Length,
+    length_range: &'a core::ops::RangeInclusive<u64>,
+    elements: &'a str,
+}
+
+impl<'a> Sequence<'a> {
+    /// Collections with varint length prefix are described as a tuple of `VarU32` length,
+    /// followed by the untagged sequence of elements.
+    fn varint_prefixed(
+        declaration: &'a str,
+        elements: &'a [String],
+        schema: &'a BorshSchemaContainer,
+    ) -> Option<Self> {
+        let untagged = match elements {
+            [length, untagged] if length == "VarU32" => untagged,
+            _ => return None,
+        };
+        match schema.get_definition(untagged) {
+            Some(Definition::Sequence {
+                length_width: Definition::ARRAY_LENGTH_WIDTH,
+                length_range,
+                elements,
+            }) if length_range.start() != length_range.end() => Some(Sequence {
+                declaration,
+                length: Length::VarU32,
+                length_range,
+                elements,
+            }),
+            _ => None,
+        }
+    }
+
+    fn serialize(
+        &self,
+        schema: &BorshSchemaContainer,
+        value: &Json,
+        path: &Path<'_>,
+        out: &mut Vec<u8>,
+        depth: usize,
+    ) -> Result<()> {
+        let bytes = self.elements == "u8" || self.elements == "AsciiChar";
+        if bytes && is_string(self.declaration) {
+            let string = match value {
+                Json::String(string) => string,
+                _ => return Err(mismatch(path, "a string", self.declaration, value)),
+            };
+            if self.elements == "AsciiChar" && !string.is_ascii() {
+                return Err(error(
+                    path,
+                    format!("`{}` expects an ASCII string", self.declaration),
+                ));
+            }
+            self.write_length(string.len(), path, out)?;
+            out.extend_from_slice(string.as_bytes());
+            return Ok(());
+        }
+
+        let values = match value {
+            Json::Array(values) => values,
+            _ => return Err(mismatch(path, "an array", self.declaration, value)),
+        };
+        self.write_length(values.len(), path, out)?;
+        for (index, value) in values.iter().enumerate() {
+            let path = Path::Index(path, index);
+            if bytes {
+                let byte = unsigned(self.elements, 8, value, &path)? as u8;
+                if self.elements == "AsciiChar" && !byte.is_ascii() {
+                    return Err(out_of_range(&path, self.elements, value));
+                }
+                out.push(byte);
+            } else {
+                serialize_json_impl(self.elements, schema, value, &path, out, depth)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_length(&self, len: usize, path: &Path<'_>, out: &mut Vec<u8>) -> Result<()> {
+        let len = len as u64;
+        if !self.length_range.contains(&len) {
+            return Err(error(
+                path,
+                format!(
+                    "length {} of `{}` is out of {}..={} range",
+                    len,
+                    self.declaration,
+                    self.length_range.start(),
+                    self.length_range.end()
+                ),
+            ));
+        }
+        match self.length {
+            Length::Width(width) => {
+                if width != Definition::ARRAY_LENGTH_WIDTH
+                    && width < 8
+                    && len >> (8 * u32::from(width)) != 0
+                {
+                    return Err(error(
+                        path,
+                        format!(
+                            "length {} of `{}` doesn't fit into {} bytes",
+                            len, self.declaration, width
+                        ),
+                    ));
+                }
+                write_width(width, len, path, out)
+            }
+            Length::VarU32 => {
+                let len = u32::try_from(len).map_err(|_| {
+                    error(
+                        path,
+                        format!(
+                            "length {} of `{}` doesn't fit into u32",
+                            len, self.declaration
+                        ),
+                    )
+                })?;
+                VarU32(len).serialize(out)
+            }
+        }
+    }
+}
+
+/// Reads an unsigned integer of `bits` width from a JSON number or a decimal string.
+fn unsigned(declaration: &str, bits: u32, value: &Json, path: &Path<'_>) -> Result<u128> {
+    let parsed = match value {
+        Json::Number(number) => match number.as_u64() {
+            Some(number) => Some(u128::from(number)),
+            None if number.is_i64() => None,
+            None => return Err(mismatch(path, "an integer", declaration, value)),
+        },
+        Json::String(string) => match string.parse::<u128>() {
+            Ok(number) => Some(number),
+            Err(_) if string.parse::<i128>().is_ok() => None,
+            Err(_) => return Err(mismatch(path, "an integer", declaration, value)),
+        },
+        _ => return Err(mismatch(path, "an integer", declaration, value)),
+    };
+    match parsed {
+        Some(number) if bits >= 128 || number >> bits == 0 => Ok(number),
+        _ => Err(out_of_range(path, declaration, value)),
+    }
+}
+
+/// Reads a signed integer of `bits` width from a JSON number or a decimal string.
+fn signed(declaration: &str, bits: u32, value: &Json, path: &Path<'_>) -> Result<i128> {
+    let parsed = match value {
+        Json::Number(number) => match (number.as_i64(), number.is_u64()) {
+            (Some(number), _) => Some(i128::from(number)),
+            (None, true) => None,
+            (None, false) => return Err(mismatch(path, "an integer", declaration, value)),
+        },
+        Json::String(string) => match string.parse::<i128>() {
+            Ok(number) => Some(number),
+            Err(_) if string.parse::<u128>().is_ok() => None,
+            Err(_) => return Err(mismatch(path, "an integer", declaration, value)),
+        },
+        _ => return Err(mismatch(path, "an integer", declaration, value)),
+    };
+    match parsed {
+        Some(number)
+            if bits >= 128 || (number >> (bits - 1) == 0 || number >> (bits - 1) == -1) =>
+        {
+            Ok(number)
+        }
+        _ => Err(out_of_range(path, declaration, value)),
+    }
+}
+
+fn primitive(
+    declaration: &str,
+    size: u8,
+    value: &Json,
+    path: &Path<'_>,
+    out: &mut Vec<u8>,
+) -> Result<()> {
+    enum Format {
+        Float,
+        UInt,
+        Int,
+    }
+
+    let (inner, big_endian) = match declaration
+        .strip_prefix("BigEndian<")
+        .and_then(|inner| inner.strip_suffix('>'))
+    {
+        Some(inner) => (inner, true),
+        None => (declaration, false),
+    };
+    let format = match inner {
+        "bool" => {
+            return match value {
+                Json::Bool(value) => {
+                    out.push(u8::from(*value));
+                    Ok(())
+                }
+                _ => Err(mismatch(path, "a boolean", declaration, value)),
+            };
+        }
+        "f32" | "f64" => Format::Float,
+        "u8" | "u16" | "u32" | "u64" | "u128" => Format::UInt,
+        "i8" | "i16" | "i32" | "i64" | "i128" => Format::Int,
+        "NonZeroU8" | "NonZeroU16" | "NonZeroU32" | "NonZeroU64" | "NonZeroU128"
+        | "NonZeroUsize" => Format::UInt,
+        "NonZeroI8" | "NonZeroI16" | "NonZeroI32" | "NonZeroI64" | "NonZeroI128" => Format::Int,
+        _ if size == 0 => {
+            return match value {
+                Json::Null => Ok(()),
+                _ => Err(mismatch(path, "null", declaration, value)),
+            };
+        }
+        _ => {
+            // primitives of unknown types are arrays of their bytes
+            let values = match value {
+                Json::Array(values) if values.len() == usize::from(size) => values,
+                _ => {
+                    return Err(mismatch(
+                        path,
+                        &format!("an array of {} bytes", size),
+                        declaration,
+                        value,
+                    ))
+                }
+            };
+            for (index, value) in values.iter().enumerate() {
+                let path = Path::Index(path, index);
+                out.push(unsigned("u8", 8, value, &path)? as u8);
+            }
+            return Ok(());
+        }
+    };
+
+    let bits = 8 * u32::from(size);
+    let mut bytes = match format {
+        Format::Float => {
+            let number = match value {
+                Json::Number(number) => match number.as_f64() {
+                    Some(number) => number,
+                    None => return Err(out_of_range(path, declaration, value)),
+                },
+                _ => return Err(mismatch(path, "a number", declaration, value)),
+            };
+            if size == 4 {
+                let number = number as f32;
+                if number.is_infinite() {
+                    return Err(out_of_range(path, declaration, value));
+                }
+                number.to_le_bytes().to_vec()
+            } else {
+                number.to_le_bytes().to_vec()
+            }
+        }
+        Format::UInt => {
+            unsigned(declaration, bits, value, path)?.to_le_bytes()[..usize::from(size)].to_vec()
+        }
+        Format::Int => {
+            signed(declaration, bits, value, path)?.to_le_bytes()[..usize::from(size)].to_vec()
+        }
+    };
+    if inner.starts_with("NonZero") && bytes.iter().all(|byte| *byte == 0) {
+        return Err(out_of_range(path, declaration, value));
+    }
+    if big_endian {
+        bytes.reverse();
+    }
+    out.extend_from_slice(&bytes);
+    Ok(())
+}
+
+/// Varints are sequences of bytes of variable length, which is determined by the bytes themselves.
+fn varint(declaration: &str, value: &Json, path: &Path<'_>, out: &mut Vec<u8>) -> Result<bool> {
+    macro_rules! write {
+        ($varint: ident, $ty: ty, $read: ident) => {
+            $varint($read(declaration, <$ty>::BITS, value, path)? as $ty).serialize(out)?
+        };
+    }
+
+    match declaration {
+        "VarU16" => write!(VarU16, u16, unsigned),
+        "VarU32" => write!(VarU32, u32, unsigned),
+        "VarU64" => write!(VarU64, u64, unsigned),
+        "VarU128" => write!(VarU128, u128, unsigned),
+        "VarI16" => write!(VarI16, i16, signed),
+        "VarI32" => write!(VarI32, i32, signed),
+        "VarI64" => write!(VarI64, i64, signed),
+        "VarI128" => write!(VarI128, i128, signed),
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Implementation of [`BorshSchemaContainer::deserialize_json`].
+fn to_json(value: Value, path: &Path<'_>) -> Result<Json> {
+    let json = match value {
+        Value::Unit => Json::Null,
+        Value::Bool(value) => Json::Bool(value),
+        Value::UInt(value) => match u64::try_from(value) {
+            Ok(value) => Json::from(value),
+            Err(_) => Json::String(value.to_string()),
+        },
+        Value::Int(value) => match i64::try_from(value) {
+            Ok(value) => Json::from(value),
+            Err(_) => Json::String(value.to_string()),
+        },
+        Value::Float(value) => match Number::from_f64(value) {
+            Some(number) => Json::Number(number),
+            None => {
+                return Err(error(
+                    path,
+                    format!("{} is not representable in JSON", value),
+                ))
+            }
+        },
+        Value::String(value) => Json::String(value),
+        Value::Bytes(bytes) => Json::Array(bytes.into_iter().map(Json::from).collect()),
+        Value::Seq(values) | Value::Tuple(values) => {
+            let mut array = Vec::with_capacity(values.len());
+            for (index, value) in values.into_iter().enumerate() {
+                array.push(to_json(value, &Path::Index(path, index))?);
+            }
+            Json::Array(array)
+        }
+        Value::Struct(fields) => {
+            let mut map = Map::new();
+            for (name, value) in fields {
+                let value = to_json(value, &Path::Field(path, &name))?;
+                map.insert(name, value);
+            }
+            Json::Object(map)
+        }
+        Value::Enum(name, value) => {
+            let value = to_json(*value, &Path::Field(path, &name))?;
+            let mut map = Map::new();
+            map.insert(name, value);
+            Json::Object(map)
+        }
+    };
+    Ok(json)
+}
@@ -0,0 +1,369 @@
+use core::fmt::Write;
+
+use serde_json::{json, Map, Value as Json};
+
+use super::value::{invalid_data, is_string};
+use super::{BorshSchemaContainer, Definition, Fields};
+use crate::__private::maybestd::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use crate::io::Result;
+
+/// Identifier of JSON Schema dialect of [`BorshSchemaContainer::to_json_schema`].
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+impl BorshSchemaContainer {
+    /// Exports the container as [JSON Schema](https://json-schema.org/draft/2020-12) of JSON
+    /// values, which [`BorshSchemaContainer::serialize_json`] accepts and
+    /// [`BorshSchemaContainer::deserialize_json`] produces.
+    ///
+    /// Primitives are inlined, while all other definitions are placed under `$defs` with their
+    /// declarations as keys, so that recursive types are expressed with `$ref`. Integers are
+    /// bounded by the widths of primitives, `u128` and `i128` out of range of `u64` and `i64` are
+    /// decimal strings. Lengths of sequences are bounded by `minItems`/`maxItems`, enums are
+    /// `oneOf` objects with a single variant, and variants without a value may also be given
+    /// by their name alone.
+    ///
+    /// Returns an error for the types, which `serialize_json` doesn't support either, e.g.
+    /// sequences without a length prefix of variable length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use borsh::schema::BorshSchemaContainer;
+    ///
+    /// #[derive(borsh::BorshSchema)]
+    /// struct Point {
+    ///     x: u8,
+    ///     y: Option<i16>,
+    /// }
+    ///
+    /// let schema = BorshSchemaContainer::for_type::<Point>().to_json_schema().unwrap();
+    /// assert_eq!(schema["$ref"], "#/$defs/Point");
+    /// assert_eq!(
+    ///     schema["$defs"]["Point"]["properties"]["x"],
+    ///     serde_json::json!({"type": "integer", "minimum": 0, "maximum": 255})
+    /// );
+    /// assert_eq!(
+    ///     schema["$defs"]["Point"]["properties"]["y"],
+    ///     serde_json::json!({"$ref": "#/$defs/Option%3Ci16%3E"})
+    /// );
+    /// ```
+    pub fn to_json_schema(&self) -> Result<Json> {
+        let mut defs = Map::new();
+        let root = json_schema_impl(self.declaration(), self, &mut defs)?;
+
+        let mut schema = Map::new();
+        schema.insert("$schema".to_string(), Json::from(DRAFT));
+        if let Json::Object(root) = root {
+            schema.extend(root);
+        }
+        if !defs.is_empty() {
+            schema.insert("$defs".to_string(), Json::Object(defs));
+        }
+        Ok(Json::Object(schema))
+    }
+}
+
+/// Schema of a primitive inline, or a reference to the schema of any other definition,
+/// which is added to `defs`.
+fn json_schema_impl(
+    declaration: &str,
+    schema: &BorshSchemaContainer,
+    defs: &mut Map<String, Json>,
+) -> Result<Json> {
+    let definition = schema
+        .get_definition(declaration)
+        .ok_or_else(|| invalid_data(format!("missing definition of `{}`", declaration)))?;
+    if let Definition::Primitive(size) = definition {
+        return Ok(primitive(declaration, *size));
+    }
+    if !defs.contains_key(declaration) {
+        // placeholder, which stops recursion of recursive types
+        defs.insert(declaration.to_string(), Json::Null);
+        let definition = definition_schema(declaration, definition, schema, defs)?;
+        defs.insert(declaration.to_string(), definition);
+    }
+    Ok(reference(declaration))
+}
+
+/// `$ref` to the definition under `$defs`, which is escaped as JSON Pointer and URI fragment.
+fn reference(declaration: &str) -> Json {
+    let mut reference = String::from("#/$defs/");
+    for byte in declaration.bytes() {
+        match byte {
+            b'~' => reference.push_str("~0"),
+            b'/' => reference.push_str("~1"),
+            b'-' | b'.' | b'_' => reference.push(char::from(byte)),
+            _ if byte.is_ascii_alphanumeric() => reference.push(char::from(byte)),
+            _ => write!(reference, "%{:02X}", byte).unwrap(),
+        }
+    }
+    json!({ "$ref": reference })
+}
+
+fn definition_schema(
+    declaration: &str,
+    definition: &Definition,
+    schema: &BorshSchemaContainer,
+    defs: &mut Map<String, Json>,
+) -> Result<Json> {
+    let json = match definition {
+        Definition::Primitive(size) => primitive(declaration, *size),
+        Definition::Sequence {
+            length_width,
+            length_range,
+            elements,
+        } => {
+            if *length_width == Definition::ARRAY_LENGTH_WIDTH
+                && length_range.start() != length_range.end()
+            {
+                return varint(declaration).ok_or_else(|| {
+                    invalid_data(format!(
+                        "length of `{}` is not determined by the schema",
+                        declaration
+                    ))
+                });
+            }
+            let mut max_len = *length_range.end();
+            if *length_width != Definition::ARRAY_LENGTH_WIDTH && *length_width < 8 {
+                max_len = max_len.min((1 << (8 * u32::from(*length_width))) - 1);
+            }
+            let sequence = Sequence {
+                declaration,
+                min_len: *length_range.start(),
+                max_len,
+                elements,
+            };
+            sequence.json_schema(schema, defs)?
+        }
+        Definition::Tuple { elements } => match varint_prefixed(elements, schema) {
+            Some((min_len, max_len, untagged_elements)) => {
+                let sequence = Sequence {
+                    declaration,
+                    min_len,
+                    max_len: max_len.min(u64::from(u32::MAX)),
+                    elements: untagged_elements,
+                };
+                sequence.json_schema(schema, defs)?
+            }
+            None => array(elements, schema, defs)?,
+        },
+        Definition::Enum {
+            tag_width,
+            variants,
+        } => {
+            if *tag_width == 0 {
+                return Err(invalid_data(format!(
+                    "variant of untagged `{}` is not determined by the schema",
+                    declaration
+                )));
+            }
+            let mut one_of = Vec::new();
+            for (_, name, variant) in variants {
+                let value = json_schema_impl(variant, schema, defs)?;
+                one_of.push(json!({
+                    "type": "object",
+                    "properties": { name.as_str(): value },
+                    "required": [name],
+                    "additionalProperties": false,
+                }));
+                if is_null(variant, schema) {
+                    one_of.push(json!({ "const": name }));
+                }
+            }
+            json!({ "oneOf": one_of })
+        }
+        Definition::Struct { fields } => match fields {
+            Fields::NamedFields(fields) => {
+                let mut properties = Map::new();
+                for (name, field) in fields {
+                    properties.insert(name.clone(), json_schema_impl(field, schema, defs)?);
+                }
+                let required: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+                json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                    "additionalProperties": false,
+                })
+            }
+            Fields::UnnamedFields(fields) => array(fields, schema, defs)?,
+            Fields::Empty => json!({ "type": "null" }),
+        },
+    };
+    Ok(json)
+}
+
+/// Whether values of the declaration are JSON `null`, i.e. it's zero-sized.
+fn is_null(declaration: &str, schema: &BorshSchemaContainer) -> bool {
+    matches!(
+        schema.get_definition(declaration),
+        Some(Definition::Primitive(0))
+            | Some(Definition::Struct {
+                fields: Fields::Empty
+            })
+    )
+}
+
+/// Array of a fixed number of elements of possibly different types.
+fn array(
+    elements: &[String],
+    schema: &BorshSchemaContainer,
+    defs: &mut Map<String, Json>,
+) -> Result<Json> {
+    let mut prefix_items = Vec::with_capacity(elements.len());
+    for element in elements {
+        prefix_items.push(json_schema_impl(element, schema, defs)?);
+    }
+    Ok(json!({
+        "type": "array",
+        "prefixItems": prefix_items,
+        "items": false,
+        "minItems": elements.len(),
+    }))
+}
+
+/// Collections with varint length prefix are described as a tuple of `VarU32` length,
+/// followed by the untagged sequence of elements.
+fn varint_prefixed<'a>(
+    elements: &'a [String],
+    schema: &'a BorshSchemaContainer,
+) -> Option<(u64, u64, &'a str)> {
+    let untagged = match elements {
+        [length, untagged] if length == "VarU32" => untagged,
+        _ => return None,
+    };
+    match schema.get_definition(untagged) {
+        Some(Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range,
+            elements,
+        }) if length_range.start() != length_range.end() => {
+            Some((*length_range.start(), *length_range.end(), elements))
+        }
+        _ => None,
+    }
+}
+
+struct Sequence<'a> {
+    declaration: &'a str,
+    min_len: u64,
+    max_len: u64,
+    elements: &'a str,
+}
+
+impl Sequence<'_> {
+    fn json_schema(
+        &self,
+        schema: &BorshSchemaContainer,
+        defs: &mut Map<String, Json>,
+    ) -> Result<Json> {
+        let bytes = self.elements == "u8" || self.elements == "AsciiChar";
+        if bytes && is_string(self.declaration) {
+            let mut json = json!({ "type": "string" });
+            if self.elements == "AsciiChar" {
+                // length of ASCII strings in bytes is the same as in characters
+                json["pattern"] = Json::from("^[\\u0000-\\u007f]*$");
+                if self.min_len > 0 {
+                    json["minLength"] = Json::from(self.min_len);
+                }
+            }
+            // UTF-8 strings have no more characters than bytes
+            json["maxLength"] = Json::from(self.max_len);
+            return Ok(json);
+        }
+
+        let items = if self.elements == "AsciiChar" {
+            integer(7, false, false)
+        } else if bytes {
+            integer(8, false, false)
+        } else {
+            json_schema_impl(self.elements, schema, defs)?
+        };
+        let mut json = json!({ "type": "array", "items": items });
+        if self.min_len > 0 {
+            json["minItems"] = Json::from(self.min_len);
+        }
+        json["maxItems"] = Json::from(self.max_len);
+        Ok(json)
+    }
+}
+
+/// Integer of `bits` width, which is a JSON number if it fits into `u64`/`i64`,
+/// and a decimal string otherwise.
+fn integer(bits: u32, signed: bool, non_zero: bool) -> Json {
+    let mut json = match (bits, signed) {
+        (128, false) => json!({
+            "anyOf": [
+                { "type": "integer", "minimum": 0, "maximum": u64::MAX },
+                { "type": "string", "pattern": "^[0-9]+$" },
+            ]
+        }),
+        (128, true) => json!({
+            "anyOf": [
+                { "type": "integer", "minimum": i64::MIN, "maximum": i64::MAX },
+                { "type": "string", "pattern": "^-?[0-9]+$" },
+            ]
+        }),
+        (bits, false) => json!({
+            "type": "integer",
+            "minimum": 0,
+            "maximum": u64::MAX >> (64 - bits),
+        }),
+        (bits, true) => json!({
+            "type": "integer",
+            "minimum": i64::MIN >> (64 - bits),
+            "maximum": i64::MAX >> (64 - bits),
+        }),
+    };
+    if non_zero {
+        json["not"] = json!({ "const": 0 });
+    }
+    json
+}
+
+fn primitive(declaration: &str, size: u8) -> Json {
+    let inner = declaration
+        .strip_prefix("BigEndian<")
+        .and_then(|inner| inner.strip_suffix('>'))
+        .unwrap_or(declaration);
+    let bits = 8 * u32::from(size);
+    match inner {
+        "bool" => json!({ "type": "boolean" }),
+        "f32" | "f64" => json!({ "type": "number" }),
+        "u8" | "u16" | "u32" | "u64" | "u128" => integer(bits, false, false),
+        "i8" | "i16" | "i32" | "i64" | "i128" => integer(bits, true, false),
+        "NonZeroU8" | "NonZeroU16" | "NonZeroU32" | "NonZeroU64" | "NonZeroU128"
+        | "NonZeroUsize" => integer(bits, false, true),
+        "NonZeroI8" | "NonZeroI16" | "NonZeroI32" | "NonZeroI64" | "NonZeroI128" => {
+            integer(bits, true, true)
+        }
+        _ if size == 0 => json!({ "type": "null" }),
+        // primitives of unknown types are arrays of their bytes
+        _ => json!({
+            "type": "array",
+            "items": integer(8, false, false),
+            "minItems": size,
+            "maxItems": size,
+        }),
+    }
+}
+
+/// Varints are sequences of bytes of variable length, which are integers in JSON.
+fn varint(declaration: &str) -> Option<Json> {
+    let json = match declaration {
+        "VarU16" => integer(16, false, false),
+        "VarU32" => integer(32, false, false),
+        "VarU64" => integer(64, false, false),
+        "VarU128" => integer(128, false, false),
+        "VarI16" => integer(16, true, false),
+        "VarI32" => integer(32, true, false),
+        "VarI64" => integer(64, true, false),
+        "VarI128" => integer(128, true, false),
+        _ => return None,
+    };
+    Some(json)
+}
@@ -0,0 +1,782 @@
+//! Generator of Rust source code from a [`BorshSchemaContainer`], so that types matching a schema,
+//! received from elsewhere, don't have to be written by hand.
+//!
+//! Every struct and enum of the schema becomes a type with
+//! `#[derive(BorshSerialize, BorshDeserialize, BorshSchema)]`, and also `Debug`, `Clone` and
+//! `PartialEq`, the second one being required to deserialize boxed recursive types. Schema of
+//! the generated root type is the same as the given one, as long as the latter is produced
+//! by the derives:
+//!
+//! * types, which declarations are not identifiers, e.g. `Wrapper<u8>`, are named after them,
+//!   e.g. `WrapperU8`, and get `#[borsh(schema(name = "..."))]`;
+//! * variants of enums, defined with structs named `{Enum}{Variant}`, get fields of the latter;
+//! * enums with discriminants other than `0, 1, 2, ...` get `#[borsh(use_discriminant = true)]`;
+//! * sequences with length prefixes other than `u32` get `#[borsh(length = ...)]` or
+//!   `#[borsh(varint)]`, bounded ones get `#[borsh(max_len = ...)]`;
+//! * fields, which contain the type they belong to, are boxed.
+//!
+//! Sequences are generated as `String`, `Vec`, `VecDeque`, `LinkedList`, `BTreeMap`, `BTreeSet`,
+//! `HashMap` and `HashSet`, following their declarations, or as `Vec` otherwise, e.g. for maps with
+//! keys of generated types, which don't implement `Ord` or `Hash`. Variants, which aren't defined with
+//! structs, get a single unnamed field. Generated code is then compatible with the schema, though
+//! its schema differs in declarations of types. The code relies on `std`.
+//!
+//! # Example
+//!
+//! ```
+//! use borsh::schema::{rust, BorshSchemaContainer};
+//!
+//! #[derive(borsh::BorshSchema)]
+//! struct Account {
+//!     owner: String,
+//!     #[borsh(length = u8)]
+//!     keys: Vec<[u8; 32]>,
+//!     next: Option<Box<Account>>,
+//! }
+//!
+//! let container = BorshSchemaContainer::for_type::<Account>();
+//! let generated = rust::generate(&container).unwrap();
+//! assert!(generated.contains(
+//!     "#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+//! pub struct Account {
+//!     pub owner: String,
+//!     #[borsh(length = u8)]
+//!     pub keys: Vec<[u8; 32]>,
+//!     pub next: Box<Option<Account>>,
+//! }"
+//! ));
+//! ```
+
+use core::fmt::{self, Write};
+
+use super::value::is_string;
+use super::{
+    BorshSchemaContainer, Declaration, Definition, DiscriminantValue, Fields, VariantName,
+};
+use crate::__private::maybestd::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Possible error when generating Rust from a [`BorshSchemaContainer`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// Some of the declared types were lacking definition.
+    MissingDefinition(Declaration),
+    /// Primitive is none of the primitives, which borsh implements `BorshSchema` for.
+    UnsupportedPrimitive(Declaration),
+    /// Sequence has variable length without a prefix, or a length, which can't be expressed
+    /// with `length`, `varint` and `max_len` attributes of the field it's nested in.
+    UnsupportedLength(Declaration),
+    /// Enum has a tag other than `u8`, or a discriminant out of `0..=255` range.
+    UnsupportedTag(Declaration),
+    /// Type contains itself not through a struct or an enum.
+    UnsupportedRecursion(Declaration),
+    /// Name of a field or a variant is not a Rust identifier.
+    UnsupportedName(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingDefinition(declaration) => {
+                write!(f, "`{}` lacks definition", declaration)
+            }
+            Error::UnsupportedPrimitive(declaration) => {
+                write!(f, "`{}` is not a known primitive", declaration)
+            }
+            Error::UnsupportedLength(declaration) => write!(
+                f,
+                "length of `{}` can't be expressed with borsh attributes",
+                declaration
+            ),
+            Error::UnsupportedTag(declaration) => write!(
+                f,
+                "tag of `{}` is not `u8` or its discriminants are out of 0..=255 range",
+                declaration
+            ),
+            Error::UnsupportedRecursion(declaration) => write!(
+                f,
+                "`{}` contains itself not through a struct or an enum",
+                declaration
+            ),
+            Error::UnsupportedName(name) => write!(f, "`{}` is not a Rust identifier", name),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Generates Rust module with types of the `container`, the root one being the first.
+pub fn generate(container: &BorshSchemaContainer) -> Result<String, Error> {
+    let mut generator = Generator {
+        container,
+        names: BTreeMap::new(),
+        used_names: RESERVED_NAMES.iter().map(ToString::to_string).collect(),
+        items: Vec::new(),
+        typing: BTreeSet::new(),
+        imports: BTreeSet::new(),
+        output: String::new(),
+    };
+    generator.visit(container.declaration(), &mut BTreeSet::new())?;
+    for declaration in generator.items.clone() {
+        match generator.kind(declaration)? {
+            Kind::Struct(fields) => generator.write_struct(declaration, fields)?,
+            Kind::Enum(variants) => generator.write_enum(declaration, variants)?,
+            _ => unreachable!("only structs and enums are items"),
+        }
+    }
+
+    let mut result = format!(
+        "// Generated by borsh from the schema of `{}`. Do not edit.\n\n",
+        container.declaration()
+    );
+    result.push_str("use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};\n");
+    let imports: Vec<_> = generator.imports.iter().copied().collect();
+    match imports.as_slice() {
+        [] => {}
+        [import] => writeln!(result, "use std::collections::{};", import).unwrap(),
+        imports => writeln!(result, "use std::collections::{{{}}};", imports.join(", ")).unwrap(),
+    }
+    result.push_str(&generator.output);
+    Ok(result)
+}
+
+/// Names of types, which are used by the generated code, so that types of the schema
+/// don't shadow them.
+const RESERVED_NAMES: &[&str] = &[
+    "Self",
+    "Box",
+    "Option",
+    "Result",
+    "String",
+    "Vec",
+    "VecDeque",
+    "LinkedList",
+    "BTreeMap",
+    "BTreeSet",
+    "HashMap",
+    "HashSet",
+    "BorshSerialize",
+    "BorshDeserialize",
+    "BorshSchema",
+];
+
+const VARINTS: &[(&str, &str)] = &[
+    ("VarU16", "u16"),
+    ("VarU32", "u32"),
+    ("VarU64", "u64"),
+    ("VarU128", "u128"),
+    ("VarI16", "i16"),
+    ("VarI32", "i32"),
+    ("VarI64", "i64"),
+    ("VarI128", "i128"),
+];
+
+/// Length prefix of a sequence, as it's expressed with attributes of a field.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Length {
+    Prefix(u8),
+    Varint,
+}
+
+impl Length {
+    const DEFAULT: Length = Length::Prefix(Definition::DEFAULT_LENGTH_WIDTH);
+
+    /// Largest length, which is expressible with the prefix, so it needs no `max_len`.
+    fn max(self) -> u64 {
+        match self {
+            Length::Prefix(1) => u8::MAX.into(),
+            Length::Prefix(2) => u16::MAX.into(),
+            Length::Prefix(8) => u64::MAX,
+            _ => u32::MAX.into(),
+        }
+    }
+
+    fn attribute(self) -> Option<&'static str> {
+        match self {
+            Length::Prefix(1) => Some("length = u8"),
+            Length::Prefix(2) => Some("length = u16"),
+            Length::Prefix(8) => Some("length = u64"),
+            Length::Prefix(_) => None,
+            Length::Varint => Some("varint"),
+        }
+    }
+}
+
+/// Shape of a definition, as it's expressed in Rust.
+enum Kind<'a> {
+    Struct(&'a Fields),
+    Enum(&'a [(DiscriminantValue, VariantName, Declaration)]),
+    Primitive(String),
+    Varint(&'static str),
+    Option(&'a Declaration),
+    Result {
+        ok: &'a Declaration,
+        err: &'a Declaration,
+    },
+    Array {
+        elements: &'a Declaration,
+        len: u64,
+    },
+    Sequence {
+        elements: &'a Declaration,
+        length: Length,
+        max_len: u64,
+    },
+    Tuple(&'a [Declaration]),
+}
+
+impl<'a> Kind<'a> {
+    /// Whether the kind is generated as a type.
+    fn is_item(&self) -> bool {
+        matches!(self, Kind::Struct(_) | Kind::Enum(_))
+    }
+}
+
+fn primitive(declaration: &str, size: u8) -> Option<String> {
+    let primitive = match declaration {
+        "bool" | "f32" | "f64" | "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32"
+        | "i64" | "i128" => declaration.to_string(),
+        "()" if size == 0 => declaration.to_string(),
+        "NonZeroU8" | "NonZeroU16" | "NonZeroU32" | "NonZeroU64" | "NonZeroU128"
+        | "NonZeroUsize" | "NonZeroI8" | "NonZeroI16" | "NonZeroI32" | "NonZeroI64"
+        | "NonZeroI128" => format!("core::num::{}", declaration),
+        _ => {
+            let inner = declaration
+                .strip_prefix("BigEndian<")
+                .and_then(|inner| inner.strip_suffix('>'))?;
+            if inner == "bool" || inner == "()" || inner.starts_with("BigEndian<") {
+                return None;
+            }
+            format!("borsh::endian::BigEndian<{}>", primitive(inner, size)?)
+        }
+    };
+    Some(primitive)
+}
+
+/// Identifier of a type, derived from its declaration, e.g. `WrapperU8` for `Wrapper<u8>`.
+fn identifier(declaration: &str) -> String {
+    let mut identifier = String::new();
+    for part in declaration.split(|c: char| !c.is_ascii_alphanumeric() && c != '_') {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            identifier.push(first.to_ascii_uppercase());
+            identifier.extend(chars);
+        }
+    }
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, 'T');
+    }
+    identifier
+}
+
+/// Name of a field or a variant, which is a raw identifier if it's a keyword.
+fn name(name: &str) -> Result<String, Error> {
+    let identifier = name.strip_prefix("r#").unwrap_or(name);
+    let mut chars = identifier.chars();
+    let is_identifier = chars
+        .next()
+        .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_identifier || ["_", "self", "Self", "super", "crate"].contains(&identifier) {
+        return Err(Error::UnsupportedName(name.to_string()));
+    }
+    if identifier.len() == name.len() && KEYWORDS.contains(&name) {
+        Ok(format!("r#{}", name))
+    } else {
+        Ok(name.to_string())
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+struct Generator<'a> {
+    container: &'a BorshSchemaContainer,
+    /// Names of types of structs and enums.
+    names: BTreeMap<&'a str, String>,
+    used_names: BTreeSet<String>,
+    /// Structs and enums in order of their definitions.
+    items: Vec<&'a Declaration>,
+    /// Declarations, which types are being generated, to detect infinite recursion.
+    typing: BTreeSet<&'a str>,
+    /// Used collections of `std::collections`.
+    imports: BTreeSet<&'static str>,
+    output: String,
+}
+
+impl<'a> Generator<'a> {
+    fn definition(&self, declaration: &'a Declaration) -> Result<&'a Definition, Error> {
+        self.container
+            .get_definition(declaration)
+            .ok_or_else(|| Error::MissingDefinition(declaration.clone()))
+    }
+
+    fn kind(&self, declaration: &'a Declaration) -> Result<Kind<'a>, Error> {
+        let kind = match self.definition(declaration)? {
+            Definition::Primitive(size) => match primitive(declaration, *size) {
+                Some(primitive) => Kind::Primitive(primitive),
+                None => return Err(Error::UnsupportedPrimitive(declaration.clone())),
+            },
+            Definition::Sequence {
+                length_width: Definition::ARRAY_LENGTH_WIDTH,
+                length_range,
+                elements,
+            } => {
+                let varint = VARINTS.iter().find(|(varint, _)| varint == declaration);
+                match varint {
+                    Some((varint, _)) if elements == "u8" => Kind::Varint(varint),
+                    _ if length_range.start() == length_range.end() => Kind::Array {
+                        elements,
+                        len: *length_range.start(),
+                    },
+                    _ => return Err(Error::UnsupportedLength(declaration.clone())),
+                }
+            }
+            Definition::Sequence {
+                length_width: length_width @ (1 | 2 | 4 | 8),
+                length_range,
+                elements,
+            } if *length_range.start() == 0 => Kind::Sequence {
+                elements,
+                length: Length::Prefix(*length_width),
+                max_len: *length_range.end(),
+            },
+            Definition::Sequence { .. } => {
+                return Err(Error::UnsupportedLength(declaration.clone()));
+            }
+            Definition::Tuple { elements } => match elements.as_slice() {
+                [length, untagged] if length == "VarU32" && untagged.starts_with('[') => {
+                    match self.definition(untagged)? {
+                        Definition::Sequence {
+                            length_width: Definition::ARRAY_LENGTH_WIDTH,
+                            length_range,
+                            elements,
+                        } if *length_range.start() == 0 => Kind::Sequence {
+                            elements,
+                            length: Length::Varint,
+                            max_len: *length_range.end(),
+                        },
+                        _ => Kind::Tuple(elements),
+                    }
+                }
+                _ => Kind::Tuple(elements),
+            },
+            Definition::Enum { tag_width, .. } if *tag_width != 1 => {
+                return Err(Error::UnsupportedTag(declaration.clone()));
+            }
+            Definition::Enum { variants, .. } => match variants.as_slice() {
+                [(0, none, unit), (1, some, value)]
+                    if none == "None" && some == "Some" && unit == "()" =>
+                {
+                    Kind::Option(value)
+                }
+                [(1, ok_name, ok), (0, err_name, err)] if ok_name == "Ok" && err_name == "Err" => {
+                    Kind::Result { ok, err }
+                }
+                _ => {
+                    let mut discriminants = BTreeSet::new();
+                    for (discriminant, _, _) in variants {
+                        if !(0..=255).contains(discriminant) || !discriminants.insert(discriminant)
+                        {
+                            return Err(Error::UnsupportedTag(declaration.clone()));
+                        }
+                    }
+                    Kind::Enum(variants)
+                }
+            },
+            Definition::Struct { fields } => Kind::Struct(fields),
+        };
+        Ok(kind)
+    }
+
+    /// Fields of the struct, which defines the variant, if they are generated inline.
+    fn variant_fields(
+        &self,
+        enum_declaration: &str,
+        (_, variant_name, variant): &'a (DiscriminantValue, VariantName, Declaration),
+    ) -> Option<&'a Fields> {
+        if *variant != format!("{}{}", enum_declaration, variant_name) {
+            return None;
+        }
+        match self.container.get_definition(variant) {
+            Some(Definition::Struct { fields }) => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// Names all structs and enums, reachable from `declaration`.
+    fn visit(
+        &mut self,
+        declaration: &'a Declaration,
+        visited: &mut BTreeSet<&'a str>,
+    ) -> Result<(), Error> {
+        if !visited.insert(declaration) {
+            return Ok(());
+        }
+        let kind = self.kind(declaration)?;
+        if kind.is_item() {
+            let mut name = identifier(declaration);
+            let mut suffix = 1;
+            while self.used_names.contains(&name) {
+                suffix += 1;
+                name = format!("{}{}", identifier(declaration), suffix);
+            }
+            self.used_names.insert(name.clone());
+            self.names.insert(declaration, name);
+            self.items.push(declaration);
+        }
+        let children: Vec<&'a Declaration> = match kind {
+            Kind::Struct(fields) => fields_declarations(fields),
+            Kind::Enum(variants) => {
+                let mut children = Vec::new();
+                for variant in variants {
+                    match self.variant_fields(declaration, variant) {
+                        Some(fields) => children.extend(fields_declarations(fields)),
+                        None => children.push(&variant.2),
+                    }
+                }
+                children
+            }
+            Kind::Primitive(_) | Kind::Varint(_) => Vec::new(),
+            Kind::Option(value) => Vec::from([value]),
+            Kind::Result { ok, err } => Vec::from([ok, err]),
+            Kind::Array { elements, .. } | Kind::Sequence { elements, .. } => Vec::from([elements]),
+            Kind::Tuple(elements) => elements.iter().collect(),
+        };
+        for child in children {
+            self.visit(child, visited)?;
+        }
+        Ok(())
+    }
+
+    /// Whether a value of `declaration` contains a value of `target` not through a sequence,
+    /// so a field of the former in the latter has to be boxed.
+    fn contains(
+        &self,
+        declaration: &'a Declaration,
+        target: &str,
+        visited: &mut BTreeSet<&'a str>,
+    ) -> bool {
+        if declaration == target {
+            return true;
+        }
+        if !visited.insert(declaration) {
+            return false;
+        }
+        let children: Vec<&Declaration> = match self.container.get_definition(declaration) {
+            Some(Definition::Struct { fields }) => fields_declarations(fields),
+            Some(Definition::Enum { variants, .. }) => {
+                variants.iter().map(|(_, _, variant)| variant).collect()
+            }
+            Some(Definition::Tuple { elements }) => elements.iter().collect(),
+            Some(Definition::Sequence {
+                length_width: Definition::ARRAY_LENGTH_WIDTH,
+                length_range,
+                elements,
+            }) if length_range.start() == length_range.end() => Vec::from([elements]),
+            _ => Vec::new(),
+        };
+        children
+            .into_iter()
+            .any(|child| self.contains(child, target, visited))
+    }
+
+    /// Type of `declaration`, where sequences have the `length` of the field they belong to.
+    fn rust_type(&mut self, declaration: &'a Declaration, length: Length) -> Result<String, Error> {
+        let kind = self.kind(declaration)?;
+        if kind.is_item() {
+            return Ok(self.names[declaration.as_str()].clone());
+        }
+        if !self.typing.insert(declaration) {
+            return Err(Error::UnsupportedRecursion(declaration.clone()));
+        }
+        let rust_type = match kind {
+            Kind::Struct(_) | Kind::Enum(_) => unreachable!("items are named"),
+            Kind::Primitive(primitive) => primitive,
+            Kind::Varint(varint) if length == Length::Varint => VARINTS
+                .iter()
+                .find(|(name, _)| *name == varint)
+                .map(|(_, integer)| integer.to_string())
+                .unwrap(),
+            Kind::Varint(varint) => format!("borsh::varint::{}", varint),
+            Kind::Option(value) => format!("Option<{}>", self.rust_type(value, length)?),
+            Kind::Result { ok, err } => format!(
+                "Result<{}, {}>",
+                self.rust_type(ok, length)?,
+                self.rust_type(err, length)?
+            ),
+            Kind::Array { elements, len } => {
+                format!("[{}; {}]", self.rust_type(elements, length)?, len)
+            }
+            Kind::Sequence {
+                elements,
+                length: sequence_length,
+                max_len,
+            } => {
+                if sequence_length != length || max_len != length.max() {
+                    return Err(Error::UnsupportedLength(declaration.clone()));
+                }
+                self.sequence_type(declaration, elements, length)?
+            }
+            Kind::Tuple(elements) => {
+                let mut types = Vec::new();
+                for element in elements {
+                    types.push(self.rust_type(element, length)?);
+                }
+                match types.as_slice() {
+                    [single] => format!("({},)", single),
+                    types => format!("({})", types.join(", ")),
+                }
+            }
+        };
+        self.typing.remove(declaration.as_str());
+        Ok(rust_type)
+    }
+
+    fn sequence_type(
+        &mut self,
+        declaration: &'a Declaration,
+        elements: &'a Declaration,
+        length: Length,
+    ) -> Result<String, Error> {
+        if is_string(declaration) && elements == "u8" {
+            return Ok("String".to_string());
+        }
+        let collection = declaration.split('<').next().unwrap_or_default();
+        let collection = match collection {
+            "BTreeMap" | "HashMap" => match self.kind(elements)? {
+                Kind::Tuple([key, value]) if self.is_key(key) => {
+                    let key = self.rust_type(key, length)?;
+                    let value = self.rust_type(value, length)?;
+                    let collection = if collection == "BTreeMap" {
+                        "BTreeMap"
+                    } else {
+                        "HashMap"
+                    };
+                    self.imports.insert(collection);
+                    return Ok(format!("{}<{}, {}>", collection, key, value));
+                }
+                _ => "Vec",
+            },
+            "BTreeSet" if self.is_key(elements) => "BTreeSet",
+            "HashSet" if self.is_key(elements) => "HashSet",
+            "VecDeque" => "VecDeque",
+            "LinkedList" => "LinkedList",
+            _ => "Vec",
+        };
+        if collection != "Vec" {
+            self.imports.insert(collection);
+        }
+        Ok(format!(
+            "{}<{}>",
+            collection,
+            self.rust_type(elements, length)?
+        ))
+    }
+
+    /// Whether the type of `declaration` implements `Ord` and `Hash`, so it can be a key of a map.
+    fn is_key(&self, declaration: &'a Declaration) -> bool {
+        match self.kind(declaration) {
+            Ok(Kind::Primitive(primitive)) => {
+                !primitive.contains("f32") && !primitive.contains("f64")
+            }
+            Ok(Kind::Option(value)) => self.is_key(value),
+            Ok(Kind::Result { ok, err }) => self.is_key(ok) && self.is_key(err),
+            Ok(Kind::Array { elements, .. }) => self.is_key(elements),
+            Ok(Kind::Sequence { elements, .. }) => {
+                let collection = declaration.split('<').next().unwrap_or_default();
+                ["String", "Vec", "VecDeque", "LinkedList", "BTreeSet"].contains(&collection)
+                    && self.is_key(elements)
+            }
+            Ok(Kind::Tuple(elements)) => elements.iter().all(|element| self.is_key(element)),
+            _ => false,
+        }
+    }
+
+    /// Attributes and type of a field of `item`.
+    fn field(
+        &mut self,
+        item: &'a Declaration,
+        declaration: &'a Declaration,
+    ) -> Result<(String, String), Error> {
+        // `max_len` is also allowed on options of sequences
+        let (sequence, optional) = match self.kind(declaration)? {
+            Kind::Option(value) => (value, true),
+            _ => (declaration, false),
+        };
+        let (attributes, rust_type) = match self.kind(sequence)? {
+            Kind::Sequence {
+                elements,
+                length,
+                max_len,
+            } => {
+                let mut attributes = Vec::new();
+                attributes.extend(length.attribute().map(ToString::to_string));
+                if max_len != length.max() {
+                    attributes.push(format!("max_len = {}", max_len));
+                }
+                let mut rust_type = self.sequence_type(sequence, elements, length)?;
+                if optional {
+                    rust_type = format!("Option<{}>", rust_type);
+                }
+                (attributes, rust_type)
+            }
+            Kind::Varint(_) if !optional => (
+                Vec::from(["varint".to_string()]),
+                self.rust_type(declaration, Length::Varint)?,
+            ),
+            _ => (Vec::new(), self.rust_type(declaration, Length::DEFAULT)?),
+        };
+        let attributes = if attributes.is_empty() {
+            String::new()
+        } else {
+            format!("#[borsh({})] ", attributes.join(", "))
+        };
+        if self.contains(declaration, item, &mut BTreeSet::new()) {
+            Ok((attributes, format!("Box<{}>", rust_type)))
+        } else {
+            Ok((attributes, rust_type))
+        }
+    }
+
+    /// Fields of `item` with their attributes, formatted as in a struct with `{ ... }` or `( ... )`.
+    fn fields(
+        &mut self,
+        item: &'a Declaration,
+        fields: &'a Fields,
+        indent: &str,
+        visibility: &str,
+    ) -> Result<String, Error> {
+        let mut output = String::new();
+        match fields {
+            Fields::NamedFields(fields) => {
+                output.push_str(" {\n");
+                for (field_name, field) in fields {
+                    let (attributes, rust_type) = self.field(item, field)?;
+                    if !attributes.is_empty() {
+                        writeln!(output, "{}    {}", indent, attributes.trim_end()).unwrap();
+                    }
+                    writeln!(
+                        output,
+                        "{}    {}{}: {},",
+                        indent,
+                        visibility,
+                        name(field_name)?,
+                        rust_type
+                    )
+                    .unwrap();
+                }
+                write!(output, "{}}}", indent).unwrap();
+            }
+            Fields::UnnamedFields(fields) => {
+                output.push('(');
+                for (index, field) in fields.iter().enumerate() {
+                    if index > 0 {
+                        output.push_str(", ");
+                    }
+                    let (attributes, rust_type) = self.field(item, field)?;
+                    write!(output, "{}{}{}", attributes, visibility, rust_type).unwrap();
+                }
+                output.push(')');
+            }
+            Fields::Empty => {}
+        }
+        Ok(output)
+    }
+
+    fn write_header(&mut self, declaration: &str, attributes: &[String]) {
+        self.output.push_str(
+            "\n#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]\n",
+        );
+        let mut attributes = attributes.to_vec();
+        if self.names[declaration] != declaration {
+            attributes.push(format!("schema(name = {:?})", declaration));
+        }
+        if !attributes.is_empty() {
+            writeln!(self.output, "#[borsh({})]", attributes.join(", ")).unwrap();
+        }
+    }
+
+    fn write_struct(
+        &mut self,
+        declaration: &'a Declaration,
+        fields: &'a Fields,
+    ) -> Result<(), Error> {
+        let fields_output = self.fields(declaration, fields, "", "pub ")?;
+        self.write_header(declaration, &[]);
+        let terminator = if matches!(fields, Fields::NamedFields(_)) {
+            ""
+        } else {
+            ";"
+        };
+        writeln!(
+            self.output,
+            "pub struct {}{}{}",
+            self.names[declaration.as_str()],
+            fields_output,
+            terminator
+        )
+        .unwrap();
+        Ok(())
+    }
+
+    fn write_enum(
+        &mut self,
+        declaration: &'a Declaration,
+        variants: &'a [(DiscriminantValue, VariantName, Declaration)],
+    ) -> Result<(), Error> {
+        let use_discriminant = variants
+            .iter()
+            .enumerate()
+            .any(|(index, (discriminant, _, _))| *discriminant != index as DiscriminantValue);
+        let mut body = String::new();
+        for variant in variants {
+            let (discriminant, variant_name, variant_declaration) = variant;
+            let fields = match self.variant_fields(declaration, variant) {
+                Some(fields) => self.fields(declaration, fields, "    ", "")?,
+                None => {
+                    let (attributes, rust_type) = self.field(declaration, variant_declaration)?;
+                    format!("({}{})", attributes, rust_type)
+                }
+            };
+            write!(body, "    {}{}", name(variant_name)?, fields).unwrap();
+            if use_discriminant {
+                write!(body, " = {}", discriminant).unwrap();
+            }
+            body.push_str(",\n");
+        }
+
+        if use_discriminant {
+            self.write_header(declaration, &["use_discriminant = true".to_string()]);
+            self.output.push_str("#[repr(u8)]\n");
+        } else {
+            self.write_header(declaration, &[]);
+        }
+        writeln!(
+            self.output,
+            "pub enum {} {{\n{}}}",
+            self.names[declaration.as_str()],
+            body
+        )
+        .unwrap();
+        Ok(())
+    }
+}
+
+fn fields_declarations(fields: &Fields) -> Vec<&Declaration> {
+    match fields {
+        Fields::NamedFields(fields) => fields.iter().map(|(_, field)| field).collect(),
+        Fields::UnnamedFields(fields) => fields.iter().collect(),
+        Fields::Empty => Vec::new(),
+    }
+}
@@ -0,0 +1,597 @@
+//! Generator of [TypeScript](https://www.typescriptlang.org/) type declarations and
+//! [borsh-js](https://github.com/near/borsh-js) schemas from a [`BorshSchemaContainer`],
+//! so that schemas of frontends are derived from Rust types instead of being written by hand.
+//!
+//! Every struct and enum of the schema gets a type alias, describing values which borsh-js
+//! serializes and deserializes, and a constant with the schema, named after the type with
+//! `Schema` suffix. The schema of the root declaration is exported as `schema`.
+//!
+//! | Rust                               | TypeScript                 | borsh-js                        |
+//! |------------------------------------|----------------------------|---------------------------------|
+//! | `bool`                             | `boolean`                  | `'bool'`                        |
+//! | `u8`..`u32`, `i8`..`i32`, floats   | `number`                   | `'u8'`, ..., `'f64'`            |
+//! | `u64`, `u128`, `i64`, `i128`       | `bigint`                   | `'u64'`, ..., `'i128'`          |
+//! | `String`                           | `string`                   | `'string'`                      |
+//! | `Option<T>`                        | `T \| null`                | `{ option: T }`                 |
+//! | `Vec<T>`, `[T; N]`                 | `T[]`                      | `{ array: { type: T, len?: N } }` |
+//! | `BTreeMap<K, V>`, `HashMap<K, V>`  | `Map<K, V>`                | `{ map: { key: K, value: V } }` |
+//! | `BTreeSet<T>`, `HashSet<T>`        | `Set<T>`                   | `{ set: T }`                    |
+//! | tuples and tuple structs           | `{ 0: A; 1: B }`           | `{ struct: { 0: A, 1: B } }`    |
+//! | enums                              | `{ A: EnumA } \| ...`      | `{ enum: [...] }`               |
+//!
+//! Other fixed-size primitives, e.g. `BigEndian<u32>`, are represented with arrays of their bytes.
+//! Gaps between discriminants of enums are filled with placeholder variants, as borsh-js uses
+//! indices of variants as their tags.
+//!
+//! borsh-js validates schemas recursively, so schemas of recursive types have to be used
+//! with `validate` argument of `serialize`/`deserialize` set to `false`.
+//!
+//! # Example
+//!
+//! ```
+//! use borsh::schema::{typescript, BorshSchemaContainer};
+//!
+//! #[derive(borsh::BorshSchema)]
+//! struct Account {
+//!     owner: String,
+//!     balance: u64,
+//!     tags: Vec<Option<u8>>,
+//! }
+//!
+//! let container = BorshSchemaContainer::for_type::<Account>();
+//! let generated = typescript::generate(&container).unwrap();
+//! assert!(generated.contains(
+//!     "export type Account = {
+//!   owner: string;
+//!   balance: bigint;
+//!   tags: (number | null)[];
+//! };"
+//! ));
+//! assert!(generated.contains(
+//!     "export const AccountSchema: Schema = {
+//!   struct: {
+//!     owner: 'string',
+//!     balance: 'u64',
+//!     tags: { array: { type: { option: 'u8' } } },
+//!   },
+//! };"
+//! ));
+//! ```
+
+use core::fmt::{self, Write};
+
+use super::value::is_string;
+use super::{
+    BorshSchemaContainer, Declaration, Definition, DiscriminantValue, Fields, VariantName,
+};
+use crate::__private::maybestd::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Possible error when generating TypeScript from a [`BorshSchemaContainer`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// Some of the declared types were lacking definition.
+    MissingDefinition(Declaration),
+    /// Sequence has a length prefix other than `u32`, or has variable length without a prefix,
+    /// e.g. a varint, neither of which borsh-js supports.
+    UnsupportedLength(Declaration),
+    /// Enum has a tag other than `u8`, or a discriminant out of `0..=255` range.
+    UnsupportedTag(Declaration),
+    /// Type contains itself not through a struct or an enum, which can't be expressed
+    /// without naming it.
+    UnsupportedRecursion(Declaration),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingDefinition(declaration) => {
+                write!(f, "`{}` lacks definition", declaration)
+            }
+            Error::UnsupportedLength(declaration) => write!(
+                f,
+                "length of `{}` is neither fixed nor prefixed with `u32`",
+                declaration
+            ),
+            Error::UnsupportedTag(declaration) => write!(
+                f,
+                "tag of `{}` is not `u8` or its discriminants are out of 0..=255 range",
+                declaration
+            ),
+            Error::UnsupportedRecursion(declaration) => write!(
+                f,
+                "`{}` contains itself not through a struct or an enum",
+                declaration
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Generates TypeScript module with type declarations and borsh-js schemas
+/// of all types of the `container`.
+pub fn generate(container: &BorshSchemaContainer) -> Result<String, Error> {
+    let mut generator = Generator {
+        container,
+        visited: BTreeMap::new(),
+        names: BTreeMap::new(),
+        used_names: RESERVED_NAMES.iter().map(ToString::to_string).collect(),
+        recursive: false,
+        output: String::new(),
+    };
+    generator.visit(container.declaration())?;
+    let schema = generator.schema(container.declaration(), &mut false)?;
+
+    let mut result = format!(
+        "// Generated by borsh from the schema of `{}`. Do not edit.\n",
+        container.declaration()
+    );
+    if generator.recursive {
+        result.push_str(
+            "// Schemas of recursive types have to be used with `validate = false` in borsh-js.\n",
+        );
+    }
+    result.push_str("import type { Schema } from 'borsh';\n");
+    result.push_str(&generator.output);
+    writeln!(result, "\nexport const schema: Schema = {};", schema).unwrap();
+    Ok(result)
+}
+
+/// Names of types, which are used by the generated code, so that types of the schema
+/// don't shadow them.
+const RESERVED_NAMES: &[&str] = &["Schema", "Map", "Set", "Record"];
+
+/// Shape of a definition, as it's expressed in TypeScript.
+enum Kind<'a> {
+    Struct(&'a Fields),
+    Enum(&'a [(DiscriminantValue, VariantName, Declaration)]),
+    Primitive(&'static str),
+    /// Fixed-size primitive, which is unknown to borsh-js.
+    Bytes(u8),
+    Unit,
+    String,
+    Option(&'a Declaration),
+    Array {
+        elements: &'a Declaration,
+        len: Option<u64>,
+    },
+    Map {
+        key: &'a Declaration,
+        value: &'a Declaration,
+    },
+    Set(&'a Declaration),
+    Tuple(&'a [Declaration]),
+}
+
+impl<'a> Kind<'a> {
+    /// Whether the kind gets its own type and schema constant.
+    fn is_named(&self) -> bool {
+        matches!(self, Kind::Struct(_) | Kind::Enum(_))
+    }
+
+    fn children(&self) -> Vec<&'a Declaration> {
+        match *self {
+            Kind::Struct(fields) => match fields {
+                Fields::NamedFields(fields) => fields.iter().map(|(_, field)| field).collect(),
+                Fields::UnnamedFields(fields) => fields.iter().collect(),
+                Fields::Empty => Vec::new(),
+            },
+            Kind::Enum(variants) => variants
+                .iter()
+                .map(|(_, _, declaration)| declaration)
+                .collect(),
+            Kind::Primitive(_) | Kind::Bytes(_) | Kind::Unit | Kind::String => Vec::new(),
+            Kind::Option(declaration)
+            | Kind::Array {
+                elements: declaration,
+                ..
+            }
+            | Kind::Set(declaration) => Vec::from([declaration]),
+            Kind::Map { key, value } => Vec::from([key, value]),
+            Kind::Tuple(elements) => elements.iter().collect(),
+        }
+    }
+}
+
+fn primitive(declaration: &str) -> Option<&'static str> {
+    let primitive = match declaration {
+        "bool" => "bool",
+        "f32" => "f32",
+        "f64" => "f64",
+        "u8" | "NonZeroU8" => "u8",
+        "u16" | "NonZeroU16" => "u16",
+        "u32" | "NonZeroU32" => "u32",
+        "u64" | "NonZeroU64" | "NonZeroUsize" => "u64",
+        "u128" | "NonZeroU128" => "u128",
+        "i8" | "NonZeroI8" => "i8",
+        "i16" | "NonZeroI16" => "i16",
+        "i32" | "NonZeroI32" => "i32",
+        "i64" | "NonZeroI64" => "i64",
+        "i128" | "NonZeroI128" => "i128",
+        _ => return None,
+    };
+    Some(primitive)
+}
+
+fn primitive_type(primitive: &str) -> &'static str {
+    match primitive {
+        "bool" => "boolean",
+        "u64" | "u128" | "i64" | "i128" => "bigint",
+        _ => "number",
+    }
+}
+
+/// Identifier of a type, derived from its declaration, e.g. `WrapperU8` for `Wrapper<u8>`.
+fn identifier(declaration: &str) -> String {
+    let mut identifier = String::new();
+    for part in declaration.split(|c: char| !c.is_ascii_alphanumeric() && c != '_') {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            identifier.push(first.to_ascii_uppercase());
+            identifier.extend(chars);
+        }
+    }
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, 'T');
+    }
+    identifier
+}
+
+/// Name of a field or a variant, quoted if it's not an identifier or an index.
+fn property(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .map_or(false, |c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    let is_index = !name.is_empty() && name.chars().all(|c| c.is_ascii_digit());
+    if is_identifier || is_index {
+        name.to_string()
+    } else {
+        format!("{:?}", name)
+    }
+}
+
+/// Field of an object literal with a schema, which is a getter if the schema refers to
+/// constants, which aren't defined yet.
+fn schema_property(name: &str, schema: &str, lazy: bool) -> String {
+    if lazy {
+        format!(
+            "get {}(): Schema {{\n      return {};\n    }}",
+            property(name),
+            schema
+        )
+    } else {
+        format!("{}: {}", property(name), schema)
+    }
+}
+
+struct Generator<'a> {
+    container: &'a BorshSchemaContainer,
+    /// Visited declarations, whether their definitions are already written to the output.
+    visited: BTreeMap<&'a str, bool>,
+    /// Names of types of structs and enums.
+    names: BTreeMap<&'a str, String>,
+    used_names: BTreeSet<String>,
+    /// Whether some schema refers to itself, so it can't be validated by borsh-js.
+    recursive: bool,
+    output: String,
+}
+
+impl<'a> Generator<'a> {
+    fn kind(&self, declaration: &'a Declaration) -> Result<Kind<'a>, Error> {
+        let definition = self
+            .container
+            .get_definition(declaration)
+            .ok_or_else(|| Error::MissingDefinition(declaration.clone()))?;
+        let kind = match definition {
+            Definition::Primitive(size) => match primitive(declaration) {
+                Some(primitive) => Kind::Primitive(primitive),
+                None if *size == 0 => Kind::Unit,
+                None => Kind::Bytes(*size),
+            },
+            Definition::Sequence {
+                length_width: Definition::DEFAULT_LENGTH_WIDTH,
+                elements,
+                ..
+            } => {
+                if is_string(declaration) && elements == "u8" {
+                    Kind::String
+                } else if declaration.starts_with("BTreeMap<")
+                    || declaration.starts_with("HashMap<")
+                {
+                    match self.container.get_definition(elements) {
+                        Some(Definition::Tuple { elements }) if elements.len() == 2 => Kind::Map {
+                            key: &elements[0],
+                            value: &elements[1],
+                        },
+                        _ => Kind::Array {
+                            elements,
+                            len: None,
+                        },
+                    }
+                } else if declaration.starts_with("BTreeSet<")
+                    || declaration.starts_with("HashSet<")
+                {
+                    Kind::Set(elements)
+                } else {
+                    Kind::Array {
+                        elements,
+                        len: None,
+                    }
+                }
+            }
+            Definition::Sequence {
+                length_width: Definition::ARRAY_LENGTH_WIDTH,
+                length_range,
+                elements,
+            } if length_range.start() == length_range.end() => Kind::Array {
+                elements,
+                len: Some(*length_range.start()),
+            },
+            Definition::Sequence { .. } => {
+                return Err(Error::UnsupportedLength(declaration.clone()));
+            }
+            Definition::Tuple { elements } if elements.is_empty() => Kind::Unit,
+            Definition::Tuple { elements } => Kind::Tuple(elements),
+            Definition::Enum { tag_width, .. } if *tag_width != 1 => {
+                return Err(Error::UnsupportedTag(declaration.clone()));
+            }
+            Definition::Enum { variants, .. } => match variants.as_slice() {
+                [(0, none, unit), (1, some, value)]
+                    if none == "None"
+                        && some == "Some"
+                        && matches!(self.kind(unit)?, Kind::Unit) =>
+                {
+                    Kind::Option(value)
+                }
+                _ => {
+                    if variants
+                        .iter()
+                        .any(|(discriminant, _, _)| !(0..=255).contains(discriminant))
+                    {
+                        return Err(Error::UnsupportedTag(declaration.clone()));
+                    }
+                    Kind::Enum(variants)
+                }
+            },
+            Definition::Struct { fields } => Kind::Struct(fields),
+        };
+        Ok(kind)
+    }
+
+    /// Writes definitions of `declaration` and all types it depends on, the latter first.
+    fn visit(&mut self, declaration: &'a Declaration) -> Result<(), Error> {
+        let kind = self.kind(declaration)?;
+        match self.visited.get(declaration.as_str()) {
+            Some(true) => return Ok(()),
+            Some(false) if kind.is_named() => return Ok(()),
+            Some(false) => return Err(Error::UnsupportedRecursion(declaration.clone())),
+            None => {}
+        }
+        self.visited.insert(declaration, false);
+        if kind.is_named() {
+            let mut name = identifier(declaration);
+            let mut suffix = 1;
+            while self.used_names.contains(&name) {
+                suffix += 1;
+                name = format!("{}{}", identifier(declaration), suffix);
+            }
+            self.used_names.insert(name.clone());
+            self.names.insert(declaration, name);
+        }
+        for child in kind.children() {
+            self.visit(child)?;
+        }
+        match kind {
+            Kind::Struct(fields) => self.write_struct(declaration, fields)?,
+            Kind::Enum(variants) => self.write_enum(declaration, variants)?,
+            _ => {}
+        }
+        self.visited.insert(declaration, true);
+        Ok(())
+    }
+
+    fn write_struct(
+        &mut self,
+        declaration: &'a Declaration,
+        fields: &'a Fields,
+    ) -> Result<(), Error> {
+        let name = self.names[declaration.as_str()].clone();
+        let fields: Vec<(String, &Declaration)> = match fields {
+            Fields::NamedFields(fields) => fields
+                .iter()
+                .map(|(name, field)| (name.clone(), field))
+                .collect(),
+            Fields::UnnamedFields(fields) => fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| (index.to_string(), field))
+                .collect(),
+            Fields::Empty => Vec::new(),
+        };
+        if fields.is_empty() {
+            let output = format!(
+                "\nexport type {name} = Record<string, never>;\nexport const {name}Schema: Schema = {{ struct: {{}} }};\n",
+                name = name
+            );
+            self.output.push_str(&output);
+            return Ok(());
+        }
+
+        let mut type_ = format!("\nexport type {} = {{\n", name);
+        let mut schema = format!("export const {}Schema: Schema = {{\n  struct: {{\n", name);
+        for (field_name, field) in fields {
+            writeln!(
+                type_,
+                "  {}: {};",
+                property(&field_name),
+                self.ts_type(field)?
+            )
+            .unwrap();
+            let mut lazy = false;
+            let field_schema = self.schema(field, &mut lazy)?;
+            let field_schema = schema_property(&field_name, &field_schema, lazy);
+            writeln!(schema, "    {},", field_schema).unwrap();
+        }
+        type_.push_str("};\n");
+        schema.push_str("  },\n};\n");
+        self.output.push_str(&type_);
+        self.output.push_str(&schema);
+        Ok(())
+    }
+
+    fn write_enum(
+        &mut self,
+        declaration: &'a Declaration,
+        variants: &'a [(DiscriminantValue, VariantName, Declaration)],
+    ) -> Result<(), Error> {
+        let name = self.names[declaration.as_str()].clone();
+        let mut variants: Vec<_> = variants.iter().collect();
+        variants.sort_by_key(|(discriminant, _, _)| *discriminant);
+        if variants.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+            return Err(Error::UnsupportedTag(declaration.clone()));
+        }
+        if variants.is_empty() {
+            let output = format!(
+                "\nexport type {name} = never;\nexport const {name}Schema: Schema = {{ enum: [] }};\n",
+                name = name
+            );
+            self.output.push_str(&output);
+            return Ok(());
+        }
+
+        let mut type_ = format!("\nexport type {} =\n", name);
+        let mut schema = format!("export const {}Schema: Schema = {{\n  enum: [\n", name);
+        let mut next_discriminant = 0;
+        for (discriminant, variant_name, variant) in variants {
+            for placeholder in next_discriminant..*discriminant {
+                writeln!(
+                    schema,
+                    "    {{ struct: {{ __unused{}: {{ struct: {{}} }} }} }},",
+                    placeholder
+                )
+                .unwrap();
+            }
+            next_discriminant = discriminant + 1;
+
+            write!(
+                type_,
+                "  | {{ {}: {} }}",
+                property(variant_name),
+                self.ts_type(variant)?
+            )
+            .unwrap();
+            let mut lazy = false;
+            let variant_schema = self.schema(variant, &mut lazy)?;
+            if lazy {
+                writeln!(
+                    schema,
+                    "    {{\n      struct: {{\n        get {}(): Schema {{\n          return {};\n        }},\n      }},\n    }},",
+                    property(variant_name),
+                    variant_schema
+                )
+                .unwrap();
+            } else {
+                writeln!(
+                    schema,
+                    "    {{ struct: {{ {}: {} }} }},",
+                    property(variant_name),
+                    variant_schema
+                )
+                .unwrap();
+            }
+            type_.push('\n');
+        }
+        type_.pop();
+        type_.push_str(";\n");
+        schema.push_str("  ],\n};\n");
+        self.output.push_str(&type_);
+        self.output.push_str(&schema);
+        Ok(())
+    }
+
+    fn ts_type(&self, declaration: &'a Declaration) -> Result<String, Error> {
+        let ts_type = match self.kind(declaration)? {
+            Kind::Struct(_) | Kind::Enum(_) => self.names[declaration.as_str()].clone(),
+            Kind::Primitive(primitive) => primitive_type(primitive).to_string(),
+            Kind::Bytes(_) => "number[]".to_string(),
+            Kind::Unit => "Record<string, never>".to_string(),
+            Kind::String => "string".to_string(),
+            Kind::Option(value) => format!("{} | null", self.ts_type(value)?),
+            Kind::Array { elements, .. } => {
+                let elements = self.ts_type(elements)?;
+                if elements.contains('|') {
+                    format!("({})[]", elements)
+                } else {
+                    format!("{}[]", elements)
+                }
+            }
+            Kind::Map { key, value } => {
+                format!("Map<{}, {}>", self.ts_type(key)?, self.ts_type(value)?)
+            }
+            Kind::Set(elements) => format!("Set<{}>", self.ts_type(elements)?),
+            Kind::Tuple(elements) => {
+                let mut ts_type = String::from("{ ");
+                for (index, element) in elements.iter().enumerate() {
+                    if index > 0 {
+                        ts_type.push_str("; ");
+                    }
+                    write!(ts_type, "{}: {}", index, self.ts_type(element)?).unwrap();
+                }
+                ts_type.push_str(" }");
+                ts_type
+            }
+        };
+        Ok(ts_type)
+    }
+
+    /// Schema of `declaration`, `lazy` is set if it refers to constants, which aren't defined yet.
+    fn schema(&mut self, declaration: &'a Declaration, lazy: &mut bool) -> Result<String, Error> {
+        let schema = match self.kind(declaration)? {
+            Kind::Struct(_) | Kind::Enum(_) => {
+                if self.visited.get(declaration.as_str()) != Some(&true) {
+                    *lazy = true;
+                    self.recursive = true;
+                }
+                format!("{}Schema", self.names[declaration.as_str()])
+            }
+            Kind::Primitive(primitive) => format!("'{}'", primitive),
+            Kind::Bytes(size) => format!("{{ array: {{ type: 'u8', len: {} }} }}", size),
+            Kind::Unit => "{ struct: {} }".to_string(),
+            Kind::String => "'string'".to_string(),
+            Kind::Option(value) => format!("{{ option: {} }}", self.schema(value, lazy)?),
+            Kind::Array { elements, len } => {
+                let elements = self.schema(elements, lazy)?;
+                match len {
+                    Some(len) => format!("{{ array: {{ type: {}, len: {} }} }}", elements, len),
+                    None => format!("{{ array: {{ type: {} }} }}", elements),
+                }
+            }
+            Kind::Map { key, value } => format!(
+                "{{ map: {{ key: {}, value: {} }} }}",
+                self.schema(key, lazy)?,
+                self.schema(value, lazy)?
+            ),
+            Kind::Set(elements) => format!("{{ set: {} }}", self.schema(elements, lazy)?),
+            Kind::Tuple(elements) => {
+                let mut schema = String::from("{ struct: { ");
+                for (index, element) in elements.iter().enumerate() {
+                    if index > 0 {
+                        schema.push_str(", ");
+                    }
+                    write!(schema, "{}: {}", index, self.schema(element, lazy)?).unwrap();
+                }
+                schema.push_str(" } }");
+                schema
+            }
+        };
+        Ok(schema)
+    }
+}
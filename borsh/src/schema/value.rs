@@ -0,0 +1,377 @@
+use core::convert::TryFrom;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+use super::container_ext::min_serialized_size_of;
+use super::{BorshSchemaContainer, Definition, FieldName, Fields, VariantName};
+use crate::__private::maybestd::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use crate::de::{ERROR_NOT_ALL_BYTES_READ, ERROR_UNEXPECTED_LENGTH_OF_INPUT};
+use crate::endian::BigEndian;
+use crate::error::ERROR_ZST_FORBIDDEN;
+use crate::io::{Error, ErrorKind, Result};
+use crate::varint::{VarI128, VarI16, VarI32, VarI64, VarU128, VarU16, VarU32, VarU64};
+use crate::BorshDeserialize;
+
+/// Nesting depth of values, at which [`BorshSchemaContainer::deserialize_value`] gives up,
+/// so that schemas of recursive types without an exit don't overflow the stack.
+pub(super) const MAX_DEPTH: usize = 256;
+
+/// A value of any type, decoded with [`BorshSchemaContainer::deserialize_value`]
+/// without the Rust type, which produced it, being available.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Value {
+    /// `()`, other zero-sized primitives and structs without fields.
+    Unit,
+    Bool(bool),
+    /// `u8`..`u128`, their `NonZero` and [`BigEndian`] counterparts, and unsigned varints.
+    UInt(u128),
+    /// `i8`..`i128`, their `NonZero` and [`BigEndian`] counterparts, and signed varints.
+    Int(i128),
+    /// `f32` and `f64`.
+    Float(f64),
+    /// Sequences of bytes declared as `String` (including ones with custom length prefixes),
+    /// `BoundedString` or `AsciiString`.
+    String(String),
+    /// Other sequences of `u8`, e.g. `Vec<u8>` and `[u8; N]`, and primitives of unknown types.
+    Bytes(Vec<u8>),
+    /// Other sequences, e.g. `Vec<T>`, `[T; N]` and maps, which are sequences of key-value tuples.
+    Seq(Vec<Value>),
+    /// Tuples and structs with unnamed fields.
+    Tuple(Vec<Value>),
+    /// Structs with named fields.
+    Struct(Vec<(FieldName, Value)>),
+    /// A variant of an enum with its name and its value.
+    Enum(VariantName, Box<Value>),
+}
+
+impl BorshSchemaContainer {
+    /// Deserializes a value of the container's type from `bytes`, guided only by the schema.
+    ///
+    /// Primitives are interpreted according to their declarations (e.g. `u32` or `f64`),
+    /// primitives of unknown types are returned as [`Value::Bytes`].
+    /// Sequences with `length_width` of zero are supported if they are fixed-size,
+    /// or if they are varints, or if they follow a `VarU32` length in a tuple, as is the case
+    /// for collections with varint length prefix.
+    /// Untagged enums are not supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use borsh::schema::{BorshSchemaContainer, Value};
+    ///
+    /// let schema = BorshSchemaContainer::for_type::<(u16, Option<String>)>();
+    /// let bytes = borsh::to_vec(&(7u16, Some("borsh".to_string()))).unwrap();
+    /// assert_eq!(
+    ///     schema.deserialize_value(&bytes).unwrap(),
+    ///     Value::Tuple(vec![
+    ///         Value::UInt(7),
+    ///         Value::Enum("Some".to_string(), Box::new(Value::String("borsh".to_string()))),
+    ///     ])
+    /// );
+    /// ```
+    pub fn deserialize_value(&self, bytes: &[u8]) -> Result<Value> {
+        let mut buf = bytes;
+        let value = deserialize_value_impl(self.declaration(), self, &mut buf, 0)?;
+        if !buf.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData, ERROR_NOT_ALL_BYTES_READ));
+        }
+        Ok(value)
+    }
+}
+
+pub(super) fn invalid_data(msg: String) -> Error {
+    Error::new(ErrorKind::InvalidData, msg)
+}
+
+/// Implementation of [`BorshSchemaContainer::deserialize_value`].
+fn deserialize_value_impl(
+    declaration: &str,
+    schema: &BorshSchemaContainer,
+    buf: &mut &[u8],
+    depth: usize,
+) -> Result<Value> {
+    if depth > MAX_DEPTH {
+        return Err(invalid_data(format!(
+            "Value of `{}` is nested too deeply",
+            declaration
+        )));
+    }
+    let depth = depth + 1;
+
+    let definition = schema
+        .get_definition(declaration)
+        .ok_or_else(|| invalid_data(format!("Missing definition of `{}`", declaration)))?;
+    match definition {
+        Definition::Primitive(size) => primitive(declaration, *size, buf),
+        Definition::Sequence {
+            length_width,
+            length_range,
+            elements,
+        } => {
+            let len = if *length_width == Definition::ARRAY_LENGTH_WIDTH {
+                if length_range.start() != length_range.end() {
+                    return match varint(declaration, buf)? {
+                        Some(value) => Ok(value),
+                        None => Err(invalid_data(format!(
+                            "Length of `{}` is not determined by the schema",
+                            declaration
+                        ))),
+                    };
+                }
+                *length_range.start()
+            } else {
+                let len = read_width(*length_width, buf)?;
+                if !length_range.contains(&len) {
+                    return Err(invalid_data(format!(
+                        "Length {} of `{}` is out of {}..={} range",
+                        len,
+                        declaration,
+                        length_range.start(),
+                        length_range.end()
+                    )));
+                }
+                check_zero_size(len, elements, schema)?;
+                len
+            };
+            sequence(declaration, len, elements, schema, buf, depth)
+        }
+        Definition::Tuple { elements } => {
+            if let Some(value) =
+                varint_prefixed_sequence(declaration, elements, schema, buf, depth)?
+            {
+                return Ok(value);
+            }
+            let mut values = Vec::with_capacity(elements.len());
+            for element in elements {
+                values.push(deserialize_value_impl(element, schema, buf, depth)?);
+            }
+            Ok(Value::Tuple(values))
+        }
+        Definition::Enum {
+            tag_width,
+            variants,
+        } => {
+            if *tag_width == 0 {
+                return Err(invalid_data(format!(
+                    "Variant of untagged `{}` is not determined by the schema",
+                    declaration
+                )));
+            }
+            let tag = read_width(*tag_width, buf)?;
+            let mask = u64::MAX >> (64 - 8 * u32::from(*tag_width));
+            let (_, name, variant) = variants
+                .iter()
+                .find(|(discriminant, _, _)| (*discriminant as u64) & mask == tag)
+                .ok_or_else(|| {
+                    invalid_data(format!(
+                        "Unexpected variant tag of `{}`: {}",
+                        declaration, tag
+                    ))
+                })?;
+            let value = deserialize_value_impl(variant, schema, buf, depth)?;
+            Ok(Value::Enum(name.clone(), Box::new(value)))
+        }
+        Definition::Struct { fields } => match fields {
+            Fields::NamedFields(fields) => {
+                let mut values = Vec::with_capacity(fields.len());
+                for (name, field) in fields {
+                    let value = deserialize_value_impl(field, schema, buf, depth)?;
+                    values.push((name.clone(), value));
+                }
+                Ok(Value::Struct(values))
+            }
+            Fields::UnnamedFields(fields) => {
+                let mut values = Vec::with_capacity(fields.len());
+                for field in fields {
+                    values.push(deserialize_value_impl(field, schema, buf, depth)?);
+                }
+                Ok(Value::Tuple(values))
+            }
+            Fields::Empty => Ok(Value::Unit),
+        },
+    }
+}
+
+/// Reads `width`-byte wide little-endian unsigned integer, i.e. a length or a tag.
+fn read_width(width: u8, buf: &mut &[u8]) -> Result<u64> {
+    let width = usize::from(width);
+    if width > 8 {
+        return Err(invalid_data(format!(
+            "Width {} of a length or a tag is greater than 8",
+            width
+        )));
+    }
+    let bytes = take(width, buf)?;
+    let mut le_bytes = [0u8; 8];
+    le_bytes[..width].copy_from_slice(bytes);
+    Ok(u64::from_le_bytes(le_bytes))
+}
+
+fn take<'a>(len: usize, buf: &mut &'a [u8]) -> Result<&'a [u8]> {
+    if buf.len() < len {
+        return Err(invalid_data(ERROR_UNEXPECTED_LENGTH_OF_INPUT.to_string()));
+    }
+    let (bytes, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(bytes)
+}
+
+fn primitive(declaration: &str, size: u8, buf: &mut &[u8]) -> Result<Value> {
+    macro_rules! read {
+        ($ty: ty) => {
+            <$ty as BorshDeserialize>::deserialize(buf)?
+        };
+    }
+
+    let value = match declaration {
+        "bool" => Value::Bool(read!(bool)),
+        "f32" => Value::Float(read!(f32).into()),
+        "f64" => Value::Float(read!(f64)),
+        "u8" => Value::UInt(read!(u8).into()),
+        "u16" => Value::UInt(read!(u16).into()),
+        "u32" => Value::UInt(read!(u32).into()),
+        "u64" => Value::UInt(read!(u64).into()),
+        "u128" => Value::UInt(read!(u128)),
+        "i8" => Value::Int(read!(i8).into()),
+        "i16" => Value::Int(read!(i16).into()),
+        "i32" => Value::Int(read!(i32).into()),
+        "i64" => Value::Int(read!(i64).into()),
+        "i128" => Value::Int(read!(i128)),
+        "NonZeroU8" => Value::UInt(read!(NonZeroU8).get().into()),
+        "NonZeroU16" => Value::UInt(read!(NonZeroU16).get().into()),
+        "NonZeroU32" => Value::UInt(read!(NonZeroU32).get().into()),
+        "NonZeroU64" => Value::UInt(read!(NonZeroU64).get().into()),
+        "NonZeroU128" => Value::UInt(read!(NonZeroU128).get()),
+        "NonZeroUsize" => Value::UInt(read!(NonZeroUsize).get() as u128),
+        "NonZeroI8" => Value::Int(read!(NonZeroI8).get().into()),
+        "NonZeroI16" => Value::Int(read!(NonZeroI16).get().into()),
+        "NonZeroI32" => Value::Int(read!(NonZeroI32).get().into()),
+        "NonZeroI64" => Value::Int(read!(NonZeroI64).get().into()),
+        "NonZeroI128" => Value::Int(read!(NonZeroI128).get()),
+        "BigEndian<f32>" => Value::Float(read!(BigEndian<f32>).0.into()),
+        "BigEndian<f64>" => Value::Float(read!(BigEndian<f64>).0),
+        "BigEndian<u8>" => Value::UInt(read!(BigEndian<u8>).0.into()),
+        "BigEndian<u16>" => Value::UInt(read!(BigEndian<u16>).0.into()),
+        "BigEndian<u32>" => Value::UInt(read!(BigEndian<u32>).0.into()),
+        "BigEndian<u64>" => Value::UInt(read!(BigEndian<u64>).0.into()),
+        "BigEndian<u128>" => Value::UInt(read!(BigEndian<u128>).0),
+        "BigEndian<i8>" => Value::Int(read!(BigEndian<i8>).0.into()),
+        "BigEndian<i16>" => Value::Int(read!(BigEndian<i16>).0.into()),
+        "BigEndian<i32>" => Value::Int(read!(BigEndian<i32>).0.into()),
+        "BigEndian<i64>" => Value::Int(read!(BigEndian<i64>).0.into()),
+        "BigEndian<i128>" => Value::Int(read!(BigEndian<i128>).0),
+        _ if size == 0 => Value::Unit,
+        _ => Value::Bytes(take(usize::from(size), buf)?.to_vec()),
+    };
+    Ok(value)
+}
+
+/// Varints are sequences of bytes of variable length, which is determined by the bytes themselves.
+fn varint(declaration: &str, buf: &mut &[u8]) -> Result<Option<Value>> {
+    let value = match declaration {
+        "VarU16" => Value::UInt(VarU16::deserialize(buf)?.0.into()),
+        "VarU32" => Value::UInt(VarU32::deserialize(buf)?.0.into()),
+        "VarU64" => Value::UInt(VarU64::deserialize(buf)?.0.into()),
+        "VarU128" => Value::UInt(VarU128::deserialize(buf)?.0),
+        "VarI16" => Value::Int(VarI16::deserialize(buf)?.0.into()),
+        "VarI32" => Value::Int(VarI32::deserialize(buf)?.0.into()),
+        "VarI64" => Value::Int(VarI64::deserialize(buf)?.0.into()),
+        "VarI128" => Value::Int(VarI128::deserialize(buf)?.0),
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+/// Collections with varint length prefix are described as a tuple of `VarU32` length,
+/// followed by the untagged sequence of elements.
+fn varint_prefixed_sequence(
+    declaration: &str,
+    elements: &[String],
+    schema: &BorshSchemaContainer,
+    buf: &mut &[u8],
+    depth: usize,
+) -> Result<Option<Value>> {
+    let untagged = match elements {
+        [length, untagged] if length == "VarU32" => untagged,
+        _ => return Ok(None),
+    };
+    let (length_range, elements) = match schema.get_definition(untagged) {
+        Some(Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range,
+            elements,
+        }) if length_range.start() != length_range.end() => (length_range, elements),
+        _ => return Ok(None),
+    };
+    let len = u64::from(VarU32::deserialize(buf)?.0);
+    if !length_range.contains(&len) {
+        return Err(invalid_data(format!(
+            "Length {} of `{}` is out of {}..={} range",
+            len,
+            declaration,
+            length_range.start(),
+            length_range.end()
+        )));
+    }
+    check_zero_size(len, elements, schema)?;
+    sequence(declaration, len, elements, schema, buf, depth).map(Some)
+}
+
+/// Fails for a non-empty sequence of `elements`, whose values may take no bytes, as its length
+/// is read from input and would make arbitrarily many values out of a few bytes, the same way
+/// collections of zero-sized types are rejected on deserialization.
+fn check_zero_size(len: u64, elements: &str, schema: &BorshSchemaContainer) -> Result<()> {
+    if len > 0 && min_serialized_size_of(elements, schema) == Ok(0) {
+        return Err(invalid_data(ERROR_ZST_FORBIDDEN.to_string()));
+    }
+    Ok(())
+}
+
+/// Whether the sequence of bytes with the given declaration is a string.
+pub(super) fn is_string(declaration: &str) -> bool {
+    declaration == "String"
+        || declaration == "AsciiString"
+        || declaration.starts_with("String<")
+        || declaration.starts_with("BoundedString<")
+}
+
+fn sequence(
+    declaration: &str,
+    len: u64,
+    elements: &str,
+    schema: &BorshSchemaContainer,
+    buf: &mut &[u8],
+    depth: usize,
+) -> Result<Value> {
+    if elements == "u8" || elements == "AsciiChar" {
+        let len = usize::try_from(len)
+            .map_err(|_| invalid_data(ERROR_UNEXPECTED_LENGTH_OF_INPUT.to_string()))?;
+        let bytes = take(len, buf)?.to_vec();
+        if !is_string(declaration) {
+            return Ok(Value::Bytes(bytes));
+        }
+        if elements == "AsciiChar" && !bytes.is_ascii() {
+            return Err(invalid_data(format!(
+                "Value of `{}` is not an ASCII string",
+                declaration
+            )));
+        }
+        return String::from_utf8(bytes)
+            .map(Value::String)
+            .map_err(|err| invalid_data(err.to_string()));
+    }
+
+    // cautious preallocation, as the length may come from untrusted input
+    let mut values = Vec::with_capacity(core::cmp::min(len, 4096) as usize);
+    for _ in 0..len {
+        values.push(deserialize_value_impl(elements, schema, buf, depth)?);
+    }
+    Ok(Value::Seq(values))
+}
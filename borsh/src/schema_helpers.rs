@@ -1,7 +1,9 @@
 use crate::__private::maybestd::vec::Vec;
 use crate::from_slice;
 use crate::io::{Error, ErrorKind, Result};
-use crate::schema::{BorshSchemaContainer, SchemaMaxSerializedSizeError};
+use crate::schema::{
+    BorshSchemaContainer, SchemaMaxSerializedSizeError, SchemaMinSerializedSizeError,
+};
 use crate::{BorshDeserialize, BorshSchema, BorshSerialize};
 
 /// Deserialize this instance from a slice of bytes, but assume that at the beginning we have
@@ -29,6 +31,41 @@ pub fn try_to_vec_with_schema<T: BorshSerialize + BorshSchema + ?Sized>(
     Ok(res)
 }
 
+/// Serialize object into a vector of bytes and prefix with the [fingerprint](BorshSchemaContainer::fingerprint)
+/// of its schema, which is much more compact than the schema itself.
+///
+/// # Example
+///
+/// ```
+/// let bytes = borsh::to_vec_with_schema_hash(&(1u8, 2u16)).unwrap();
+/// assert_eq!(bytes.len(), 8 + 3);
+/// assert_eq!(borsh::from_slice_with_schema_hash::<(u8, u16)>(&bytes).unwrap(), (1, 2));
+/// assert!(borsh::from_slice_with_schema_hash::<(u8, i16)>(&bytes).is_err());
+/// ```
+pub fn to_vec_with_schema_hash<T: BorshSerialize + BorshSchema + ?Sized>(
+    value: &T,
+) -> Result<Vec<u8>> {
+    let fingerprint = schema_container_of::<T>().fingerprint();
+    let mut res = crate::to_vec(&fingerprint)?;
+    value.serialize(&mut res)?;
+    Ok(res)
+}
+
+/// Deserialize this instance from a slice of bytes, but assume that at the beginning we have
+/// the [fingerprint](BorshSchemaContainer::fingerprint) of the schema of the type, as written by
+/// [to_vec_with_schema_hash]. We verify that it matches the schema of `T` before deserializing the value.
+pub fn from_slice_with_schema_hash<T: BorshDeserialize + BorshSchema>(v: &[u8]) -> Result<T> {
+    let mut v = v;
+    let fingerprint = u64::deserialize(&mut v)?;
+    if schema_container_of::<T>().fingerprint() != fingerprint {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Borsh schema hash does not match",
+        ));
+    }
+    from_slice(v)
+}
+
 /// generate [BorshSchemaContainer] for type `T`
 ///
 /// this is an alias of [BorshSchemaContainer::for_type]
@@ -51,3 +88,17 @@ pub fn max_serialized_size<T: BorshSchema + ?Sized>(
     let schema = BorshSchemaContainer::for_type::<T>();
     schema.max_serialized_size()
 }
+
+/// Returns the smallest possible size of a serialised object based solely on its type `T`.
+///
+/// this is a shortcut for using [BorshSchemaContainer::min_serialized_size]
+/// # Example
+///
+/// ```
+/// assert_eq!(Ok(4), borsh::min_serialized_size::<String>());
+/// ```
+pub fn min_serialized_size<T: BorshSchema + ?Sized>(
+) -> core::result::Result<usize, SchemaMinSerializedSizeError> {
+    let schema = BorshSchemaContainer::for_type::<T>();
+    schema.min_serialized_size()
+}
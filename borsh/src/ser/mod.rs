@@ -13,6 +13,8 @@ use crate::io::{Error, ErrorKind, Result, Write};
 
 pub(crate) mod helpers;
 
+pub use crate::endian::BigEndian;
+
 const FLOAT_NAN_ERR: &str = "For portability reasons we do not allow to serialize NaNs.";
 
 /// A data-structure that can be serialized into binary format by NBOR.
@@ -62,6 +64,61 @@ pub trait BorshSerialize {
     }
 }
 
+/// A data-structure that serializes values of type `T` into binary format with a custom encoding.
+///
+/// Types implementing it are usually zero-sized markers (e.g. [`crate::length::Sequence`]),
+/// which are used by `#[derive(BorshSerialize)]` for fields annotated with
+/// `#[borsh(length = ...)]`.
+pub trait BorshSerializeAs<T: ?Sized> {
+    fn serialize_as<W: Write>(source: &T, writer: &mut W) -> Result<()>;
+}
+
+/// Additional methods offered on enums which is used by `[derive(BorshSerialize)]`.
+///
+/// Counterpart of [`crate::de::EnumExt`].
+pub trait EnumExt: BorshSerialize {
+    /// Tags of all variants of an enum, in declaration order.
+    ///
+    /// Tag of `#[borsh(other)]` catch-all variant isn't known in advance, so it isn't listed.
+    const TAGS: &'static [u8];
+
+    /// Returns the tag, which is written before the variant's fields.
+    ///
+    /// It's the variant's index, or its discriminant, if enum is annotated
+    /// with `#[borsh(use_discriminant = true)]`.
+    fn variant_tag(&self) -> u8;
+
+    /// Serialises fields of the variant without its tag.
+    ///
+    /// ```
+    /// use borsh::ser::EnumExt as _;
+    ///
+    /// /// derive is only available if borsh is built with `features = ["derive"]`
+    /// # #[cfg(feature = "derive")]
+    /// #[derive(borsh::BorshSerialize)]
+    /// #[borsh(use_discriminant = true, tag_consts)]
+    /// #[repr(u8)]
+    /// enum MyEnum {
+    ///     Zero = 3,
+    ///     Many(Vec<u8>) = 5,
+    /// }
+    ///
+    /// # #[cfg(feature = "derive")]
+    /// {
+    /// assert_eq!(MyEnum::TAGS, &[3, 5]);
+    /// assert_eq!(MyEnum::MANY_TAG, 5);
+    ///
+    /// let value = MyEnum::Many(vec![1]);
+    /// assert_eq!(value.variant_tag(), 5);
+    ///
+    /// let mut body = Vec::new();
+    /// value.serialize_variant_body(&mut body).unwrap();
+    /// assert_eq!(body, vec![1, 0, 0, 0, 1]);
+    /// }
+    /// ```
+    fn serialize_variant_body<W: Write>(&self, writer: &mut W) -> Result<()>;
+}
+
 impl BorshSerialize for u8 {
     #[inline]
     fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
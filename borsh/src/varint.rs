@@ -0,0 +1,497 @@
+//! Variable-length integers, encoded with [LEB128](https://en.wikipedia.org/wiki/LEB128).
+//!
+//! Unsigned types ([`VarU16`], [`VarU32`], [`VarU64`], [`VarU128`]) use unsigned LEB128,
+//! signed types ([`VarI16`], [`VarI32`], [`VarI64`], [`VarI128`]) use signed LEB128.
+//! Small values take fewer bytes, e.g. `VarU64(127)` is encoded as a single byte.
+//!
+//! Deserialization accepts only the shortest possible encoding of a value, so that
+//! every value has a single valid encoding, as is the case for other borsh types.
+//!
+//! Besides being usable as field types, these types implement
+//! [`BorshSerializeAs`]/[`BorshDeserializeAs`] for respective primitive integers,
+//! which is what `#[borsh(varint)]` field attribute expands to.
+//! [`VarU32`] also implements [`LengthWidth`], so it may be used as a length prefix
+//! of collections from [`crate::length`] module.
+//!
+//! ```
+//! use borsh::varint::{VarI64, VarU32};
+//!
+//! assert_eq!(borsh::to_vec(&VarU32(300)).unwrap(), vec![0xac, 0x02]);
+//! assert_eq!(borsh::to_vec(&VarI64(-2)).unwrap(), vec![0x7e]);
+//!
+//! // non-minimal encoding of `VarU32(1)`
+//! assert!(borsh::from_slice::<VarU32>(&[0x81, 0x00]).is_err());
+//! ```
+use core::convert::TryFrom;
+
+use crate::de::{BorshDeserializeAs, ContextFree};
+use crate::io::{Error, ErrorKind, Read, Result, Write};
+use crate::length::LengthWidth;
+use crate::ser::BorshSerializeAs;
+use crate::{BorshDeserialize, BorshSerialize};
+
+const ERROR_VARINT_OVERFLOW: &str = "Overflow on deserialization of variable-length integer";
+const ERROR_VARINT_NON_MINIMAL: &str = "Non-minimal encoding of variable-length integer";
+const ERROR_LENGTH_OVERFLOW: &str = "Collection length does not fit into its length prefix";
+
+/// Maximal number of bytes in encoding of an integer with `bits` bits.
+const fn max_encoded_len(bits: u32) -> usize {
+    ((bits + 6) / 7) as usize
+}
+
+macro_rules! impl_unsigned_varint {
+    ($(#[$doc: meta])* $name: ident, $type: ident) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub $type);
+
+        impl $name {
+            /// Maximal number of bytes in encoding of a value.
+            pub const MAX_ENCODED_LEN: usize = max_encoded_len($type::BITS);
+        }
+
+        impl From<$type> for $name {
+            fn from(value: $type) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for $type {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl BorshSerialize for $name {
+            #[inline]
+            fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                let mut buf = [0u8; $name::MAX_ENCODED_LEN];
+                let mut len = 0;
+                let mut value = self.0;
+                loop {
+                    let byte = (value & 0x7f) as u8;
+                    value >>= 7;
+                    if value == 0 {
+                        buf[len] = byte;
+                        len += 1;
+                        break;
+                    }
+                    buf[len] = byte | 0x80;
+                    len += 1;
+                }
+                writer.write_all(&buf[..len])
+            }
+        }
+
+        impl BorshDeserialize for $name {
+            #[inline]
+            fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                let mut result: $type = 0;
+                let mut shift = 0;
+                for idx in 0..$name::MAX_ENCODED_LEN {
+                    let byte = u8::deserialize_reader(reader)?;
+                    let low = byte & 0x7f;
+                    let remaining_bits = $type::BITS - shift;
+                    if remaining_bits < 7 && low >> remaining_bits != 0 {
+                        return Err(Error::new(ErrorKind::InvalidData, ERROR_VARINT_OVERFLOW));
+                    }
+                    result |= $type::from(low) << shift;
+                    if byte & 0x80 == 0 {
+                        if byte == 0 && idx > 0 {
+                            return Err(Error::new(
+                                ErrorKind::InvalidData,
+                                ERROR_VARINT_NON_MINIMAL,
+                            ));
+                        }
+                        return Ok(Self(result));
+                    }
+                    shift += 7;
+                }
+                Err(Error::new(ErrorKind::InvalidData, ERROR_VARINT_OVERFLOW))
+            }
+        }
+
+        impl BorshSerializeAs<$type> for $name {
+            #[inline]
+            fn serialize_as<W: Write>(source: &$type, writer: &mut W) -> Result<()> {
+                $name(*source).serialize(writer)
+            }
+        }
+
+        impl BorshDeserializeAs<$type> for $name {
+            #[inline]
+            fn deserialize_as<R: Read>(reader: &mut R) -> Result<$type> {
+                $name::deserialize_reader(reader).map(|value| value.0)
+            }
+        }
+
+        impl ContextFree for $name {}
+    };
+}
+
+macro_rules! impl_signed_varint {
+    ($(#[$doc: meta])* $name: ident, $type: ident) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub $type);
+
+        impl $name {
+            /// Maximal number of bytes in encoding of a value.
+            pub const MAX_ENCODED_LEN: usize = max_encoded_len($type::BITS);
+        }
+
+        impl From<$type> for $name {
+            fn from(value: $type) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for $type {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl BorshSerialize for $name {
+            #[inline]
+            fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                let mut buf = [0u8; $name::MAX_ENCODED_LEN];
+                let mut len = 0;
+                let mut value = self.0;
+                loop {
+                    let byte = (value & 0x7f) as u8;
+                    // arithmetic shift, keeps the sign
+                    value >>= 7;
+                    let sign_bit = byte & 0x40 != 0;
+                    if (value == 0 && !sign_bit) || (value == -1 && sign_bit) {
+                        buf[len] = byte;
+                        len += 1;
+                        break;
+                    }
+                    buf[len] = byte | 0x80;
+                    len += 1;
+                }
+                writer.write_all(&buf[..len])
+            }
+        }
+
+        impl BorshDeserialize for $name {
+            #[inline]
+            fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                let mut result: $type = 0;
+                let mut shift = 0;
+                let mut prev_byte = 0u8;
+                for idx in 0..$name::MAX_ENCODED_LEN {
+                    let byte = u8::deserialize_reader(reader)?;
+                    let low = byte & 0x7f;
+                    let remaining_bits = $type::BITS - shift;
+                    if remaining_bits < 7 {
+                        // bits, which don't fit into the type, have to be the extension of sign bit
+                        let mask = (0x7f << (remaining_bits - 1)) & 0x7f;
+                        if low & mask != 0 && low & mask != mask {
+                            return Err(Error::new(ErrorKind::InvalidData, ERROR_VARINT_OVERFLOW));
+                        }
+                    }
+                    result |= $type::from(low) << shift;
+                    shift += 7;
+                    if byte & 0x80 == 0 {
+                        let redundant_zero = low == 0 && prev_byte & 0x40 == 0;
+                        let redundant_ones = low == 0x7f && prev_byte & 0x40 != 0;
+                        if idx > 0 && (redundant_zero || redundant_ones) {
+                            return Err(Error::new(
+                                ErrorKind::InvalidData,
+                                ERROR_VARINT_NON_MINIMAL,
+                            ));
+                        }
+                        if shift < $type::BITS && byte & 0x40 != 0 {
+                            result |= -1 << shift;
+                        }
+                        return Ok(Self(result));
+                    }
+                    prev_byte = byte;
+                }
+                Err(Error::new(ErrorKind::InvalidData, ERROR_VARINT_OVERFLOW))
+            }
+        }
+
+        impl BorshSerializeAs<$type> for $name {
+            #[inline]
+            fn serialize_as<W: Write>(source: &$type, writer: &mut W) -> Result<()> {
+                $name(*source).serialize(writer)
+            }
+        }
+
+        impl BorshDeserializeAs<$type> for $name {
+            #[inline]
+            fn deserialize_as<R: Read>(reader: &mut R) -> Result<$type> {
+                $name::deserialize_reader(reader).map(|value| value.0)
+            }
+        }
+
+        impl ContextFree for $name {}
+    };
+}
+
+impl_unsigned_varint!(
+    /// `u16`, encoded with unsigned LEB128 in 1 to 3 bytes.
+    VarU16,
+    u16
+);
+impl_unsigned_varint!(
+    /// `u32`, encoded with unsigned LEB128 in 1 to 5 bytes.
+    VarU32,
+    u32
+);
+impl_unsigned_varint!(
+    /// `u64`, encoded with unsigned LEB128 in 1 to 10 bytes.
+    VarU64,
+    u64
+);
+impl_unsigned_varint!(
+    /// `u128`, encoded with unsigned LEB128 in 1 to 19 bytes.
+    VarU128,
+    u128
+);
+impl_signed_varint!(
+    /// `i16`, encoded with signed LEB128 in 1 to 3 bytes.
+    VarI16,
+    i16
+);
+impl_signed_varint!(
+    /// `i32`, encoded with signed LEB128 in 1 to 5 bytes.
+    VarI32,
+    i32
+);
+impl_signed_varint!(
+    /// `i64`, encoded with signed LEB128 in 1 to 10 bytes.
+    VarI64,
+    i64
+);
+impl_signed_varint!(
+    /// `i128`, encoded with signed LEB128 in 1 to 19 bytes.
+    VarI128,
+    i128
+);
+
+/// Length prefix, encoded as [`VarU32`].
+impl LengthWidth for VarU32 {
+    const WIDTH: u8 = 0;
+    const MAX: u64 = u32::MAX as u64;
+    const NAME: &'static str = "varint";
+
+    #[inline]
+    fn serialize_length<W: Write>(len: usize, writer: &mut W) -> Result<()> {
+        let len = u32::try_from(len)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, ERROR_LENGTH_OVERFLOW))?;
+        VarU32(len).serialize(writer)
+    }
+
+    #[inline]
+    fn deserialize_length<R: Read>(reader: &mut R) -> Result<usize> {
+        let len = VarU32::deserialize_reader(reader)?.0;
+        usize::try_from(len).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "Overflow on machine with 32 bit usize",
+            )
+        })
+    }
+
+    #[cfg(feature = "unstable__schema")]
+    fn add_sequence_definition(
+        declaration: crate::schema::Declaration,
+        elements: crate::schema::Declaration,
+        max_len: u64,
+        collector: &mut crate::schema::DefinitionsCollector<'_>,
+    ) {
+        schema::add_sequence_definition(declaration, elements, max_len, collector);
+    }
+}
+
+#[cfg(feature = "unstable__schema")]
+mod schema {
+    use super::{VarI128, VarI16, VarI32, VarI64, VarU128, VarU16, VarU32, VarU64};
+    use crate::__private::maybestd::{collections::BTreeMap, format, string::ToString, vec};
+    use crate::schema::{BorshSchemaAs, Declaration, Definition, DefinitionsCollector};
+    use crate::BorshSchema;
+
+    /// Varint length prefix isn't expressible with `length_width`, so such a sequence
+    /// is described as a tuple of [`VarU32`] length, followed by the untagged elements.
+    pub(super) fn add_sequence_definition(
+        declaration: Declaration,
+        elements: Declaration,
+        max_len: u64,
+        collector: &mut DefinitionsCollector<'_>,
+    ) {
+        let untagged = if max_len == u64::from(u32::MAX) {
+            format!("[{}]", elements)
+        } else {
+            format!("[{}; ..={}]", elements, max_len)
+        };
+        collector.add_definition(
+            declaration,
+            Definition::Tuple {
+                elements: vec![<VarU32 as BorshSchema>::declaration(), untagged.clone()],
+            },
+        );
+        collector.add_definition(
+            untagged,
+            Definition::Sequence {
+                length_width: 0,
+                length_range: 0..=max_len,
+                elements,
+            },
+        );
+        <VarU32 as BorshSchema>::collect_definitions(collector);
+    }
+
+    macro_rules! impl_schema {
+        ($name: ident, $type: ident) => {
+            impl BorshSchema for $name {
+                fn add_definitions_recursively(
+                    definitions: &mut BTreeMap<Declaration, Definition>,
+                ) {
+                    DefinitionsCollector::collect_or_panic(
+                        definitions,
+                        <Self as BorshSchema>::collect_definitions,
+                    );
+                }
+
+                fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+                    let definition = Definition::Sequence {
+                        length_width: 0,
+                        length_range: 1..=($name::MAX_ENCODED_LEN as u64),
+                        elements: u8::declaration(),
+                    };
+                    collector.add_definition(<Self as BorshSchema>::declaration(), definition);
+                    u8::collect_definitions(collector);
+                }
+
+                fn declaration() -> Declaration {
+                    stringify!($name).to_string()
+                }
+            }
+
+            impl BorshSchemaAs<$type> for $name {
+                fn add_definitions_recursively(
+                    definitions: &mut BTreeMap<Declaration, Definition>,
+                ) {
+                    <$name as BorshSchema>::add_definitions_recursively(definitions);
+                }
+
+                fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
+                    <$name as BorshSchema>::collect_definitions(collector);
+                }
+
+                fn declaration() -> Declaration {
+                    <$name as BorshSchema>::declaration()
+                }
+            }
+        };
+    }
+
+    impl_schema!(VarU16, u16);
+    impl_schema!(VarU32, u32);
+    impl_schema!(VarU64, u64);
+    impl_schema!(VarU128, u128);
+    impl_schema!(VarI16, i16);
+    impl_schema!(VarI32, i32);
+    impl_schema!(VarI64, i64);
+    impl_schema!(VarI128, i128);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::__private::maybestd::{string::ToString, vec, vec::Vec};
+    use crate::{from_slice, to_vec};
+
+    #[test]
+    fn test_unsigned_known_encodings() {
+        assert_eq!(to_vec(&VarU32(0)).unwrap(), vec![0x00]);
+        assert_eq!(to_vec(&VarU32(127)).unwrap(), vec![0x7f]);
+        assert_eq!(to_vec(&VarU32(128)).unwrap(), vec![0x80, 0x01]);
+        assert_eq!(to_vec(&VarU64(624485)).unwrap(), vec![0xe5, 0x8e, 0x26]);
+        assert_eq!(
+            to_vec(&VarU32(u32::MAX)).unwrap(),
+            vec![0xff, 0xff, 0xff, 0xff, 0x0f]
+        );
+        assert_eq!(to_vec(&VarU128(u128::MAX)).unwrap().len(), 19);
+    }
+
+    #[test]
+    fn test_signed_known_encodings() {
+        assert_eq!(to_vec(&VarI64(0)).unwrap(), vec![0x00]);
+        assert_eq!(to_vec(&VarI64(-1)).unwrap(), vec![0x7f]);
+        assert_eq!(to_vec(&VarI64(63)).unwrap(), vec![0x3f]);
+        assert_eq!(to_vec(&VarI64(64)).unwrap(), vec![0xc0, 0x00]);
+        assert_eq!(to_vec(&VarI64(-123456)).unwrap(), vec![0xc0, 0xbb, 0x78]);
+        assert_eq!(
+            to_vec(&VarI32(i32::MIN)).unwrap(),
+            vec![0x80, 0x80, 0x80, 0x80, 0x78]
+        );
+    }
+
+    macro_rules! roundtrip {
+        ($name: ident, $type: ident) => {
+            let mut values: Vec<$type> = vec![$type::MIN, $type::MAX, 0, 1, 63, 64, 127, 128];
+            for shift in 0..$type::BITS {
+                values.push((1 as $type) << shift);
+                values.push(((1 as $type) << shift).wrapping_sub(1));
+                values.push(((1 as $type) << shift).wrapping_neg());
+            }
+            for value in values {
+                let data = to_vec(&$name(value)).unwrap();
+                assert!(data.len() <= $name::MAX_ENCODED_LEN);
+                assert_eq!(from_slice::<$name>(&data).unwrap(), $name(value));
+            }
+        };
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        roundtrip!(VarU16, u16);
+        roundtrip!(VarU32, u32);
+        roundtrip!(VarU64, u64);
+        roundtrip!(VarU128, u128);
+        roundtrip!(VarI16, i16);
+        roundtrip!(VarI32, i32);
+        roundtrip!(VarI64, i64);
+        roundtrip!(VarI128, i128);
+    }
+
+    #[test]
+    fn test_reject_non_minimal() {
+        for data in [&[0x80, 0x00][..], &[0xff, 0x80, 0x00], &[0x81, 0x80, 0x00]] {
+            let err = from_slice::<VarU32>(data).unwrap_err();
+            assert_eq!(err.to_string(), ERROR_VARINT_NON_MINIMAL);
+        }
+        // redundant sign extension of 0 and -1
+        for data in [&[0x80, 0x00][..], &[0xff, 0x7f], &[0xc0, 0x7f, 0x7f]] {
+            let err = from_slice::<VarI64>(data).unwrap_err();
+            assert_eq!(err.to_string(), ERROR_VARINT_NON_MINIMAL);
+        }
+    }
+
+    #[test]
+    fn test_reject_overflow() {
+        let data = [0xff, 0xff, 0xff, 0xff, 0x1f];
+        let err = from_slice::<VarU32>(&data).unwrap_err();
+        assert_eq!(err.to_string(), ERROR_VARINT_OVERFLOW);
+
+        let data = [0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+        let err = from_slice::<VarU32>(&data).unwrap_err();
+        assert_eq!(err.to_string(), ERROR_VARINT_OVERFLOW);
+
+        // i32::MAX + 1
+        let data = [0x80, 0x80, 0x80, 0x80, 0x08];
+        let err = from_slice::<VarI32>(&data).unwrap_err();
+        assert_eq!(err.to_string(), ERROR_VARINT_OVERFLOW);
+    }
+
+    #[test]
+    fn test_unexpected_eof() {
+        let err = from_slice::<VarU64>(&[0x80]).unwrap_err();
+        assert_eq!(err.to_string(), "Unexpected length of input");
+    }
+}
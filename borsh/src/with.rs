@@ -0,0 +1,144 @@
+//! Ready-made modules for `#[borsh(with = "...")]` field attribute.
+//!
+//! Each module provides `serialize` and `deserialize` functions and,
+//! if borsh is built with `features = ["unstable__schema"]`, `declaration`
+//! and `add_definitions_recursively` functions, which are looked up by the attribute.
+//!
+//! ```
+//! use borsh::{BorshDeserialize, BorshSerialize};
+//!
+//! /// derive is only available if borsh is built with `features = ["derive"]`
+//! # #[cfg(feature = "derive")]
+//! #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+//! struct Header {
+//!     #[borsh(with = "borsh::with::big_endian::u32")]
+//!     magic: u32,
+//!     #[borsh(with = "borsh::with::fixed_point::e3")]
+//!     price: f64,
+//! }
+//!
+//! # #[cfg(feature = "derive")]
+//! # {
+//! let header = Header { magic: 0x0102_0304, price: 1.5 };
+//! let encoded = borsh::to_vec(&header).unwrap();
+//! assert_eq!(encoded, vec![1, 2, 3, 4, 220, 5, 0, 0, 0, 0, 0, 0]);
+//! assert_eq!(borsh::from_slice::<Header>(&encoded).unwrap(), header);
+//! # }
+//! ```
+
+/// Integers and floats, serialized in big-endian byte order, instead of little-endian one,
+/// e.g. `#[borsh(with = "borsh::with::big_endian::u32")]`.
+///
+/// These are equivalent to `#[borsh(big_endian)]` field attribute, i.e. to
+/// [`BigEndian<T>`](crate::endian::BigEndian) encoding, which also provides the schema:
+/// declaration of the field is `BigEndian<u32>`, so that it's distinct from `u32`.
+pub mod big_endian {
+    macro_rules! impl_big_endian {
+        ($($type: ident),*) => {
+            $(
+                #[doc = concat!("`", stringify!($type), "` in big-endian byte order.")]
+                pub mod $type {
+                    use crate::de::BorshDeserializeAs;
+                    use crate::endian::BigEndian;
+                    use crate::io::{Read, Result, Write};
+                    use crate::ser::BorshSerializeAs;
+
+                    pub fn serialize<W: Write>(value: &$type, writer: &mut W) -> Result<()> {
+                        <BigEndian<$type> as BorshSerializeAs<$type>>::serialize_as(value, writer)
+                    }
+
+                    pub fn deserialize<R: Read>(reader: &mut R) -> Result<$type> {
+                        <BigEndian<$type> as BorshDeserializeAs<$type>>::deserialize_as(reader)
+                    }
+
+                    #[cfg(feature = "unstable__schema")]
+                    pub fn declaration() -> crate::schema::Declaration {
+                        <BigEndian<$type> as crate::BorshSchema>::declaration()
+                    }
+
+                    #[cfg(feature = "unstable__schema")]
+                    pub fn add_definitions_recursively(
+                        definitions: &mut crate::__private::maybestd::collections::BTreeMap<
+                            crate::schema::Declaration,
+                            crate::schema::Definition,
+                        >,
+                    ) {
+                        <BigEndian<$type> as crate::BorshSchema>::add_definitions_recursively(
+                            definitions,
+                        );
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_big_endian!(u16, u32, u64, u128, i16, i32, i64, i128, f32, f64);
+}
+
+/// Decimal fixed-point numbers: `f64` field is serialized as `i64` number of
+/// `10^-N`-ths, e.g. `#[borsh(with = "borsh::with::fixed_point::e6")]` serializes `1.5` as `1_500_000i64`.
+///
+/// Value is rounded half away from zero to the nearest multiple of `10^-N`.
+/// Serialization fails for NaN, infinite values and values out of `i64` range after scaling.
+///
+/// Schema declaration of the field is `i64`.
+pub mod fixed_point {
+    use crate::io::{Error, ErrorKind, Result};
+
+    fn to_fixed(value: f64, scale: f64) -> Result<i64> {
+        let scaled = value * scale;
+        // `i64::MAX as f64` is rounded up to 2^63, which is out of range itself
+        if !scaled.is_finite() || scaled < i64::MIN as f64 || scaled >= i64::MAX as f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Value can't be represented as fixed-point number",
+            ));
+        }
+        let rounded = if scaled < 0.0 {
+            scaled - 0.5
+        } else {
+            scaled + 0.5
+        };
+        // saturating cast truncates towards zero
+        Ok(rounded as i64)
+    }
+
+    macro_rules! impl_fixed_point {
+        ($($module: ident => $exp: literal, $scale: literal);*) => {
+            $(
+                #[doc = concat!("`f64`, serialized as `i64` number of `10^-", stringify!($exp), "`-ths.")]
+                pub mod $module {
+                    use crate::io::{Read, Result, Write};
+                    use crate::{BorshDeserialize, BorshSerialize};
+
+                    const SCALE: f64 = $scale;
+
+                    pub fn serialize<W: Write>(value: &f64, writer: &mut W) -> Result<()> {
+                        super::to_fixed(*value, SCALE)?.serialize(writer)
+                    }
+
+                    pub fn deserialize<R: Read>(reader: &mut R) -> Result<f64> {
+                        Ok(i64::deserialize_reader(reader)? as f64 / SCALE)
+                    }
+
+                    #[cfg(feature = "unstable__schema")]
+                    pub fn declaration() -> crate::schema::Declaration {
+                        <i64 as crate::BorshSchema>::declaration()
+                    }
+
+                    #[cfg(feature = "unstable__schema")]
+                    pub fn add_definitions_recursively(
+                        definitions: &mut crate::__private::maybestd::collections::BTreeMap<
+                            crate::schema::Declaration,
+                            crate::schema::Definition,
+                        >,
+                    ) {
+                        <i64 as crate::BorshSchema>::add_definitions_recursively(definitions);
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_fixed_point!(e2 => 2, 1e2; e3 => 3, 1e3; e6 => 6, 1e6; e9 => 9, 1e9);
+}
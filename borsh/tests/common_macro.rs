@@ -110,6 +110,7 @@ macro_rules! map_wrong_order_test [
     ]
 ];
 
+/// Also checks that the definitions round-trip through the textual IDL of `BorshSchemaContainer`.
 #[allow(unused)]
 macro_rules! schema_map(
     () => { BTreeMap::new() };
@@ -119,11 +120,31 @@ macro_rules! schema_map(
             $(
                 m.insert($key.to_string(), $value);
             )+
+            crate::common_macro::assert_idl_roundtrip(&m);
             m
         }
      };
 );
 
+#[allow(unused)]
+#[cfg(feature = "unstable__schema")]
+#[track_caller]
+pub fn assert_idl_roundtrip(
+    definitions: &schema_imports::BTreeMap<schema_imports::Declaration, schema_imports::Definition>,
+) {
+    use schema_imports::*;
+
+    let root = definitions.keys().next().cloned().unwrap_or_default();
+    let container = BorshSchemaContainer::new(root, definitions.clone());
+    let idl = container.to_string();
+    assert_eq!(
+        idl.parse::<BorshSchemaContainer>(),
+        Ok(container),
+        "{}",
+        idl
+    );
+}
+
 #[allow(unused)]
 #[cfg(feature = "unstable__schema")]
 pub mod schema_imports {
@@ -138,8 +159,9 @@ pub mod schema_imports {
     };
 
     pub use borsh::schema::{
-        add_definition, BorshSchemaContainer, Declaration, Definition, Fields,
-        SchemaContainerValidateError, SchemaMaxSerializedSizeError,
+        add_definition, BorshSchemaContainer, Declaration, Definition, DefinitionsCollector,
+        Fields, SchemaContainerValidateError, SchemaMaxSerializedSizeError,
+        SchemaMinSerializedSizeError,
     };
     pub use borsh::{schema_container_of, BorshSchema};
 }
@@ -0,0 +1,107 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+struct Header {
+    #[borsh(big_endian)]
+    magic: u32,
+    #[borsh(big_endian, length = u8)]
+    offsets: Vec<i16>,
+    #[borsh(big_endian)]
+    scale: Option<f64>,
+    flags: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+#[borsh(big_endian)]
+struct Packet {
+    id: u64,
+    name: String,
+    payload: Vec<u8>,
+    #[borsh(varint)]
+    seq: u32,
+    // no multi-byte numbers, so byte order doesn't matter
+    checksum: [u8; 2],
+    flags: (bool, Option<i8>),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+#[borsh(big_endian)]
+enum Command {
+    Stop,
+    Move { x: i32, y: i32 },
+    Speed(f32),
+}
+
+#[test]
+fn test_big_endian_fields() {
+    let header = Header {
+        magic: 0x0102_0304,
+        offsets: vec![1, -2],
+        scale: Some(1.0),
+        flags: 0x0506,
+    };
+    let encoded = to_vec(&header).unwrap();
+    let expected = [
+        vec![1, 2, 3, 4],
+        vec![2, 0x00, 0x01, 0xff, 0xfe],
+        vec![1, 0x3f, 0xf0, 0, 0, 0, 0, 0, 0],
+        // fields without the attribute stay little-endian
+        vec![0x06, 0x05],
+    ]
+    .concat();
+    assert_eq!(encoded, expected);
+    assert_eq!(from_slice::<Header>(&encoded).unwrap(), header);
+}
+
+#[test]
+fn test_big_endian_container() {
+    let packet = Packet {
+        id: 1,
+        name: "ab".to_string(),
+        payload: vec![7, 8],
+        seq: 300,
+        checksum: [9, 10],
+        flags: (true, Some(-1)),
+    };
+    let encoded = to_vec(&packet).unwrap();
+    let expected = [
+        vec![0, 0, 0, 0, 0, 0, 0, 1],
+        // length prefixes stay little-endian
+        vec![2, 0, 0, 0, b'a', b'b'],
+        vec![2, 0, 0, 0, 7, 8],
+        vec![0xac, 0x02],
+        vec![9, 10],
+        vec![1, 1, 0xff],
+    ]
+    .concat();
+    assert_eq!(encoded, expected);
+    assert_eq!(from_slice::<Packet>(&encoded).unwrap(), packet);
+}
+
+#[test]
+fn test_big_endian_enum() {
+    let command = Command::Move { x: 1, y: -1 };
+    let encoded = to_vec(&command).unwrap();
+    assert_eq!(encoded, vec![1, 0, 0, 0, 1, 0xff, 0xff, 0xff, 0xff]);
+    assert_eq!(from_slice::<Command>(&encoded).unwrap(), command);
+
+    let command = Command::Speed(-0.5);
+    let encoded = to_vec(&command).unwrap();
+    assert_eq!(encoded, vec![2, 0xbf, 0, 0, 0]);
+    assert_eq!(from_slice::<Command>(&encoded).unwrap(), command);
+}
+
+#[test]
+fn test_big_endian_nan() {
+    let err = to_vec(&Command::Speed(f32::NAN)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "For portability reasons we do not allow to serialize NaNs."
+    );
+    assert!(from_slice::<Command>(&[2, 0x7f, 0xc0, 0, 0]).is_err());
+}
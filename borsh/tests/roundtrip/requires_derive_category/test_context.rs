@@ -0,0 +1,319 @@
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use borsh::de::{BorshDeserializeWith, ContextFree};
+use borsh::io::{Error, ErrorKind, Read, Result};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+#[derive(Default)]
+struct Interner {
+    names: Vec<String>,
+}
+
+impl Interner {
+    fn intern(&mut self, name: String) -> usize {
+        match self.names.iter().position(|interned| *interned == name) {
+            Some(index) => index,
+            None => {
+                self.names.push(name);
+                self.names.len() - 1
+            }
+        }
+    }
+}
+
+/// index of a name in [Interner], serialized as the name itself
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct Symbol(usize);
+
+impl BorshDeserializeWith<Interner> for Symbol {
+    fn deserialize_with_context<R: Read>(reader: &mut R, ctx: &mut Interner) -> Result<Self> {
+        let name = String::deserialize_reader(reader)?;
+        Ok(Symbol(ctx.intern(name)))
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+struct Header {
+    version: u8,
+    length: u32,
+}
+
+#[derive(BorshDeserialize, PartialEq, Debug)]
+#[borsh(context = Interner, init = validate)]
+struct Record {
+    header: Header,
+    name: Symbol,
+    aliases: Vec<Symbol>,
+    parent: Option<Symbol>,
+    weights: BTreeMap<Symbol, u32>,
+    pair: (Symbol, u8),
+    #[borsh(skip)]
+    valid: bool,
+}
+
+impl Record {
+    fn validate(&mut self) {
+        self.valid = self.header.version != 0;
+    }
+}
+
+#[derive(BorshDeserialize, PartialEq, Debug)]
+#[borsh(context = Interner)]
+enum Event {
+    Reset,
+    Rename { from: Symbol, to: Symbol },
+    Tagged(u64, Vec<Symbol>),
+}
+
+#[derive(BorshDeserialize, PartialEq, Debug)]
+#[borsh(context = Interner)]
+struct Batch<T> {
+    items: Vec<T>,
+}
+
+#[derive(BorshDeserialize, PartialEq, Debug)]
+#[borsh(context = Interner)]
+enum Flag {
+    On,
+    Off,
+}
+
+/// type with a hand-written `BorshDeserialize` implementation
+#[derive(PartialEq, Debug)]
+struct Celsius(i16);
+
+impl BorshDeserialize for Celsius {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        Ok(Celsius(i16::deserialize_reader(reader)?))
+    }
+}
+
+impl ContextFree for Celsius {}
+
+#[derive(BorshDeserialize, PartialEq, Debug)]
+#[borsh(context = Interner)]
+struct Reading {
+    sensors: [Symbol; 2],
+    header: Box<Header>,
+    temperature: Celsius,
+}
+
+/// protocol version, which selects between two encodings of the same value
+struct Version(u8);
+
+#[derive(PartialEq, Debug)]
+struct Amount(u64);
+
+impl BorshDeserializeWith<Version> for Amount {
+    fn deserialize_with_context<R: Read>(reader: &mut R, ctx: &mut Version) -> Result<Self> {
+        match ctx.0 {
+            1 => Ok(Amount(u32::deserialize_reader(reader)?.into())),
+            2 => Ok(Amount(u64::deserialize_reader(reader)?)),
+            version => Err(Error::new(
+                ErrorKind::InvalidData,
+                alloc::format!("unsupported version {}", version),
+            )),
+        }
+    }
+}
+
+#[derive(BorshDeserialize, PartialEq, Debug)]
+#[borsh(context = Version)]
+struct Transfer {
+    amounts: Vec<Amount>,
+    fee: Option<Amount>,
+}
+
+#[test]
+fn test_context_struct() {
+    let mut weights = BTreeMap::new();
+    weights.insert("b".to_string(), 2u32);
+    weights.insert("c".to_string(), 3u32);
+    let data = to_vec(&(
+        Header {
+            version: 1,
+            length: 9,
+        },
+        "a",
+        vec!["b", "a"],
+        Some("c"),
+        weights,
+        ("b", 7u8),
+    ))
+    .unwrap();
+
+    let mut interner = Interner::default();
+    let record = Record::try_from_slice_with_context(&data, &mut interner).unwrap();
+    let mut expected_weights = BTreeMap::new();
+    expected_weights.insert(Symbol(1), 2);
+    expected_weights.insert(Symbol(2), 3);
+    assert_eq!(
+        record,
+        Record {
+            header: Header {
+                version: 1,
+                length: 9,
+            },
+            name: Symbol(0),
+            aliases: vec![Symbol(1), Symbol(0)],
+            parent: Some(Symbol(2)),
+            weights: expected_weights,
+            pair: (Symbol(1), 7),
+            valid: true,
+        }
+    );
+    assert_eq!(interner.names, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_context_enum() {
+    let mut interner = Interner::default();
+    let data = to_vec(&(1u8, "x", "y")).unwrap();
+    let event = Event::try_from_slice_with_context(&data, &mut interner).unwrap();
+    assert_eq!(
+        event,
+        Event::Rename {
+            from: Symbol(0),
+            to: Symbol(1),
+        }
+    );
+
+    let data = to_vec(&(2u8, 5u64, vec!["y", "z"])).unwrap();
+    let event = Event::try_from_slice_with_context(&data, &mut interner).unwrap();
+    assert_eq!(event, Event::Tagged(5, vec![Symbol(1), Symbol(2)]));
+
+    let event = Event::try_from_slice_with_context(&[0], &mut interner).unwrap();
+    assert_eq!(event, Event::Reset);
+    let flag = Flag::try_from_slice_with_context(&[1], &mut interner).unwrap();
+    assert_eq!(flag, Flag::Off);
+    assert_eq!(interner.names, vec!["x", "y", "z"]);
+
+    let err = Event::try_from_slice_with_context(&[3], &mut interner).unwrap_err();
+    assert_eq!(err.to_string(), "Unexpected variant tag: 3");
+}
+
+#[test]
+fn test_context_generic() {
+    let mut interner = Interner::default();
+    let data = to_vec(&vec!["p", "q", "p"]).unwrap();
+    let batch = Batch::<Symbol>::try_from_slice_with_context(&data, &mut interner).unwrap();
+    assert_eq!(batch.items, vec![Symbol(0), Symbol(1), Symbol(0)]);
+
+    let data = to_vec(&vec![3u16, 4]).unwrap();
+    let batch = Batch::<u16>::try_from_slice_with_context(&data, &mut interner).unwrap();
+    assert_eq!(batch.items, vec![3, 4]);
+}
+
+#[test]
+fn test_context_array_box_and_manual_impl() {
+    let mut interner = Interner::default();
+    let data = to_vec(&(
+        ["t", "t"],
+        Header {
+            version: 1,
+            length: 2,
+        },
+        -5i16,
+    ))
+    .unwrap();
+    let reading = Reading::try_from_slice_with_context(&data, &mut interner).unwrap();
+    assert_eq!(
+        reading,
+        Reading {
+            sensors: [Symbol(0), Symbol(0)],
+            header: Box::new(Header {
+                version: 1,
+                length: 2,
+            }),
+            temperature: Celsius(-5),
+        }
+    );
+    assert_eq!(interner.names, vec!["t"]);
+
+    let err = Reading::try_from_slice_with_context(&data[..5], &mut interner).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_context_version() {
+    let data = to_vec(&(vec![1u32, 2], Some(3u32))).unwrap();
+    let transfer = Transfer::try_from_slice_with_context(&data, &mut Version(1)).unwrap();
+    assert_eq!(
+        transfer,
+        Transfer {
+            amounts: vec![Amount(1), Amount(2)],
+            fee: Some(Amount(3)),
+        }
+    );
+
+    let data = to_vec(&(vec![u64::MAX], None::<u64>)).unwrap();
+    let transfer = Transfer::try_from_slice_with_context(&data, &mut Version(2)).unwrap();
+    assert_eq!(
+        transfer,
+        Transfer {
+            amounts: vec![Amount(u64::MAX)],
+            fee: None,
+        }
+    );
+
+    let err = Transfer::try_from_slice_with_context(&data, &mut Version(3)).unwrap_err();
+    assert_eq!(err.to_string(), "unsupported version 3");
+}
+
+#[test]
+fn test_context_free() {
+    let header = Header {
+        version: 2,
+        length: 5,
+    };
+    let data = to_vec(&header).unwrap();
+    assert_eq!(
+        Header::try_from_slice_with_context(&data, &mut ()).unwrap(),
+        from_slice::<Header>(&data).unwrap()
+    );
+    let mut interner = Interner::default();
+    assert_eq!(
+        Header::try_from_slice_with_context(&data, &mut interner).unwrap(),
+        header
+    );
+}
+
+#[test]
+fn test_context_errors() {
+    let mut interner = Interner::default();
+    let data = to_vec(&vec!["a"]).unwrap();
+    let err = Batch::<Symbol>::try_from_slice_with_context(&data[..data.len() - 1], &mut interner)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    let mut data = to_vec(&vec!["a"]).unwrap();
+    data.push(0);
+    let err = Batch::<Symbol>::try_from_slice_with_context(&data, &mut interner).unwrap_err();
+    assert_eq!(err.to_string(), "Not all bytes read");
+
+    let err = Option::<Symbol>::try_from_slice_with_context(&[2], &mut interner).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid Option representation: 2. The first byte must be 0 or 1"
+    );
+}
+
+#[test]
+fn test_context_bytes() {
+    let mut interner = Interner::default();
+    let bytes = (0..=255u8).cycle().take(3000).collect::<Vec<_>>();
+    let data = to_vec(&bytes).unwrap();
+    assert_eq!(
+        Vec::<u8>::try_from_slice_with_context(&data, &mut interner).unwrap(),
+        bytes
+    );
+
+    let err = Vec::<u8>::try_from_slice_with_context(&[0xff, 0xff, 0xff, 0xff, 1], &mut interner)
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unexpected length of input");
+}
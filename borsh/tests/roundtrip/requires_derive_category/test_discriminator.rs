@@ -0,0 +1,71 @@
+use alloc::{string::ToString, vec, vec::Vec};
+use borsh::discriminator::{Discriminator, ERROR_DISCRIMINATOR_MISMATCH};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+#[borsh(discriminator)]
+struct Counter {
+    value: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+#[borsh(discriminator = [0xca, 0xfe])]
+struct Wrapper<T>(T);
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+#[borsh(discriminator = [1, 2, 3, 4])]
+enum Instruction {
+    Increment,
+    Set { value: u64 },
+}
+
+#[test]
+fn test_default_discriminator() {
+    // `sha256("account:Counter")[..8]`
+    assert_eq!(
+        Counter::DISCRIMINATOR,
+        &[255, 176, 4, 245, 188, 253, 124, 25]
+    );
+    let counter = Counter { value: 1 };
+    let encoded = to_vec(&counter).unwrap();
+    assert_eq!(
+        encoded,
+        [Counter::DISCRIMINATOR, &[1, 0, 0, 0, 0, 0, 0, 0]].concat()
+    );
+    assert_eq!(from_slice::<Counter>(&encoded).unwrap(), counter);
+}
+
+#[test]
+fn test_custom_discriminator() {
+    assert_eq!(<Wrapper<Vec<u8>>>::DISCRIMINATOR, &[0xca, 0xfe]);
+    let wrapper = Wrapper(vec![7u8]);
+    let encoded = to_vec(&wrapper).unwrap();
+    assert_eq!(encoded, vec![0xca, 0xfe, 1, 0, 0, 0, 7]);
+    assert_eq!(from_slice::<Wrapper<Vec<u8>>>(&encoded).unwrap(), wrapper);
+}
+
+#[test]
+fn test_enum_discriminator() {
+    let instruction = Instruction::Set { value: 2 };
+    let encoded = to_vec(&instruction).unwrap();
+    // discriminator is written before the variant tag
+    assert_eq!(encoded, vec![1, 2, 3, 4, 1, 2, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(from_slice::<Instruction>(&encoded).unwrap(), instruction);
+    assert_eq!(
+        from_slice::<Instruction>(&[1, 2, 3, 4, 0]).unwrap(),
+        Instruction::Increment
+    );
+}
+
+#[test]
+fn test_discriminator_mismatch() {
+    let err = from_slice::<Wrapper<u8>>(&[0xca, 0xff, 1]).unwrap_err();
+    assert_eq!(err.to_string(), ERROR_DISCRIMINATOR_MISMATCH);
+
+    let err = from_slice::<Instruction>(&[4, 3, 2, 1, 0]).unwrap_err();
+    assert_eq!(err.to_string(), ERROR_DISCRIMINATOR_MISMATCH);
+
+    // a value of another type with a discriminator isn't accepted
+    let encoded = to_vec(&Counter { value: 1 }).unwrap();
+    assert!(from_slice::<Wrapper<u64>>(&encoded).is_err());
+}
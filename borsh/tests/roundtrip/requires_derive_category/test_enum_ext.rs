@@ -0,0 +1,108 @@
+use alloc::{string::String, vec, vec::Vec};
+use borsh::ser::EnumExt as _;
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+#[borsh(tag_consts)]
+enum Plain<T> {
+    Empty,
+    Single(T),
+    NamedFields { x: u8, y: String },
+}
+
+#[allow(unused)]
+#[derive(BorshSerialize, PartialEq, Debug)]
+#[borsh(use_discriminant = true, tag_consts)]
+#[repr(u8)]
+enum Discriminated {
+    First = 5,
+    Second(u16),
+    Third = 20,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+enum WithOther {
+    Known(u8),
+    #[borsh(other)]
+    Unknown(u8, Vec<u8>),
+}
+
+#[test]
+fn test_tags() {
+    assert_eq!(Plain::<u8>::TAGS, &[0, 1, 2]);
+    assert_eq!(Plain::<u8>::EMPTY_TAG, 0);
+    assert_eq!(Plain::<u8>::SINGLE_TAG, 1);
+    assert_eq!(Plain::<u8>::NAMED_FIELDS_TAG, 2);
+
+    assert_eq!(Discriminated::TAGS, &[5, 6, 20]);
+    assert_eq!(Discriminated::SECOND_TAG, 6);
+
+    assert_eq!(WithOther::TAGS, &[0]);
+}
+
+/// Without `#[borsh(tag_consts)]` names of the constants stay available to the enum.
+#[allow(unused, non_camel_case_types)]
+#[derive(BorshSerialize)]
+enum OwnConsts {
+    First,
+    Foo_Bar,
+    FooBar,
+}
+
+impl OwnConsts {
+    const FIRST_TAG: &'static str = "own";
+}
+
+#[test]
+fn test_without_tag_consts() {
+    assert_eq!(OwnConsts::FIRST_TAG, "own");
+    assert_eq!(OwnConsts::TAGS, &[0, 1, 2]);
+    assert_eq!(OwnConsts::FooBar.variant_tag(), 2);
+}
+
+#[test]
+fn test_variant_tag() {
+    assert_eq!(Plain::Single(3u32).variant_tag(), Plain::<u32>::SINGLE_TAG);
+    assert_eq!(Discriminated::Third.variant_tag(), 20);
+    assert_eq!(WithOther::Unknown(42, vec![]).variant_tag(), 42);
+}
+
+#[test]
+fn test_tag_and_body_compose_to_serialize() {
+    let values = [
+        Plain::Empty,
+        Plain::Single(vec![1u8, 2]),
+        Plain::NamedFields {
+            x: 7,
+            y: "y".into(),
+        },
+    ];
+    for value in values {
+        let mut bytes = vec![value.variant_tag()];
+        value.serialize_variant_body(&mut bytes).unwrap();
+        assert_eq!(bytes, to_vec(&value).unwrap());
+
+        // tag and body are exactly what `de::EnumExt::deserialize_variant` expects
+        let mut body = &bytes[1..];
+        let decoded =
+            <Plain<Vec<u8>> as borsh::de::EnumExt>::deserialize_variant(&mut body, bytes[0])
+                .unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    let mut body = vec![];
+    Discriminated::Second(0x0102)
+        .serialize_variant_body(&mut body)
+        .unwrap();
+    assert_eq!(body, vec![2, 1]);
+
+    let mut body = vec![];
+    WithOther::Unknown(9, vec![1, 2, 3])
+        .serialize_variant_body(&mut body)
+        .unwrap();
+    assert_eq!(body, vec![3, 0, 0, 0, 1, 2, 3]);
+    assert_eq!(
+        from_slice::<WithOther>(&to_vec(&WithOther::Known(4)).unwrap()).unwrap(),
+        WithOther::Known(4)
+    );
+}
@@ -0,0 +1,144 @@
+use alloc::{string::String, vec, vec::Vec};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+enum MessageV1 {
+    Ping,
+    Data(Vec<u8>),
+    #[borsh(other)]
+    Unknown(u8, Vec<u8>),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+enum MessageV2 {
+    Ping,
+    Data(Vec<u8>),
+    Text { text: String },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+struct Envelope {
+    messages: Vec<MessageV1>,
+    last: Option<MessageV1>,
+    checksum: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+enum Status {
+    Active,
+    Inactive,
+    #[borsh(other)]
+    Unknown(u8),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+#[borsh(use_discriminant = true)]
+#[repr(u8)]
+enum Discriminated {
+    A = 10,
+    B = 20,
+    #[borsh(other)]
+    Unknown(u8),
+}
+
+#[test]
+fn test_known_variants() {
+    for message in [MessageV1::Ping, MessageV1::Data(vec![1, 2, 3])] {
+        let encoded = to_vec(&message).unwrap();
+        assert_eq!(from_slice::<MessageV1>(&encoded).unwrap(), message);
+    }
+    assert_eq!(
+        to_vec(&MessageV1::Data(vec![1])).unwrap(),
+        to_vec(&MessageV2::Data(vec![1])).unwrap()
+    );
+}
+
+#[test]
+fn test_unknown_variant_roundtrip() {
+    let newer = MessageV2::Text {
+        text: "hello".into(),
+    };
+    let encoded = to_vec(&newer).unwrap();
+
+    let older = from_slice::<MessageV1>(&encoded).unwrap();
+    assert_eq!(older, MessageV1::Unknown(2, b"hello".to_vec()));
+
+    let reencoded = to_vec(&older).unwrap();
+    assert_eq!(reencoded, encoded);
+    assert_eq!(from_slice::<MessageV2>(&reencoded).unwrap(), newer);
+}
+
+#[test]
+fn test_unknown_variant_is_length_prefixed() {
+    assert_eq!(
+        to_vec(&MessageV1::Unknown(5, vec![1, 2])).unwrap(),
+        vec![5, 2, 0, 0, 0, 1, 2]
+    );
+    assert_eq!(
+        from_slice::<MessageV1>(&[5, 0, 0, 0, 0]).unwrap(),
+        MessageV1::Unknown(5, vec![])
+    );
+    assert!(from_slice::<MessageV1>(&[5]).is_err());
+    assert!(from_slice::<MessageV1>(&[5, 3, 0, 0, 0, 1, 2]).is_err());
+}
+
+#[test]
+fn test_unknown_variant_in_struct() {
+    let text = MessageV2::Text { text: "hi".into() };
+    let mut encoded = vec![2, 0, 0, 0];
+    encoded.extend(to_vec(&text).unwrap());
+    encoded.extend(to_vec(&MessageV2::Ping).unwrap());
+    encoded.extend(to_vec(&Some(text)).unwrap());
+    encoded.extend(to_vec(&7u32).unwrap());
+
+    let envelope = from_slice::<Envelope>(&encoded).unwrap();
+    assert_eq!(
+        envelope,
+        Envelope {
+            messages: vec![MessageV1::Unknown(2, b"hi".to_vec()), MessageV1::Ping],
+            last: Some(MessageV1::Unknown(2, b"hi".to_vec())),
+            checksum: 7,
+        }
+    );
+    assert_eq!(to_vec(&envelope).unwrap(), encoded);
+}
+
+#[test]
+fn test_unknown_variant_with_known_tag() {
+    for tag in [0, 1] {
+        let err = to_vec(&MessageV1::Unknown(tag, vec![])).unwrap_err();
+        assert_eq!(err.kind(), borsh::io::ErrorKind::InvalidData);
+    }
+    assert!(to_vec(&Status::Unknown(1)).is_err());
+    assert!(to_vec(&Discriminated::Unknown(20)).is_err());
+
+    // tags of unknown variants, which can only come from input, round-trip
+    let unknown = from_slice::<Discriminated>(&[1]).unwrap();
+    assert_eq!(to_vec(&unknown).unwrap(), vec![1]);
+    assert_eq!(
+        from_slice::<Discriminated>(&to_vec(&Discriminated::B).unwrap()).unwrap(),
+        Discriminated::B
+    );
+}
+
+#[test]
+fn test_tag_only() {
+    assert_eq!(from_slice::<Status>(&[1]).unwrap(), Status::Inactive);
+    assert_eq!(from_slice::<Status>(&[42]).unwrap(), Status::Unknown(42));
+    assert_eq!(to_vec(&Status::Unknown(42)).unwrap(), vec![42]);
+    // tag-only variant doesn't consume anything after the tag
+    assert!(from_slice::<Status>(&[42, 0]).is_err());
+}
+
+#[test]
+fn test_with_discriminants() {
+    assert_eq!(to_vec(&Discriminated::B).unwrap(), vec![20]);
+    assert_eq!(
+        from_slice::<Discriminated>(&[10]).unwrap(),
+        Discriminated::A
+    );
+    assert_eq!(
+        from_slice::<Discriminated>(&[1]).unwrap(),
+        Discriminated::Unknown(1)
+    );
+}
@@ -0,0 +1,130 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug)]
+struct A {
+    #[borsh(length = u8)]
+    bytes: Vec<u8>,
+    #[borsh(length = u16)]
+    name: String,
+    #[borsh(length = u64)]
+    set: BTreeSet<u32>,
+    plain: Vec<u8>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug)]
+struct Nested {
+    #[borsh(length = u8)]
+    matrix: Vec<Vec<u16>>,
+    #[borsh(length = u8)]
+    map: BTreeMap<String, Vec<u8>>,
+    #[borsh(length = u8)]
+    maybe: Option<Vec<u8>>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug)]
+enum E {
+    Unit,
+    Tuple(#[borsh(length = u8)] Vec<u32>, u8),
+    Named {
+        #[borsh(length = u16)]
+        names: Vec<String>,
+    },
+}
+
+#[test]
+fn test_struct_length_prefixes() {
+    let a = A {
+        bytes: vec![1, 2, 3],
+        name: "ab".to_string(),
+        set: vec![7].into_iter().collect(),
+        plain: vec![9],
+    };
+    let data = to_vec(&a).unwrap();
+    assert_eq!(
+        data,
+        vec![
+            3, 1, 2, 3, // bytes
+            2, 0, b'a', b'b', // name
+            1, 0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 0, // set
+            1, 0, 0, 0, 9, // plain
+        ]
+    );
+    assert_eq!(from_slice::<A>(&data).unwrap(), a);
+}
+
+#[test]
+fn test_nested_length_prefixes() {
+    let mut map = BTreeMap::new();
+    map.insert("k".to_string(), vec![5, 6]);
+    let nested = Nested {
+        matrix: vec![vec![1], vec![]],
+        map,
+        maybe: Some(vec![4]),
+    };
+    let data = to_vec(&nested).unwrap();
+    assert_eq!(
+        data,
+        vec![
+            2, 1, 1, 0, 0, // matrix
+            1, 1, b'k', 2, 5, 6, // map
+            1, 1, 4, // maybe
+        ]
+    );
+    assert_eq!(from_slice::<Nested>(&data).unwrap(), nested);
+}
+
+#[test]
+fn test_enum_length_prefixes() {
+    for e in [
+        E::Unit,
+        E::Tuple(vec![1, 2], 3),
+        E::Named {
+            names: vec!["x".to_string()],
+        },
+    ] {
+        let data = to_vec(&e).unwrap();
+        assert_eq!(from_slice::<E>(&data).unwrap(), e);
+    }
+    let data = to_vec(&E::Named {
+        names: vec!["x".to_string()],
+    })
+    .unwrap();
+    assert_eq!(data, vec![2, 1, 0, 1, 0, b'x']);
+}
+
+#[test]
+fn test_length_overflow() {
+    let a = A {
+        bytes: vec![0; 256],
+        name: String::new(),
+        set: BTreeSet::new(),
+        plain: vec![],
+    };
+    let err = to_vec(&a).unwrap_err();
+    assert_eq!(err.kind(), borsh::io::ErrorKind::InvalidData);
+    assert_eq!(
+        err.to_string(),
+        "Collection length does not fit into its length prefix"
+    );
+
+    let nested = Nested {
+        matrix: vec![vec![0; 256]],
+        map: BTreeMap::new(),
+        maybe: None,
+    };
+    assert!(to_vec(&nested).is_err());
+}
+
+#[test]
+fn test_length_prefix_unexpected_eof() {
+    // declared length 4, but only 2 elements follow
+    let data = vec![4, 1, 2];
+    let err = from_slice::<A>(&data).unwrap_err();
+    assert_eq!(err.to_string(), "Unexpected length of input");
+}
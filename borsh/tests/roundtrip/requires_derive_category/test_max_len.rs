@@ -0,0 +1,71 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+const MAX_NAME_LEN: usize = 4;
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug)]
+struct A {
+    #[borsh(max_len = 3)]
+    items: Vec<Vec<u8>>,
+    #[borsh(max_len = MAX_NAME_LEN)]
+    name: Option<String>,
+    #[borsh(max_len = 1, length = u8)]
+    map: BTreeMap<u8, u8>,
+    #[borsh(max_len = 2, varint)]
+    numbers: Vec<u64>,
+}
+
+fn sample() -> A {
+    let mut map = BTreeMap::new();
+    map.insert(1, 2);
+    A {
+        items: vec![vec![0; 10], vec![]],
+        name: Some("abcd".to_string()),
+        map,
+        numbers: vec![300],
+    }
+}
+
+#[test]
+fn test_max_len_roundtrip() {
+    let a = sample();
+    let data = to_vec(&a).unwrap();
+    let mut expected = vec![2, 0, 0, 0, 10, 0, 0, 0];
+    expected.extend([0; 10]);
+    expected.extend([0, 0, 0, 0]);
+    expected.extend([1, 4, 0, 0, 0, b'a', b'b', b'c', b'd']);
+    expected.extend([1, 1, 2]);
+    expected.extend([1, 0xac, 0x02]);
+    assert_eq!(data, expected);
+    assert_eq!(from_slice::<A>(&data).unwrap(), a);
+}
+
+#[test]
+fn test_max_len_exceeded() {
+    let mut a = sample();
+    a.name = Some("abcde".to_string());
+    let err = to_vec(&a).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Collection length exceeds its maximal length"
+    );
+
+    #[derive(BorshSerialize)]
+    struct Unbounded {
+        items: Vec<Vec<u8>>,
+    }
+    let data = to_vec(&Unbounded {
+        items: vec![vec![]; 4],
+    })
+    .unwrap();
+    let err = from_slice::<A>(&data).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Collection length exceeds its maximal length"
+    );
+}
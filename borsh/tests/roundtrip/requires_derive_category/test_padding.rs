@@ -0,0 +1,133 @@
+use alloc::{string::ToString, vec};
+use borsh::padding::ERROR_RESERVED_NOT_ZERO;
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+const FLAGS_RESERVED: usize = 2;
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+#[borsh(reserved_after = 4)]
+struct Header {
+    version: u8,
+    #[borsh(pad = 3)]
+    length: u32,
+    #[borsh(reserved = FLAGS_RESERVED)]
+    flags: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+#[borsh(pad_after = 2)]
+struct Word(u16);
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+#[borsh(reserved_after = 3)]
+struct Filler;
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+enum Frame {
+    Data(u8, #[borsh(pad = 1)] u16),
+    Ack {
+        #[borsh(reserved = 2, skip)]
+        cached: bool,
+        seq: u8,
+    },
+}
+
+#[test]
+fn test_padding_roundtrip() {
+    let header = Header {
+        version: 1,
+        length: 2,
+        flags: 3,
+    };
+    let encoded = to_vec(&header).unwrap();
+    let expected = [
+        vec![1],
+        vec![0, 0, 0],
+        vec![2, 0, 0, 0],
+        vec![0, 0],
+        vec![3, 0],
+        vec![0, 0, 0, 0],
+    ]
+    .concat();
+    assert_eq!(encoded, expected);
+    assert_eq!(from_slice::<Header>(&encoded).unwrap(), header);
+}
+
+#[test]
+fn test_padding_ignored() {
+    let encoded = [
+        vec![1],
+        vec![0xaa, 0xbb, 0xcc],
+        vec![2, 0, 0, 0],
+        vec![0, 0],
+        vec![3, 0],
+        vec![0, 0, 0, 0],
+    ]
+    .concat();
+    assert_eq!(
+        from_slice::<Header>(&encoded).unwrap(),
+        Header {
+            version: 1,
+            length: 2,
+            flags: 3,
+        }
+    );
+}
+
+#[test]
+fn test_reserved_not_zero() {
+    let encoded = [
+        vec![1],
+        vec![0, 0, 0],
+        vec![2, 0, 0, 0],
+        vec![0, 1],
+        vec![3, 0],
+        vec![0, 0, 0, 0],
+    ]
+    .concat();
+    let err = from_slice::<Header>(&encoded).unwrap_err();
+    assert_eq!(err.to_string(), ERROR_RESERVED_NOT_ZERO);
+
+    // trailing reserved bytes
+    let mut encoded = to_vec(&Header {
+        version: 1,
+        length: 2,
+        flags: 3,
+    })
+    .unwrap();
+    *encoded.last_mut().unwrap() = 1;
+    let err = from_slice::<Header>(&encoded).unwrap_err();
+    assert_eq!(err.to_string(), ERROR_RESERVED_NOT_ZERO);
+}
+
+#[test]
+fn test_trailing_padding() {
+    let encoded = to_vec(&Word(1)).unwrap();
+    assert_eq!(encoded, vec![1, 0, 0, 0]);
+    assert_eq!(from_slice::<Word>(&[1, 0, 0xaa, 0xbb]).unwrap(), Word(1));
+    assert!(from_slice::<Word>(&[1, 0, 0]).is_err());
+
+    let encoded = to_vec(&Filler).unwrap();
+    assert_eq!(encoded, vec![0, 0, 0]);
+    assert_eq!(from_slice::<Filler>(&encoded).unwrap(), Filler);
+    let err = from_slice::<Filler>(&[0, 1, 0]).unwrap_err();
+    assert_eq!(err.to_string(), ERROR_RESERVED_NOT_ZERO);
+}
+
+#[test]
+fn test_padding_enum() {
+    let frame = Frame::Data(1, 2);
+    let encoded = to_vec(&frame).unwrap();
+    assert_eq!(encoded, vec![0, 1, 0, 2, 0]);
+    assert_eq!(from_slice::<Frame>(&encoded).unwrap(), frame);
+
+    // padding before a skipped field is still written
+    let frame = Frame::Ack {
+        cached: false,
+        seq: 7,
+    };
+    let encoded = to_vec(&frame).unwrap();
+    assert_eq!(encoded, vec![1, 0, 0, 7]);
+    assert_eq!(from_slice::<Frame>(&encoded).unwrap(), frame);
+    assert!(from_slice::<Frame>(&[1, 0, 5, 7]).is_err());
+}
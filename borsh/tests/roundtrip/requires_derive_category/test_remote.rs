@@ -0,0 +1,124 @@
+use alloc::{string::String, vec, vec::Vec};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+/// Types, which are assumed to be defined in another crate
+mod other_crate {
+    use alloc::{string::String, vec::Vec};
+
+    #[derive(PartialEq, Debug)]
+    pub struct Config {
+        pub name: String,
+        pub retries: u8,
+        pub cache: Vec<u8>,
+    }
+
+    #[derive(PartialEq, Debug)]
+    pub struct Pair<T>(pub T, pub T);
+
+    #[derive(PartialEq, Debug)]
+    pub enum Shape {
+        Dot,
+        Circle { radius: u32 },
+        Line(i16, i16),
+    }
+}
+
+use other_crate::{Config, Pair, Shape};
+
+#[allow(dead_code)]
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(remote = "other_crate::Config")]
+struct ConfigDef {
+    name: String,
+    retries: u8,
+    #[borsh(skip)]
+    cache: Vec<u8>,
+}
+
+#[allow(dead_code)]
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(remote = "Pair")]
+struct PairDef<T>(T, T);
+
+#[allow(dead_code)]
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(remote = "Shape")]
+enum ShapeDef {
+    Dot,
+    Circle { radius: u32 },
+    Line(i16, i16),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+struct Scene {
+    #[borsh(
+        serialize_with = "ConfigDef::serialize",
+        deserialize_with = "ConfigDef::deserialize"
+    )]
+    config: Config,
+    #[borsh(
+        serialize_with = "PairDef::<u16>::serialize",
+        deserialize_with = "PairDef::<u16>::deserialize"
+    )]
+    offset: Pair<u16>,
+    #[borsh(
+        serialize_with = "ShapeDef::serialize",
+        deserialize_with = "ShapeDef::deserialize"
+    )]
+    shape: Shape,
+}
+
+#[test]
+fn test_remote_struct() {
+    let config = Config {
+        name: "main".into(),
+        retries: 3,
+        cache: vec![1, 2, 3],
+    };
+    let mut encoded = vec![];
+    ConfigDef::serialize(&config, &mut encoded).unwrap();
+    assert_eq!(encoded, to_vec(&(String::from("main"), 3u8)).unwrap());
+
+    let decoded = ConfigDef::deserialize(&mut encoded.as_slice()).unwrap();
+    assert_eq!(
+        decoded,
+        Config {
+            cache: vec![],
+            ..config
+        }
+    );
+}
+
+fn scene(shape: Shape) -> Scene {
+    Scene {
+        config: Config {
+            name: "main".into(),
+            retries: 3,
+            cache: vec![],
+        },
+        offset: Pair(1, 2),
+        shape,
+    }
+}
+
+#[test]
+fn test_remote_fields() {
+    for (shape, shape_encoded) in [
+        (Shape::Dot, vec![0]),
+        (Shape::Circle { radius: 5 }, vec![1, 5, 0, 0, 0]),
+        (Shape::Line(-1, 1), vec![2, 255, 255, 1, 0]),
+    ] {
+        let scene = scene(shape);
+        let encoded = to_vec(&scene).unwrap();
+        assert_eq!(
+            encoded,
+            [to_vec(&("main", 3u8, 1u16, 2u16)).unwrap(), shape_encoded].concat()
+        );
+        assert_eq!(from_slice::<Scene>(&encoded).unwrap(), scene);
+    }
+}
+
+#[test]
+fn test_remote_enum_unknown_tag() {
+    assert!(ShapeDef::deserialize(&mut &[3u8][..]).is_err());
+}
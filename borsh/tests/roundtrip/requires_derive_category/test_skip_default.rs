@@ -0,0 +1,88 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+/// A handle without any meaningful `Default` value
+#[derive(PartialEq, Debug)]
+struct Handle(u32);
+
+fn detached() -> Handle {
+    Handle(u32::MAX)
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+struct A {
+    name: String,
+    #[borsh(skip, default = "detached")]
+    handle: Handle,
+    #[borsh(skip(default = "vec![1, 2, 3]"))]
+    cache: Vec<u8>,
+}
+
+/// `T` isn't required to implement `Default`
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+struct G<T> {
+    x: u8,
+    #[borsh(skip(default = "None"))]
+    y: Option<T>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+enum E {
+    V {
+        x: u8,
+        #[borsh(skip, default = "detached")]
+        handle: Handle,
+    },
+}
+
+#[test]
+fn test_skip_default_struct() {
+    let a = A {
+        name: "a".to_string(),
+        handle: Handle(5),
+        cache: vec![],
+    };
+    let data = to_vec(&a).unwrap();
+    assert_eq!(data, to_vec(&"a".to_string()).unwrap());
+    let decoded = from_slice::<A>(&data).unwrap();
+    assert_eq!(
+        decoded,
+        A {
+            name: "a".to_string(),
+            handle: Handle(u32::MAX),
+            cache: vec![1, 2, 3],
+        }
+    );
+}
+
+#[test]
+fn test_skip_default_generic() {
+    let g = G::<Handle> {
+        x: 7,
+        y: Some(Handle(1)),
+    };
+    let data = to_vec(&g).unwrap();
+    assert_eq!(data, vec![7]);
+    assert_eq!(from_slice::<G<Handle>>(&data).unwrap(), G { x: 7, y: None });
+}
+
+#[test]
+fn test_skip_default_enum() {
+    let e = E::V {
+        x: 1,
+        handle: Handle(0),
+    };
+    let data = to_vec(&e).unwrap();
+    assert_eq!(data, vec![0, 1]);
+    assert_eq!(
+        from_slice::<E>(&data).unwrap(),
+        E::V {
+            x: 1,
+            handle: detached(),
+        }
+    );
+}
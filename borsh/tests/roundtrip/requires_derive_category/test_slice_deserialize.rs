@@ -0,0 +1,144 @@
+use alloc::string::{String, ToString};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+#[borsh(init = validate)]
+struct Header {
+    version: u8,
+    flags: u16,
+    #[borsh(skip)]
+    valid: bool,
+    height: u64,
+    delta: i32,
+    name: String,
+    tail: u32,
+}
+
+impl Header {
+    fn validate(&mut self) {
+        self.valid = self.version != 0;
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+struct Pair(i128, u16);
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+enum Message {
+    Ping,
+    Data { id: u32, header: Header },
+    Pair(Pair, u8),
+}
+
+type Balance = u64;
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+struct Account {
+    balance: Balance,
+    nonce: core::primitive::u32,
+}
+
+mod shadowed {
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    /// not an integer, despite the name
+    #[allow(non_camel_case_types)]
+    #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+    pub struct u32(pub u8);
+
+    #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+    pub struct Counter {
+        pub small: u32,
+        pub flags: u16,
+    }
+}
+
+fn header() -> Header {
+    Header {
+        version: 1,
+        flags: 0x0203,
+        valid: true,
+        height: u64::MAX - 1,
+        delta: -5,
+        name: "abc".to_string(),
+        tail: 7,
+    }
+}
+
+/// both slice-specialized `deserialize` and `deserialize_reader` decode the same value
+fn check_both_paths<T: BorshDeserialize + PartialEq + core::fmt::Debug>(encoded: &[u8], value: &T) {
+    let mut buf = encoded;
+    assert_eq!(&T::deserialize(&mut buf).unwrap(), value);
+    assert!(buf.is_empty());
+    let mut reader = encoded;
+    assert_eq!(&T::deserialize_reader(&mut reader).unwrap(), value);
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn test_slice_deserialize_struct() {
+    let header = header();
+    let encoded = to_vec(&header).unwrap();
+    check_both_paths(&encoded, &header);
+
+    let pair = Pair(-2, 3);
+    check_both_paths(&to_vec(&pair).unwrap(), &pair);
+}
+
+#[test]
+fn test_slice_deserialize_aliases_and_shadowed_names() {
+    let account = Account {
+        balance: 10,
+        nonce: 3,
+    };
+    let encoded = to_vec(&account).unwrap();
+    assert_eq!(encoded.len(), 12);
+    check_both_paths(&encoded, &account);
+
+    let counter = shadowed::Counter {
+        small: shadowed::u32(5),
+        flags: 0x0102,
+    };
+    let encoded = to_vec(&counter).unwrap();
+    assert_eq!(encoded, [5, 2, 1]);
+    check_both_paths(&encoded, &counter);
+    assert!(from_slice::<shadowed::Counter>(&[5, 2]).is_err());
+}
+
+#[test]
+fn test_slice_deserialize_enum() {
+    for message in [
+        Message::Ping,
+        Message::Data {
+            id: 5,
+            header: header(),
+        },
+        Message::Pair(Pair(i128::MIN, u16::MAX), 9),
+    ] {
+        check_both_paths(&to_vec(&message).unwrap(), &message);
+    }
+}
+
+#[test]
+fn test_slice_deserialize_leaves_remaining_bytes() {
+    let mut encoded = to_vec(&Pair(1, 2)).unwrap();
+    encoded.extend_from_slice(&[0xaa, 0xbb]);
+    let mut buf = encoded.as_slice();
+    assert_eq!(Pair::deserialize(&mut buf).unwrap(), Pair(1, 2));
+    assert_eq!(buf, &[0xaa, 0xbb]);
+}
+
+#[test]
+fn test_slice_deserialize_short_input() {
+    let encoded = to_vec(&header()).unwrap();
+    for len in 0..encoded.len() {
+        let slice_err = from_slice::<Header>(&encoded[..len]).unwrap_err();
+        let reader_err = Header::deserialize_reader(&mut &encoded[..len]).unwrap_err();
+        assert_eq!(slice_err.to_string(), reader_err.to_string());
+        assert_eq!(slice_err.kind(), reader_err.kind());
+    }
+    let err = from_slice::<Message>(&[2, 0, 0]).unwrap_err();
+    assert_eq!(err.to_string(), "Unexpected length of input");
+    assert!(from_slice::<Message>(&[3]).is_err());
+    assert!(from_slice::<Message>(&[]).is_err());
+}
@@ -0,0 +1,80 @@
+use alloc::{vec, vec::Vec};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy)]
+#[repr(C)]
+union Word {
+    value: u32,
+    bytes: [u8; 4],
+    halves: [u16; 2],
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+struct WithUnion {
+    tag: u8,
+    word: Word,
+    words: Vec<Word>,
+}
+
+#[derive(BorshDeserialize)]
+#[borsh(init = clear_high)]
+#[repr(C)]
+union Initialized {
+    value: u64,
+    bytes: [u8; 8],
+}
+
+impl Initialized {
+    pub fn clear_high(&mut self) {
+        // SAFETY: all fields of the union are plain old data
+        unsafe { self.value &= 0xffff_ffff };
+    }
+}
+
+#[test]
+fn test_union_raw_bytes() {
+    let word = Word { value: 0x0403_0201 };
+    let data = to_vec(&word).unwrap();
+    assert_eq!(data, vec![1, 2, 3, 4]);
+
+    let decoded = from_slice::<Word>(&data).unwrap();
+    // SAFETY: all fields of the union are plain old data
+    unsafe {
+        assert_eq!(decoded.value, 0x0403_0201);
+        assert_eq!(decoded.bytes, [1, 2, 3, 4]);
+        assert_eq!(decoded.halves, [0x0201, 0x0403]);
+    }
+}
+
+#[test]
+fn test_union_in_struct() {
+    let value = WithUnion {
+        tag: 7,
+        word: Word { bytes: [9; 4] },
+        words: vec![Word { value: 1 }, Word { halves: [2, 3] }],
+    };
+    let data = to_vec(&value).unwrap();
+    assert_eq!(
+        data,
+        vec![7, 9, 9, 9, 9, 2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 3, 0]
+    );
+    let decoded = from_slice::<WithUnion>(&data).unwrap();
+    assert_eq!(decoded.tag, 7);
+    // SAFETY: all fields of the union are plain old data
+    unsafe {
+        assert_eq!(decoded.word.value, 0x0909_0909);
+        assert_eq!(decoded.words[1].halves, [2, 3]);
+    }
+
+    assert!(from_slice::<Word>(&[1, 2, 3]).is_err());
+}
+
+#[test]
+fn test_union_init() {
+    let decoded = from_slice::<Initialized>(&[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+    // SAFETY: all fields of the union are plain old data
+    unsafe {
+        assert_eq!(decoded.value, 0x0403_0201);
+        assert_eq!(decoded.bytes, [1, 2, 3, 4, 0, 0, 0, 0]);
+    }
+}
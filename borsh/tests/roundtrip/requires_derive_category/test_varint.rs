@@ -0,0 +1,92 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use borsh::varint::{VarI64, VarU32};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug)]
+struct A {
+    #[borsh(varint)]
+    x: u64,
+    #[borsh(varint)]
+    y: i32,
+    #[borsh(varint)]
+    bytes: Vec<u8>,
+    #[borsh(varint)]
+    numbers: Vec<u32>,
+    #[borsh(varint)]
+    map: BTreeMap<String, i64>,
+    #[borsh(varint)]
+    maybe: Option<u128>,
+    wrapped: VarI64,
+    plain: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug)]
+enum E {
+    A(#[borsh(varint)] u64),
+    B {
+        #[borsh(varint)]
+        len: u16,
+        lengths: Vec<VarU32>,
+    },
+}
+
+#[test]
+fn test_varint_struct() {
+    let mut map = BTreeMap::new();
+    map.insert("k".to_string(), -1);
+    let a = A {
+        x: 300,
+        y: -64,
+        bytes: vec![7; 130],
+        numbers: vec![1, 128],
+        map,
+        maybe: None,
+        wrapped: VarI64(64),
+        plain: 1,
+    };
+    let data = to_vec(&a).unwrap();
+    let mut expected = vec![0xac, 0x02, 0x40, 0x82, 0x01];
+    expected.extend([7; 130]);
+    expected.extend([2, 1, 0x80, 0x01]);
+    expected.extend([1, 1, b'k', 0x7f]);
+    expected.extend([0]);
+    expected.extend([0xc0, 0x00]);
+    expected.extend([1, 0, 0, 0]);
+    assert_eq!(data, expected);
+    assert_eq!(from_slice::<A>(&data).unwrap(), a);
+}
+
+#[test]
+fn test_varint_enum() {
+    for e in [
+        E::A(u64::MAX),
+        E::B {
+            len: 1000,
+            lengths: vec![VarU32(0), VarU32(u32::MAX)],
+        },
+    ] {
+        let data = to_vec(&e).unwrap();
+        assert_eq!(from_slice::<E>(&data).unwrap(), e);
+    }
+    assert_eq!(to_vec(&E::A(5)).unwrap(), vec![0, 5]);
+}
+
+#[test]
+fn test_varint_non_minimal_field() {
+    #[derive(BorshDeserialize, Debug)]
+    #[allow(unused)]
+    struct B {
+        #[borsh(varint)]
+        x: u32,
+    }
+    let err = from_slice::<B>(&[0x85, 0x00]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Non-minimal encoding of variable-length integer"
+    );
+}
@@ -0,0 +1,93 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize};
+
+/// module, which serializes `Vec<String>` as a single comma-separated `String`
+mod comma_separated {
+    use alloc::{string::String, vec::Vec};
+    use borsh::io::{Read, Result, Write};
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    pub fn serialize<W: Write>(value: &[String], writer: &mut W) -> Result<()> {
+        value.join(",").serialize(writer)
+    }
+
+    pub fn deserialize<R: Read>(reader: &mut R) -> Result<Vec<String>> {
+        let joined = String::deserialize_reader(reader)?;
+        if joined.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(joined.split(',').map(String::from).collect())
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+struct Record {
+    #[borsh(with = "borsh::with::big_endian::u32")]
+    id: u32,
+    #[borsh(with = "borsh::with::big_endian::i16")]
+    delta: i16,
+    #[borsh(with = "borsh::with::fixed_point::e6")]
+    price: f64,
+    #[borsh(with = "comma_separated")]
+    tags: Vec<String>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+enum Event {
+    Moved(#[borsh(with = "borsh::with::big_endian::u64")] u64),
+    Priced {
+        #[borsh(with = "borsh::with::fixed_point::e2")]
+        amount: f64,
+    },
+}
+
+#[test]
+fn test_with_struct() {
+    let record = Record {
+        id: 0x0a0b_0c0d,
+        delta: -2,
+        price: 12.345678,
+        tags: vec!["a".into(), "bc".into()],
+    };
+    let encoded = to_vec(&record).unwrap();
+    let expected = [
+        vec![0x0a, 0x0b, 0x0c, 0x0d, 0xff, 0xfe],
+        to_vec(&12_345_678i64).unwrap(),
+        to_vec("a,bc").unwrap(),
+    ]
+    .concat();
+    assert_eq!(encoded, expected);
+    assert_eq!(from_slice::<Record>(&encoded).unwrap(), record);
+}
+
+#[test]
+fn test_with_enum() {
+    let moved = Event::Moved(1);
+    assert_eq!(to_vec(&moved).unwrap(), vec![0, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+    let priced = Event::Priced { amount: -0.125 };
+    let encoded = to_vec(&priced).unwrap();
+    // rounded half away from zero
+    assert_eq!(encoded, [vec![1], to_vec(&-13i64).unwrap()].concat());
+    assert_eq!(
+        from_slice::<Event>(&encoded).unwrap(),
+        Event::Priced { amount: -0.13 }
+    );
+}
+
+#[test]
+fn test_fixed_point_out_of_range() {
+    for amount in [f64::NAN, f64::INFINITY, 1e30] {
+        assert!(to_vec(&Event::Priced { amount }).is_err());
+    }
+}
+
+#[test]
+fn test_big_endian_eof() {
+    let err = from_slice::<Event>(&[0, 1, 2]).unwrap_err();
+    assert_eq!(err.to_string(), "Unexpected length of input");
+}
@@ -0,0 +1,63 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use borsh::bounded::{BoundedString, BoundedVec};
+use borsh::{from_slice, to_vec};
+use core::convert::TryFrom;
+
+#[test]
+fn test_bounded_vec_roundtrip() {
+    let vec = BoundedVec::<u32, 1, 3>::try_from(vec![1, 2, 3]).unwrap();
+    let data = to_vec(&vec).unwrap();
+    assert_eq!(data, to_vec(&vec![1u32, 2, 3]).unwrap());
+    let decoded = from_slice::<BoundedVec<u32, 1, 3>>(&data).unwrap();
+    assert_eq!(decoded, vec);
+    assert_eq!(decoded.as_slice(), &[1, 2, 3]);
+    assert_eq!(Vec::from(decoded), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_bounded_vec_out_of_bounds() {
+    assert_eq!(
+        BoundedVec::<u8, 1, 3>::try_from(vec![]).unwrap_err(),
+        Vec::<u8>::new()
+    );
+    assert!(BoundedVec::<u8, 1, 3>::try_from(vec![0; 4]).is_err());
+
+    let err = from_slice::<BoundedVec<u8, 1, 3>>(&to_vec(&vec![0u8; 4]).unwrap()).unwrap_err();
+    assert_eq!(err.to_string(), "Collection length 4 is out of bounds 1..=3");
+    let err = from_slice::<BoundedVec<u64, 1, 3>>(&[0, 0, 0, 0]).unwrap_err();
+    assert_eq!(err.to_string(), "Collection length 0 is out of bounds 1..=3");
+    // length is checked before reading (and allocating space for) any elements
+    let err = from_slice::<BoundedVec<u64, 0, 1024>>(&[0xff, 0xff, 0xff, 0xff]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Collection length 4294967295 is out of bounds 0..=1024"
+    );
+}
+
+#[test]
+fn test_bounded_string_roundtrip() {
+    let string = BoundedString::<0, 5>::try_from("hello".to_string()).unwrap();
+    let data = to_vec(&string).unwrap();
+    assert_eq!(data, to_vec("hello").unwrap());
+    let decoded = from_slice::<BoundedString<0, 5>>(&data).unwrap();
+    assert_eq!(decoded.as_str(), "hello");
+    assert_eq!(String::from(decoded), "hello");
+
+    let empty = BoundedString::<0, 5>::try_from(String::new()).unwrap();
+    assert_eq!(
+        from_slice::<BoundedString<0, 5>>(&to_vec(&empty).unwrap()).unwrap(),
+        empty
+    );
+}
+
+#[test]
+fn test_bounded_string_out_of_bounds() {
+    // length is in bytes, not in chars
+    assert!(BoundedString::<0, 5>::try_from("привет".to_string()).is_err());
+    let err = from_slice::<BoundedString<0, 5>>(&to_vec("hello!").unwrap()).unwrap_err();
+    assert_eq!(err.to_string(), "Collection length 6 is out of bounds 0..=5");
+}
@@ -0,0 +1,67 @@
+// Generated by borsh from the schema of `Account`. Do not edit.
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct Account {
+    pub owner: [u8; 32],
+    pub balance: u128,
+    pub status: Status,
+    pub limits: BTreeMap<String, (u16, bool)>,
+    pub tags: HashSet<String>,
+    pub history: VecDeque<Option<i64>>,
+    pub tree: Option<Tree>,
+    #[borsh(length = u8)]
+    pub keys: Vec<Vec<u8>>,
+    #[borsh(varint, max_len = 16)]
+    pub memo: String,
+    #[borsh(varint)]
+    pub nonce: u64,
+    #[borsh(max_len = 4)]
+    pub backup: Option<Vec<u64>>,
+    pub endian: borsh::endian::BigEndian<u32>,
+    pub non_zero: core::num::NonZeroU16,
+    pub result: Result<u8, String>,
+    pub point: Point,
+    pub unit: Unit,
+    pub config: V2Config,
+    pub wrapper: WrapperU8,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[borsh(use_discriminant = true)]
+#[repr(u8)]
+pub enum Status {
+    Active = 1,
+    Frozen {
+        reason: String,
+    } = 3,
+    Closed(u64) = 4,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct Tree {
+    pub value: u8,
+    pub children: Vec<Tree>,
+    pub next: Box<Option<Tree>>,
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct Point(pub i32, pub i32);
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct Unit;
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[borsh(schema(name = "v2::Config"))]
+pub enum V2Config {
+    Default,
+    Custom(Vec<f32>),
+}
+
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[borsh(schema(name = "Wrapper<u8>"))]
+pub struct WrapperU8 {
+    pub value: u8,
+}
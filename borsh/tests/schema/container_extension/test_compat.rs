@@ -0,0 +1,288 @@
+use crate::common_macro::schema_imports::*;
+use borsh::schema::compat::{check, Compatibility, Report};
+
+fn check_types<Old: BorshSchema, New: BorshSchema>() -> Report {
+    check(
+        &BorshSchemaContainer::for_type::<Old>(),
+        &BorshSchemaContainer::for_type::<New>(),
+    )
+}
+
+#[track_caller]
+fn assert_differences(report: &Report, want: &[(Compatibility, &str)]) {
+    let differences = report
+        .differences()
+        .iter()
+        .map(|difference| (difference.compatibility, difference.to_string()))
+        .collect::<Vec<_>>();
+    let want = want
+        .iter()
+        .map(|(compatibility, difference)| (*compatibility, difference.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(differences, want);
+}
+
+mod v1 {
+    use crate::common_macro::schema_imports::*;
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Account {
+        pub owner: String,
+        pub balance: u64,
+        pub status: Status,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub enum Status {
+        Active,
+        Frozen { reason: String },
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Node {
+        pub value: u8,
+        pub children: Vec<Node>,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Limits {
+        #[borsh(max_len = 2)]
+        pub keys: Vec<u8>,
+    }
+}
+
+mod v2 {
+    use crate::common_macro::schema_imports::*;
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Account {
+        pub owner: String,
+        pub balance: u64,
+        pub status: Status,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub enum Status {
+        Active,
+        Frozen { reason: String },
+        Closed,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Wallet {
+        pub holder: String,
+        pub balance: Balance,
+        pub status: Status,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Balance(pub u64);
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Tree {
+        pub value: u8,
+        pub children: Vec<Tree>,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Limits {
+        #[borsh(max_len = 4)]
+        pub keys: Vec<u8>,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Wrap {
+        pub inner: Inner,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Inner {
+        pub x: u8,
+        pub y: u16,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct WrapTuple(pub (u8, u16));
+}
+
+mod v3 {
+    use crate::common_macro::schema_imports::*;
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Account {
+        pub owner: String,
+        pub balance: u32,
+        pub status: Status,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub enum Status {
+        Active,
+        Closed,
+    }
+}
+
+#[test]
+fn compat_identical() {
+    let report = check_types::<v1::Account, v1::Account>();
+    assert_eq!(report.compatibility(), Compatibility::Identical);
+    assert!(report.differences().is_empty());
+    assert!(report.is_backward_compatible());
+
+    // names of types and newtypes are not part of the wire format
+    let report = check_types::<v1::Node, v2::Tree>();
+    assert_eq!(report.compatibility(), Compatibility::Identical);
+    let report = check_types::<u64, v2::Balance>();
+    assert_eq!(report.compatibility(), Compatibility::Identical);
+    let report = check_types::<(), core::marker::PhantomData<u8>>();
+    assert_eq!(report.compatibility(), Compatibility::Identical);
+}
+
+#[test]
+fn compat_newtype_of_product() {
+    let report = check_types::<v2::Wrap, v2::Inner>();
+    assert_eq!(report.compatibility(), Compatibility::Identical);
+    let report = check_types::<v2::Inner, v2::Wrap>();
+    assert_eq!(report.compatibility(), Compatibility::Identical);
+    let report = check_types::<v2::WrapTuple, (u8, u16)>();
+    assert_eq!(report.compatibility(), Compatibility::Identical);
+    let report = check_types::<(u8, u16), v2::WrapTuple>();
+    assert_eq!(report.compatibility(), Compatibility::Identical);
+
+    // a newtype of other fields is still a change of the number of fields
+    let report = check_types::<v2::WrapTuple, (u8, u32)>();
+    assert_differences(
+        &report,
+        &[(
+            Compatibility::Incompatible,
+            "`WrapTuple`: number of fields of `WrapTuple` changed from 1 to 2",
+        )],
+    );
+}
+
+#[test]
+fn compat_renamed() {
+    let report = check_types::<v1::Account, v2::Wallet>();
+    assert_eq!(report.compatibility(), Compatibility::Extended);
+    assert_differences(
+        &report,
+        &[
+            (
+                Compatibility::Renamed,
+                "`Account.owner`: field `owner` renamed to `holder`",
+            ),
+            (
+                Compatibility::Extended,
+                "`Account.status`: variant `Closed` with discriminant 2 appended",
+            ),
+        ],
+    );
+
+    let report = check_types::<(u8, u16), v1::Limits>();
+    assert_eq!(report.compatibility(), Compatibility::Incompatible);
+}
+
+#[test]
+fn compat_extended() {
+    let report = check_types::<v1::Account, v2::Account>();
+    assert_eq!(report.compatibility(), Compatibility::Extended);
+    assert!(report.is_backward_compatible());
+    assert_differences(
+        &report,
+        &[(
+            Compatibility::Extended,
+            "`Account.status`: variant `Closed` with discriminant 2 appended",
+        )],
+    );
+
+    let report = check_types::<v1::Limits, v2::Limits>();
+    assert_differences(
+        &report,
+        &[(
+            Compatibility::Extended,
+            "`Limits.keys`: length range of `Vec<u8, max_len = 2>` relaxed from 0..=2 to 0..=4",
+        )],
+    );
+}
+
+#[test]
+fn compat_incompatible() {
+    let report = check_types::<v1::Account, v3::Account>();
+    assert_eq!(report.compatibility(), Compatibility::Incompatible);
+    assert!(!report.is_backward_compatible());
+    assert_differences(
+        &report,
+        &[
+            (
+                Compatibility::Incompatible,
+                "`Account.balance`: `u64` changed to `u32`",
+            ),
+            (
+                Compatibility::Renamed,
+                "`Account.status::Frozen`: variant `Frozen` renamed to `Closed`",
+            ),
+            (
+                Compatibility::Incompatible,
+                "`Account.status::Frozen`: number of fields of `StatusFrozen` changed from 1 to 0",
+            ),
+        ],
+    );
+
+    let report = check_types::<v2::Account, v1::Account>();
+    assert_differences(
+        &report,
+        &[(
+            Compatibility::Incompatible,
+            "`Account.status::Closed`: variant `Closed` with discriminant 2 removed",
+        )],
+    );
+
+    let report = check_types::<v2::Limits, v1::Limits>();
+    assert_eq!(report.compatibility(), Compatibility::Incompatible);
+
+    let report = check_types::<Vec<u8>, [u8; 4]>();
+    assert_differences(
+        &report,
+        &[(
+            Compatibility::Incompatible,
+            "`Vec<u8>`: length width of `Vec<u8>` changed from 4 to 0",
+        )],
+    );
+
+    let report = check_types::<(u8, u8), (u8, u8, u8)>();
+    assert_differences(
+        &report,
+        &[(
+            Compatibility::Incompatible,
+            "`(u8, u8)`: number of fields of `(u8, u8)` changed from 2 to 3",
+        )],
+    );
+}
+
+#[test]
+fn compat_missing_definition() {
+    let old = BorshSchemaContainer::for_type::<u8>();
+    let new = BorshSchemaContainer::new("u8".to_string(), schema_map! {});
+    assert_differences(
+        &check(&old, &new),
+        &[(
+            Compatibility::Incompatible,
+            "`u8`: missing definition of `u8` in new schema",
+        )],
+    );
+}
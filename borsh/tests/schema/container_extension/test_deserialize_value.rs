@@ -0,0 +1,338 @@
+use crate::common_macro::schema_imports::*;
+use borsh::schema::Value;
+use borsh::BorshSerialize;
+
+#[track_caller]
+fn test_ok<T: BorshSchema + BorshSerialize>(value: T, want: Value) {
+    let schema = BorshSchemaContainer::for_type::<T>();
+    let bytes = borsh::to_vec(&value).unwrap();
+    assert_eq!(schema.deserialize_value(&bytes).unwrap(), want);
+}
+
+#[track_caller]
+fn test_err(schema: &BorshSchemaContainer, bytes: &[u8], want: &str) {
+    let err = schema.deserialize_value(bytes).unwrap_err();
+    assert_eq!(err.kind(), borsh::io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), want);
+}
+
+fn string(value: &str) -> Value {
+    Value::String(value.to_string())
+}
+
+fn variant(name: &str, value: Value) -> Value {
+    Value::Enum(name.to_string(), Box::new(value))
+}
+
+#[test]
+fn deserialize_value_primitives() {
+    test_ok((), Value::Unit);
+    test_ok(true, Value::Bool(true));
+    test_ok(u8::MAX, Value::UInt(u8::MAX.into()));
+    test_ok(u128::MAX, Value::UInt(u128::MAX));
+    test_ok(usize::MAX, Value::UInt(u64::MAX.into()));
+    test_ok(i8::MIN, Value::Int(i8::MIN.into()));
+    test_ok(i128::MIN, Value::Int(i128::MIN));
+    test_ok(1.5f32, Value::Float(1.5));
+    test_ok(-2.25f64, Value::Float(-2.25));
+    test_ok(core::num::NonZeroU16::new(3).unwrap(), Value::UInt(3));
+    test_ok(core::num::NonZeroI64::new(-3).unwrap(), Value::Int(-3));
+    test_ok(borsh::endian::BigEndian(0x0102u16), Value::UInt(0x0102));
+    test_ok(borsh::varint::VarI64(-300), Value::Int(-300));
+    test_ok(borsh::varint::VarU128(u128::MAX), Value::UInt(u128::MAX));
+}
+
+#[test]
+fn deserialize_value_collections() {
+    test_ok("borsh".to_string(), string("borsh"));
+    test_ok(vec![1u8, 2], Value::Bytes(vec![1, 2]));
+    test_ok([7u8; 3], Value::Bytes(vec![7; 3]));
+    test_ok(
+        vec![1u16, 2],
+        Value::Seq(vec![Value::UInt(1), Value::UInt(2)]),
+    );
+    test_ok([(); 2], Value::Seq(vec![Value::Unit, Value::Unit]));
+    test_ok(
+        (1u8, "a".to_string(), -1i32),
+        Value::Tuple(vec![Value::UInt(1), string("a"), Value::Int(-1)]),
+    );
+    test_ok(Some(5u32), variant("Some", Value::UInt(5)));
+    test_ok(None::<u32>, variant("None", Value::Unit));
+    test_ok(
+        Err::<u8, String>("no".to_string()),
+        variant("Err", string("no")),
+    );
+
+    let mut map = BTreeMap::new();
+    map.insert("b".to_string(), 2u8);
+    map.insert("a".to_string(), 1u8);
+    test_ok(
+        map,
+        Value::Seq(vec![
+            Value::Tuple(vec![string("a"), Value::UInt(1)]),
+            Value::Tuple(vec![string("b"), Value::UInt(2)]),
+        ]),
+    );
+}
+
+#[test]
+fn deserialize_value_derived() {
+    #[derive(BorshSchema, BorshSerialize)]
+    struct Unit;
+
+    #[derive(BorshSchema, BorshSerialize)]
+    struct Pair(u8, bool);
+
+    #[derive(BorshSchema, BorshSerialize)]
+    enum Shape {
+        Empty,
+        Circle { radius: f32 },
+        Polygon(Vec<Pair>),
+    }
+
+    #[derive(BorshSchema, BorshSerialize)]
+    struct Drawing {
+        name: String,
+        shapes: Vec<Shape>,
+        unit: Unit,
+    }
+
+    test_ok(
+        Drawing {
+            name: "d".to_string(),
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle { radius: 0.5 },
+                Shape::Polygon(vec![Pair(1, true)]),
+            ],
+            unit: Unit,
+        },
+        Value::Struct(vec![
+            ("name".to_string(), string("d")),
+            (
+                "shapes".to_string(),
+                Value::Seq(vec![
+                    variant("Empty", Value::Unit),
+                    variant(
+                        "Circle",
+                        Value::Struct(vec![("radius".to_string(), Value::Float(0.5))]),
+                    ),
+                    variant(
+                        "Polygon",
+                        Value::Tuple(vec![Value::Seq(vec![Value::Tuple(vec![
+                            Value::UInt(1),
+                            Value::Bool(true),
+                        ])])]),
+                    ),
+                ]),
+            ),
+            ("unit".to_string(), Value::Unit),
+        ]),
+    );
+}
+
+#[test]
+fn deserialize_value_recursive() {
+    #[derive(BorshSchema, BorshSerialize)]
+    struct List {
+        value: u8,
+        next: Option<Box<List>>,
+    }
+
+    let list = List {
+        value: 1,
+        next: Some(Box::new(List {
+            value: 2,
+            next: None,
+        })),
+    };
+    test_ok(
+        list,
+        Value::Struct(vec![
+            ("value".to_string(), Value::UInt(1)),
+            (
+                "next".to_string(),
+                variant(
+                    "Some",
+                    Value::Struct(vec![
+                        ("value".to_string(), Value::UInt(2)),
+                        ("next".to_string(), variant("None", Value::Unit)),
+                    ]),
+                ),
+            ),
+        ]),
+    );
+}
+
+#[test]
+fn deserialize_value_custom_length_width() {
+    #[derive(BorshSchema, BorshSerialize)]
+    struct A {
+        #[borsh(length = u8)]
+        bytes: Vec<u8>,
+        #[borsh(length = u16)]
+        name: String,
+        #[borsh(varint)]
+        values: Vec<u16>,
+        #[borsh(varint)]
+        label: String,
+        #[borsh(max_len = 2)]
+        small: Vec<u8>,
+    }
+
+    let value = A {
+        bytes: vec![1, 2, 3],
+        name: "n".to_string(),
+        values: vec![4, 5],
+        label: "l".to_string(),
+        small: vec![6],
+    };
+    let bytes = borsh::to_vec(&value).unwrap();
+    assert_eq!(bytes[0], 3);
+    test_ok(
+        value,
+        Value::Struct(vec![
+            ("bytes".to_string(), Value::Bytes(vec![1, 2, 3])),
+            ("name".to_string(), string("n")),
+            (
+                "values".to_string(),
+                Value::Seq(vec![Value::UInt(4), Value::UInt(5)]),
+            ),
+            ("label".to_string(), string("l")),
+            ("small".to_string(), Value::Bytes(vec![6])),
+        ]),
+    );
+
+    let schema = BorshSchemaContainer::for_type::<A>();
+    let mut bytes = bytes;
+    let len = bytes.len();
+    bytes[len - 5] = 3;
+    bytes.push(0);
+    bytes.push(0);
+    test_err(
+        &schema,
+        &bytes,
+        "Length 3 of `Vec<u8, max_len = 2>` is out of 0..=2 range",
+    );
+}
+
+#[test]
+fn deserialize_value_custom_tag_width() {
+    let schema = BorshSchemaContainer::new(
+        "Wide".to_string(),
+        schema_map! {
+            "Wide" => Definition::Enum {
+                tag_width: 2,
+                variants: vec![
+                    (0, "Zero".to_string(), "()".to_string()),
+                    (300, "Big".to_string(), "u8".to_string()),
+                ]
+            },
+            "()" => Definition::Primitive(0),
+            "u8" => Definition::Primitive(1)
+        },
+    );
+    assert_eq!(
+        schema.deserialize_value(&[44, 1, 9]).unwrap(),
+        variant("Big", Value::UInt(9))
+    );
+    assert_eq!(
+        schema.deserialize_value(&[0, 0]).unwrap(),
+        variant("Zero", Value::Unit)
+    );
+    test_err(&schema, &[1, 0], "Unexpected variant tag of `Wide`: 1");
+    test_err(&schema, &[0], "Unexpected length of input");
+}
+
+#[test]
+fn deserialize_value_errors() {
+    let schema = BorshSchemaContainer::for_type::<Vec<u8>>();
+    test_err(&schema, &[1, 0, 0, 0, 1, 2], "Not all bytes read");
+    test_err(&schema, &[2, 0, 0, 0, 1], "Unexpected length of input");
+
+    let schema = BorshSchemaContainer::for_type::<bool>();
+    test_err(&schema, &[2], "Invalid bool representation: 2");
+
+    let schema = BorshSchemaContainer::for_type::<String>();
+    test_err(
+        &schema,
+        &[1, 0, 0, 0, 0xff],
+        "invalid utf-8 sequence of 1 bytes from index 0",
+    );
+
+    let schema = BorshSchemaContainer::new("A".to_string(), schema_map! {});
+    test_err(&schema, &[], "Missing definition of `A`");
+
+    let schema = BorshSchemaContainer::new(
+        "A".to_string(),
+        schema_map! {
+            "A" => Definition::Enum {
+                tag_width: 0,
+                variants: vec![(0, "X".to_string(), "()".to_string())]
+            },
+            "()" => Definition::Primitive(0)
+        },
+    );
+    test_err(
+        &schema,
+        &[],
+        "Variant of untagged `A` is not determined by the schema",
+    );
+
+    let schema = BorshSchemaContainer::new(
+        "A".to_string(),
+        schema_map! {
+            "A" => Definition::Sequence {
+                length_width: 0,
+                length_range: 0..=10,
+                elements: "()".to_string()
+            },
+            "()" => Definition::Primitive(0)
+        },
+    );
+    test_err(
+        &schema,
+        &[],
+        "Length of `A` is not determined by the schema",
+    );
+
+    let schema = BorshSchemaContainer::new(
+        "A".to_string(),
+        schema_map! {
+            "A" => Definition::Struct { fields: Fields::UnnamedFields(vec!["A".to_string()]) }
+        },
+    );
+    test_err(&schema, &[], "Value of `A` is nested too deeply");
+}
+
+#[test]
+fn deserialize_value_zero_sized_elements() {
+    use borsh::varint::VarU32;
+
+    #[derive(BorshSchema, BorshSerialize)]
+    struct Empty {}
+
+    let zst_forbidden = "Collections of zero-sized types are not allowed due to deny-of-service concerns on deserialization.";
+    let schema = BorshSchemaContainer::for_type::<Vec<()>>();
+    test_err(&schema, &[0xff, 0xff, 0xff, 0xff], zst_forbidden);
+    test_err(&schema, &[1, 0, 0, 0], zst_forbidden);
+    assert_eq!(
+        schema.deserialize_value(&[0, 0, 0, 0]).unwrap(),
+        Value::Seq(vec![])
+    );
+
+    let schema = BorshSchemaContainer::for_type::<Vec<Empty>>();
+    test_err(&schema, &[0xff, 0xff, 0xff, 0xff], zst_forbidden);
+
+    #[derive(BorshSchema)]
+    struct Varint {
+        #[borsh(varint)]
+        _units: Vec<()>,
+    }
+    let schema = BorshSchemaContainer::for_type::<Varint>();
+    let bytes = borsh::to_vec(&VarU32(u32::MAX)).unwrap();
+    test_err(&schema, &bytes, zst_forbidden);
+
+    // length of arrays is defined by the schema, not by the input
+    test_ok([(), ()], Value::Seq(vec![Value::Unit, Value::Unit]));
+}
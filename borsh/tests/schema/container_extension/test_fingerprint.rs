@@ -0,0 +1,107 @@
+use crate::common_macro::schema_imports::*;
+use borsh::{from_slice_with_schema_hash, to_vec_with_schema_hash};
+
+fn fingerprint<T: BorshSchema + ?Sized>() -> u64 {
+    BorshSchemaContainer::for_type::<T>().fingerprint()
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+struct Wrapper<T> {
+    value: T,
+    values: Vec<T>,
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+struct Concrete {
+    value: u8,
+    values: Vec<u8>,
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+struct Renamed {
+    item: u8,
+    values: Vec<u8>,
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+enum List {
+    Nil,
+    Cons(u8, Box<List>),
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+enum Chain {
+    Nil,
+    Cons(u8, Box<Chain>),
+}
+
+#[test]
+fn fingerprint_ignores_names_of_types() {
+    assert_eq!(fingerprint::<Wrapper<u8>>(), fingerprint::<Concrete>());
+    assert_eq!(fingerprint::<List>(), fingerprint::<Chain>());
+    assert_eq!(fingerprint::<Vec<u8>>(), fingerprint::<Vec<u8>>());
+}
+
+#[test]
+fn fingerprint_differs() {
+    assert_ne!(fingerprint::<Concrete>(), fingerprint::<Renamed>());
+    assert_ne!(fingerprint::<Wrapper<u8>>(), fingerprint::<Wrapper<i8>>());
+    assert_ne!(fingerprint::<Vec<u8>>(), fingerprint::<[u8; 4]>());
+    assert_ne!(fingerprint::<(u8, u16)>(), fingerprint::<(u16, u8)>());
+    assert_ne!(fingerprint::<Option<u8>>(), fingerprint::<Result<u8, ()>>());
+}
+
+#[test]
+fn fingerprint_keeps_significant_names() {
+    use borsh::varint::{VarI32, VarU32, VarU64};
+
+    assert_ne!(fingerprint::<VarU32>(), fingerprint::<VarI32>());
+    assert_ne!(fingerprint::<Vec<VarU32>>(), fingerprint::<Vec<VarI32>>());
+    assert_ne!(fingerprint::<VarU32>(), fingerprint::<VarU64>());
+    assert_ne!(fingerprint::<String>(), fingerprint::<Vec<u8>>());
+    assert_ne!(
+        fingerprint::<(u8, String)>(),
+        fingerprint::<(u8, Vec<u8>)>()
+    );
+}
+
+#[test]
+fn fingerprint_canonical() {
+    let schema = BorshSchemaContainer::for_type::<Concrete>();
+    let mut definitions: BTreeMap<_, _> = schema
+        .definitions()
+        .map(|(declaration, definition)| (declaration.clone(), definition.clone()))
+        .collect();
+    // unreachable definitions don't take part in the hash
+    definitions.insert("Unused".to_string(), Definition::Primitive(3));
+    let extended = BorshSchemaContainer::new(schema.declaration().clone(), definitions);
+    assert_eq!(schema.fingerprint(), extended.fingerprint());
+
+    // hash is stable across releases
+    assert_eq!(fingerprint::<u8>(), 0x7b99_86dc_2bd9_de47);
+}
+
+#[test]
+fn schema_hash_roundtrip() {
+    let value = (7u32, "borsh".to_string(), Some(vec![1u8, 2]));
+    let bytes = to_vec_with_schema_hash(&value).unwrap();
+    assert_eq!(
+        &bytes[..8],
+        &fingerprint::<(u32, String, Option<Vec<u8>>)>().to_le_bytes()
+    );
+    assert_eq!(&bytes[8..], borsh::to_vec(&value).unwrap().as_slice());
+    assert_eq!(
+        from_slice_with_schema_hash::<(u32, String, Option<Vec<u8>>)>(&bytes).unwrap(),
+        value
+    );
+
+    let err = from_slice_with_schema_hash::<(u32, String, Option<Vec<u16>>)>(&bytes).unwrap_err();
+    assert_eq!(err.kind(), borsh::io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "Borsh schema hash does not match");
+    assert!(from_slice_with_schema_hash::<u8>(&bytes[..4]).is_err());
+}
@@ -0,0 +1,186 @@
+use crate::common_macro::schema_imports::*;
+use borsh::schema::SchemaParseError;
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+#[borsh(use_discriminant = true)]
+#[repr(u8)]
+enum E {
+    A = 0,
+    B(u64) = 5,
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+struct Foo {
+    a: u32,
+    b: Vec<u8>,
+    e: E,
+    pair: (u8, bool),
+    bytes: [u8; 2],
+    #[borsh(max_len = 10, length = u16)]
+    short: Vec<u8>,
+    unit: (),
+}
+
+#[test]
+fn idl_display() {
+    let container = BorshSchemaContainer::for_type::<Foo>();
+    let idl = container.to_string();
+    #[cfg(feature = "std")]
+    insta::assert_snapshot!(idl);
+    assert!(idl.starts_with("root Foo;\n"));
+    assert!(idl.contains("enum E: u8 {\n    A(EA) = 0,\n    B(EB) = 5,\n}\n"));
+    assert!(idl.contains("struct EA;\n"));
+    assert!(idl.contains("struct EB(u64);\n"));
+    assert!(idl.contains("type \"[u8; 2]\" = seq<0, 2..=2> u8;\n"));
+    assert!(idl.contains("type \"(u8, bool)\" = (u8, bool);\n"));
+    assert!(idl.contains("type \"()\" = primitive(0);\n"));
+    assert_eq!(idl.parse::<BorshSchemaContainer>(), Ok(container));
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+enum Tree<T> {
+    Leaf(T),
+    Node(Vec<Tree<T>>),
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+#[borsh(schema(name = "v2::Packet"), discriminator = [0xca, 0xfe], pad_after = 2)]
+struct Packet {
+    #[borsh(varint)]
+    len: u32,
+    #[borsh(big_endian)]
+    checksum: u16,
+    #[borsh(pad = 3)]
+    payload: Option<String>,
+    tree: Tree<u8>,
+    map: BTreeMap<String, Result<u8, ()>>,
+}
+
+#[track_caller]
+fn assert_idl_roundtrip(container: BorshSchemaContainer) {
+    let idl = container.to_string();
+    assert_eq!(
+        idl.parse::<BorshSchemaContainer>(),
+        Ok(container),
+        "{}",
+        idl
+    );
+}
+
+#[test]
+fn idl_roundtrip_derived_schemas() {
+    assert_idl_roundtrip(BorshSchemaContainer::for_type::<Foo>());
+    assert_idl_roundtrip(BorshSchemaContainer::for_type::<Tree<(u8, bool)>>());
+    assert_idl_roundtrip(BorshSchemaContainer::for_type::<Packet>());
+    assert_idl_roundtrip(BorshSchemaContainer::for_type::<BorshSchemaContainer>());
+    assert_idl_roundtrip(BorshSchemaContainer::for_type::<()>());
+}
+
+#[test]
+fn idl_parse() {
+    let parsed = r#"
+        // comments and whitespace are ignored
+        root Foo;
+        struct Foo { a: u32, b: "Vec<u8>", } // trailing comma
+        type "Vec<u8>" = seq<u32> u8;
+        type u8 = primitive(1);
+        type u32 = primitive(4);
+        enum a::E: u8 { A(a::EA) = 0, B(u64) = 5 }
+        struct a::EA;
+        type Bounded = seq<u16, 1..=10> u8;
+        type T = (u8,);
+        struct "with \"quotes\"\t\u{1F600}"();
+        struct Named {}
+        type u64 = primitive(8);
+    "#
+    .parse::<BorshSchemaContainer>()
+    .unwrap();
+
+    let expected = BorshSchemaContainer::new(
+        "Foo".to_string(),
+        schema_map! {
+            "Foo" => Definition::Struct {
+                fields: Fields::NamedFields(vec![
+                    ("a".to_string(), "u32".to_string()),
+                    ("b".to_string(), "Vec<u8>".to_string()),
+                ])
+            },
+            "Vec<u8>" => Definition::Sequence {
+                length_width: Definition::DEFAULT_LENGTH_WIDTH,
+                length_range: Definition::DEFAULT_LENGTH_RANGE,
+                elements: "u8".to_string(),
+            },
+            "u8" => Definition::Primitive(1),
+            "u32" => Definition::Primitive(4),
+            "a::E" => Definition::Enum {
+                tag_width: 1,
+                variants: vec![
+                    (0, "A".to_string(), "a::EA".to_string()),
+                    (5, "B".to_string(), "u64".to_string()),
+                ]
+            },
+            "a::EA" => Definition::Struct { fields: Fields::Empty },
+            "Bounded" => Definition::Sequence {
+                length_width: 2,
+                length_range: 1..=10,
+                elements: "u8".to_string(),
+            },
+            "T" => Definition::Tuple { elements: vec!["u8".to_string()] },
+            "with \"quotes\"\t\u{1F600}" => Definition::Struct {
+                fields: Fields::UnnamedFields(vec![])
+            },
+            "Named" => Definition::Struct { fields: Fields::NamedFields(vec![]) },
+            "u64" => Definition::Primitive(8)
+        },
+    );
+    assert_eq!(parsed, expected);
+}
+
+#[track_caller]
+fn parse_error(idl: &str) -> String {
+    let err: SchemaParseError = idl.parse::<BorshSchemaContainer>().unwrap_err();
+    err.to_string()
+}
+
+#[test]
+fn idl_parse_errors() {
+    assert_eq!(parse_error(""), "missing `root` at 1:1");
+    assert_eq!(parse_error("root A;\nroot B;"), "duplicate `root` at 2:1");
+    assert_eq!(
+        parse_error("root A;\nstruct A;\n  struct A {}"),
+        "duplicate definition of `A` at 3:3"
+    );
+    assert_eq!(
+        parse_error("root A; union A {}"),
+        "expected `struct`, `enum`, `type` or `root`, found `union` at 1:9"
+    );
+    assert_eq!(
+        parse_error("root A; struct A { a u8 }"),
+        "expected `:`, found `u8` at 1:22"
+    );
+    assert_eq!(
+        parse_error("root A; type A = primitive(256);"),
+        "`256` is out of range at 1:28"
+    );
+    assert_eq!(
+        parse_error("root A; type A = seq<u128> u8;"),
+        "expected a width, found `u128` at 1:22"
+    );
+    assert_eq!(
+        parse_error("root A; type A = seq<u8, 0..10> u8;"),
+        "unexpected character '.' at 1:27"
+    );
+    assert_eq!(
+        parse_error("root A; enum A: u8 { B(u8) }"),
+        "expected `=`, found `}` at 1:28"
+    );
+    assert_eq!(parse_error("root \"A;"), "unterminated string at 1:6");
+    assert_eq!(
+        parse_error("root a::;"),
+        "expected an identifier, found `;` at 1:9"
+    );
+}
@@ -0,0 +1,209 @@
+use crate::common_macro::schema_imports::*;
+use borsh::BorshSerialize;
+use serde_json::json;
+
+#[track_caller]
+fn test_ok<T: BorshSchema + BorshSerialize>(value: T, want: serde_json::Value) {
+    let schema = BorshSchemaContainer::for_type::<T>();
+    let bytes = borsh::to_vec(&value).unwrap();
+    assert_eq!(schema.serialize_json(&want).unwrap(), bytes);
+    assert_eq!(schema.deserialize_json(&bytes).unwrap(), want);
+}
+
+#[track_caller]
+fn test_err(schema: &BorshSchemaContainer, value: serde_json::Value, want: &str) {
+    let err = schema.serialize_json(&value).unwrap_err();
+    assert_eq!(err.kind(), borsh::io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), want);
+}
+
+#[derive(BorshSchema, BorshSerialize)]
+struct Pair(u8, bool);
+
+#[derive(BorshSchema, BorshSerialize)]
+enum Shape {
+    Empty,
+    Circle { radius: f32 },
+    Polygon(Vec<Pair>),
+}
+
+#[derive(BorshSchema, BorshSerialize)]
+struct Drawing {
+    name: String,
+    shapes: Vec<Shape>,
+    id: [u8; 2],
+}
+
+#[test]
+fn json_primitives() {
+    test_ok((), json!(null));
+    test_ok(true, json!(true));
+    test_ok(u8::MAX, json!(255));
+    test_ok(u64::MAX, json!(u64::MAX));
+    test_ok(u128::MAX, json!(u128::MAX.to_string()));
+    test_ok(i128::MIN, json!(i128::MIN.to_string()));
+    test_ok(-7i16, json!(-7));
+    test_ok(1.5f32, json!(1.5));
+    test_ok(core::num::NonZeroU32::new(9).unwrap(), json!(9));
+    test_ok(borsh::endian::BigEndian(0x0102u16), json!(0x0102));
+    test_ok(borsh::varint::VarI64(-300), json!(-300));
+
+    let schema = BorshSchemaContainer::for_type::<(u128, i8)>();
+    assert_eq!(
+        schema.serialize_json(&json!(["5", "-5"])).unwrap(),
+        borsh::to_vec(&(5u128, -5i8)).unwrap()
+    );
+}
+
+#[test]
+fn json_collections() {
+    test_ok("borsh".to_string(), json!("borsh"));
+    test_ok(vec![1u8, 2], json!([1, 2]));
+    test_ok((1u8, "a".to_string()), json!([1, "a"]));
+    test_ok(Some(5u32), json!({"Some": 5}));
+    test_ok(None::<u32>, json!({"None": null}));
+
+    let mut map = BTreeMap::new();
+    map.insert("a".to_string(), 1u8);
+    test_ok(map, json!([["a", 1]]));
+
+    let schema = BorshSchemaContainer::for_type::<Option<u32>>();
+    assert_eq!(schema.serialize_json(&json!("None")).unwrap(), vec![0]);
+}
+
+#[test]
+fn json_derived() {
+    test_ok(
+        Drawing {
+            name: "d".to_string(),
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle { radius: 0.5 },
+                Shape::Polygon(vec![Pair(1, true)]),
+            ],
+            id: [3, 4],
+        },
+        json!({
+            "name": "d",
+            "shapes": [
+                {"Empty": null},
+                {"Circle": {"radius": 0.5}},
+                {"Polygon": [[[1, true]]]}
+            ],
+            "id": [3, 4]
+        }),
+    );
+}
+
+#[test]
+fn json_custom_length_width() {
+    #[derive(BorshSchema, BorshSerialize)]
+    struct A {
+        #[borsh(length = u8)]
+        bytes: Vec<u8>,
+        #[borsh(varint)]
+        values: Vec<u16>,
+        #[borsh(varint)]
+        label: String,
+        #[borsh(max_len = 2)]
+        small: Vec<u8>,
+    }
+
+    test_ok(
+        A {
+            bytes: vec![1, 2, 3],
+            values: vec![4, 5],
+            label: "l".to_string(),
+            small: vec![6],
+        },
+        json!({"bytes": [1, 2, 3], "values": [4, 5], "label": "l", "small": [6]}),
+    );
+
+    let schema = BorshSchemaContainer::for_type::<A>();
+    test_err(
+        &schema,
+        json!({"bytes": [], "values": [], "label": "", "small": [1, 2, 3]}),
+        "Invalid value at `$.small`: length 3 of `Vec<u8, max_len = 2>` is out of 0..=2 range",
+    );
+}
+
+#[test]
+fn json_errors() {
+    let schema = BorshSchemaContainer::for_type::<Drawing>();
+    test_err(
+        &schema,
+        json!({"name": 1, "shapes": [], "id": [0, 0]}),
+        "Invalid value at `$.name`: expected a string for `String`, found number",
+    );
+    test_err(
+        &schema,
+        json!({"name": "", "shapes": [{"Circle": {"radius": "x"}}], "id": [0, 0]}),
+        "Invalid value at `$.shapes[0].Circle.radius`: expected a number for `f32`, found string",
+    );
+    test_err(
+        &schema,
+        json!({"name": "", "shapes": [{"Polygon": [[[256, true]]]}], "id": [0, 0]}),
+        "Invalid value at `$.shapes[0].Polygon[0][0][0]`: 256 is out of range of `u8`",
+    );
+    test_err(
+        &schema,
+        json!({"name": "", "shapes": [{"Square": null}], "id": [0, 0]}),
+        "Invalid value at `$.shapes[0]`: unknown variant `Square` of `Shape`",
+    );
+    test_err(
+        &schema,
+        json!({"name": "", "shapes": [{"Empty": null, "Circle": null}], "id": [0, 0]}),
+        "Invalid value at `$.shapes[0]`: expected an object with a single variant for `Shape`, found object",
+    );
+    test_err(
+        &schema,
+        json!({"name": "", "shapes": []}),
+        "Invalid value at `$`: missing field `id` of `Drawing`",
+    );
+    test_err(
+        &schema,
+        json!({"name": "", "shapes": [], "id": [0, 0], "extra": 1}),
+        "Invalid value at `$`: unknown field `extra` of `Drawing`",
+    );
+    test_err(
+        &schema,
+        json!({"name": "", "shapes": [], "id": [0]}),
+        "Invalid value at `$.id`: length 1 of `[u8; 2]` is out of 2..=2 range",
+    );
+
+    let schema = BorshSchemaContainer::for_type::<(i8, u16, core::num::NonZeroU8)>();
+    test_err(
+        &schema,
+        json!([-129, 0, 1]),
+        "Invalid value at `$[0]`: -129 is out of range of `i8`",
+    );
+    test_err(
+        &schema,
+        json!([0, -1, 1]),
+        "Invalid value at `$[1]`: -1 is out of range of `u16`",
+    );
+    test_err(
+        &schema,
+        json!([0, 1.5, 1]),
+        "Invalid value at `$[1]`: expected an integer for `u16`, found number",
+    );
+    test_err(
+        &schema,
+        json!([0, 0, 0]),
+        "Invalid value at `$[2]`: 0 is out of range of `NonZeroU8`",
+    );
+    test_err(
+        &schema,
+        json!([0, 0]),
+        "Invalid value at `$`: expected 3 elements for `(i8, u16, NonZeroU8)`, found 2",
+    );
+
+    let schema = BorshSchemaContainer::for_type::<f64>();
+    let err = schema
+        .deserialize_json(&f64::INFINITY.to_le_bytes())
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid value at `$`: inf is not representable in JSON"
+    );
+}
@@ -0,0 +1,211 @@
+use crate::common_macro::schema_imports::*;
+use serde_json::json;
+
+fn json_schema<T: BorshSchema + ?Sized>() -> serde_json::Value {
+    BorshSchemaContainer::for_type::<T>().to_json_schema().unwrap()
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+enum Shape {
+    Empty,
+    Circle { radius: f32 },
+    Polygon(Vec<Shape>),
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+struct Drawing {
+    name: String,
+    shapes: Vec<Shape>,
+    id: [u8; 2],
+    #[borsh(length = u8)]
+    tags: Vec<u16>,
+    #[borsh(max_len = 3)]
+    small: Vec<i8>,
+}
+
+#[test]
+fn json_schema_primitives() {
+    assert_eq!(
+        json_schema::<u16>(),
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "integer",
+            "minimum": 0,
+            "maximum": 65535,
+        })
+    );
+    assert_eq!(
+        json_schema::<i64>()["minimum"],
+        json!(i64::MIN),
+    );
+    assert_eq!(
+        json_schema::<core::num::NonZeroI8>(),
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "integer",
+            "minimum": -128,
+            "maximum": 127,
+            "not": {"const": 0},
+        })
+    );
+    assert_eq!(
+        json_schema::<u128>()["anyOf"],
+        json!([
+            {"type": "integer", "minimum": 0, "maximum": u64::MAX},
+            {"type": "string", "pattern": "^[0-9]+$"},
+        ])
+    );
+    assert_eq!(json_schema::<bool>()["type"], "boolean");
+    assert_eq!(json_schema::<f64>()["type"], "number");
+    assert_eq!(json_schema::<()>()["type"], "null");
+    assert_eq!(
+        json_schema::<borsh::endian::BigEndian<i16>>()["maximum"],
+        json!(i16::MAX)
+    );
+    assert_eq!(
+        json_schema::<borsh::varint::VarU32>(),
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$ref": "#/$defs/VarU32",
+            "$defs": {
+                "VarU32": {"type": "integer", "minimum": 0, "maximum": u32::MAX},
+            },
+        })
+    );
+}
+
+#[test]
+fn json_schema_derived() {
+    let u8_schema = json!({"type": "integer", "minimum": 0, "maximum": 255});
+    assert_eq!(
+        json_schema::<Drawing>(),
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$ref": "#/$defs/Drawing",
+            "$defs": {
+                "Drawing": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"$ref": "#/$defs/String"},
+                        "shapes": {"$ref": "#/$defs/Vec%3CShape%3E"},
+                        "id": {"$ref": "#/$defs/%5Bu8%3B%202%5D"},
+                        "tags": {"$ref": "#/$defs/Vec%3Cu16%2C%20length%20%3D%20u8%3E"},
+                        "small": {"$ref": "#/$defs/Vec%3Ci8%2C%20max_len%20%3D%203%3E"},
+                    },
+                    "required": ["name", "shapes", "id", "tags", "small"],
+                    "additionalProperties": false,
+                },
+                "String": {"type": "string", "maxLength": u32::MAX},
+                "Vec<Shape>": {
+                    "type": "array",
+                    "items": {"$ref": "#/$defs/Shape"},
+                    "maxItems": u32::MAX,
+                },
+                "Shape": {
+                    "oneOf": [
+                        {
+                            "type": "object",
+                            "properties": {"Empty": {"$ref": "#/$defs/ShapeEmpty"}},
+                            "required": ["Empty"],
+                            "additionalProperties": false,
+                        },
+                        {"const": "Empty"},
+                        {
+                            "type": "object",
+                            "properties": {"Circle": {"$ref": "#/$defs/ShapeCircle"}},
+                            "required": ["Circle"],
+                            "additionalProperties": false,
+                        },
+                        {
+                            "type": "object",
+                            "properties": {"Polygon": {"$ref": "#/$defs/ShapePolygon"}},
+                            "required": ["Polygon"],
+                            "additionalProperties": false,
+                        },
+                    ],
+                },
+                "ShapeEmpty": {"type": "null"},
+                "ShapeCircle": {
+                    "type": "object",
+                    "properties": {"radius": {"type": "number"}},
+                    "required": ["radius"],
+                    "additionalProperties": false,
+                },
+                "ShapePolygon": {
+                    "type": "array",
+                    "prefixItems": [{"$ref": "#/$defs/Vec%3CShape%3E"}],
+                    "items": false,
+                    "minItems": 1,
+                },
+                "[u8; 2]": {
+                    "type": "array",
+                    "items": u8_schema,
+                    "minItems": 2,
+                    "maxItems": 2,
+                },
+                "Vec<u16, length = u8>": {
+                    "type": "array",
+                    "items": {"type": "integer", "minimum": 0, "maximum": 65535},
+                    "maxItems": 255,
+                },
+                "Vec<i8, max_len = 3>": {
+                    "type": "array",
+                    "items": {"type": "integer", "minimum": -128, "maximum": 127},
+                    "maxItems": 3,
+                },
+            },
+        })
+    );
+}
+
+#[test]
+fn json_schema_varint_length() {
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    struct A {
+        #[borsh(varint)]
+        label: String,
+    }
+
+    let schema = json_schema::<A>();
+    let label = schema["$defs"]["A"]["properties"]["label"]["$ref"]
+        .as_str()
+        .unwrap()
+        .trim_start_matches("#/$defs/")
+        .replace("%3C", "<")
+        .replace("%3E", ">")
+        .replace("%2C", ",")
+        .replace("%20", " ")
+        .replace("%3D", "=");
+    assert_eq!(
+        schema["$defs"][label],
+        json!({"type": "string", "maxLength": u32::MAX})
+    );
+}
+
+#[test]
+fn json_schema_errors() {
+    let schema = BorshSchemaContainer::new(
+        "A".to_string(),
+        schema_map! {"A" => Definition::Tuple { elements: vec!["B".to_string()] }},
+    );
+    assert_eq!(
+        schema.to_json_schema().unwrap_err().to_string(),
+        "missing definition of `B`"
+    );
+
+    let schema = BorshSchemaContainer::new(
+        "A".to_string(),
+        schema_map! {"A" => Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range: 0..=4,
+            elements: "u8".to_string(),
+        }, "u8" => Definition::Primitive(1)},
+    );
+    assert_eq!(
+        schema.to_json_schema().unwrap_err().to_string(),
+        "length of `A` is not determined by the schema"
+    );
+}
@@ -0,0 +1,225 @@
+use crate::common_macro::schema_imports::*;
+
+#[track_caller]
+fn test_ok<T: BorshSchema + ?Sized>(want: usize) {
+    let schema = BorshSchemaContainer::for_type::<T>();
+    assert_eq!(Ok(want), schema.min_serialized_size());
+}
+
+#[track_caller]
+fn test_err<T: BorshSchema + ?Sized>(err: SchemaMinSerializedSizeError) {
+    let schema = BorshSchemaContainer::for_type::<T>();
+    assert_eq!(Err(err), schema.min_serialized_size());
+}
+
+#[track_caller]
+fn test_fixed<T: BorshSchema + ?Sized>(want: Option<usize>) {
+    let schema = BorshSchemaContainer::for_type::<T>();
+    assert_eq!(want, schema.fixed_size());
+    assert_eq!(want.is_some(), schema.is_fixed_size());
+}
+
+#[test]
+fn min_serialized_size_built_in_types() {
+    test_ok::<()>(0);
+    test_ok::<bool>(1);
+    test_ok::<u64>(8);
+    test_ok::<core::num::NonZeroU128>(16);
+
+    test_ok::<core::ops::RangeInclusive<u8>>(2);
+    test_ok::<Option<u64>>(1);
+    test_ok::<Result<u8, usize>>(2);
+    test_ok::<(u8, u32)>(5);
+
+    test_ok::<[u8; 0]>(0);
+    test_ok::<[u16; 16]>(32);
+    test_ok::<[[u16; 4]; 4]>(32);
+
+    test_ok::<Vec<u8>>(4);
+    test_ok::<String>(4);
+    test_ok::<Vec<Vec<u8>>>(4);
+    test_ok::<BTreeMap<u64, [u8; 32]>>(4);
+    test_ok::<borsh::varint::VarU64>(1);
+    assert_eq!(Ok(4), borsh::min_serialized_size::<str>());
+}
+
+#[test]
+fn min_serialized_size_derived_types() {
+    #[derive(BorshSchema)]
+    pub struct Unit;
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Account {
+        #[borsh(length = u8)]
+        keys: Vec<[u8; 32]>,
+        #[borsh(varint)]
+        memo: String,
+        balance: u64,
+        status: Status,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub enum Status {
+        Frozen { reason: String },
+        Active,
+    }
+
+    test_ok::<Unit>(0);
+    test_ok::<Account>(1 + 1 + 8 + 1);
+}
+
+#[test]
+fn min_serialized_size_recursive() {
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Tree {
+        value: u8,
+        children: Vec<Tree>,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub enum List {
+        Cons(u64, Box<List>),
+        Nil,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Even {
+        odd: Option<Box<Odd>>,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Odd {
+        value: u16,
+        even: Even,
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct NoExit(Box<NoExit>);
+
+    test_ok::<Tree>(1 + 4);
+    test_ok::<List>(1);
+    test_ok::<Even>(1);
+    test_ok::<Odd>(2 + 1);
+    test_err::<NoExit>(SchemaMinSerializedSizeError::Recursive);
+    test_err::<(u8, NoExit)>(SchemaMinSerializedSizeError::Recursive);
+    test_ok::<Vec<NoExit>>(4);
+}
+
+#[test]
+fn min_serialized_size_custom_definitions() {
+    let schema = BorshSchemaContainer::new(
+        "Word".to_string(),
+        schema_map! {
+            "Word" => Definition::Enum {
+                tag_width: 0,
+                variants: vec![
+                    (0, "value".to_string(), "u32".to_string()),
+                    (1, "bytes".to_string(), "[u8; 2]".to_string()),
+                ],
+            },
+            "[u8; 2]" => Definition::Sequence {
+                length_width: 0,
+                length_range: 2..=2,
+                elements: "u8".to_string(),
+            },
+            "u32" => Definition::Primitive(4),
+            "u8" => Definition::Primitive(1)
+        },
+    );
+    assert_eq!(Ok(4), schema.min_serialized_size());
+
+    let schema = BorshSchemaContainer::new(
+        "Bounded".to_string(),
+        schema_map! {
+            "Bounded" => Definition::Sequence {
+                length_width: 1,
+                length_range: 2..=4,
+                elements: "u16".to_string(),
+            },
+            "u16" => Definition::Primitive(2)
+        },
+    );
+    assert_eq!(Ok(1 + 2 * 2), schema.min_serialized_size());
+    assert_eq!(None, schema.fixed_size());
+
+    let schema = BorshSchemaContainer::new(
+        "Huge".to_string(),
+        schema_map! {
+            "Huge" => Definition::Sequence {
+                length_width: 0,
+                length_range: u64::MAX..=u64::MAX,
+                elements: "u64".to_string(),
+            },
+            "u64" => Definition::Primitive(8)
+        },
+    );
+    assert_eq!(
+        Err(SchemaMinSerializedSizeError::Overflow),
+        schema.min_serialized_size()
+    );
+    assert_eq!(None, schema.fixed_size());
+
+    let schema = BorshSchemaContainer::new(
+        "Missing".to_string(),
+        schema_map! {
+            "Missing" => Definition::Tuple { elements: vec!["u8".to_string()] }
+        },
+    );
+    assert_eq!(
+        Err(SchemaMinSerializedSizeError::MissingDefinition(
+            "u8".to_string()
+        )),
+        schema.min_serialized_size()
+    );
+    assert_eq!(None, schema.fixed_size());
+}
+
+#[test]
+fn fixed_size() {
+    #[derive(BorshSchema)]
+    pub struct Unit;
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Header {
+        version: u8,
+        hash: [u8; 32],
+        flags: (bool, bool),
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub enum Side {
+        Buy(u64),
+        Sell { price: u32, amount: u32 },
+    }
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    pub struct Tree {
+        children: Vec<Tree>,
+    }
+
+    test_fixed::<()>(Some(0));
+    test_fixed::<Unit>(Some(0));
+    test_fixed::<u128>(Some(16));
+    test_fixed::<[u32; 4]>(Some(16));
+    test_fixed::<(u8, [u16; 2])>(Some(5));
+    test_fixed::<Result<u16, [u8; 2]>>(Some(3));
+    test_fixed::<Header>(Some(1 + 32 + 2));
+    test_fixed::<Side>(Some(1 + 8));
+
+    test_fixed::<Option<u8>>(None);
+    test_fixed::<Result<u8, u16>>(None);
+    test_fixed::<String>(None);
+    test_fixed::<Vec<u8>>(None);
+    test_fixed::<borsh::varint::VarU32>(None);
+    test_fixed::<Tree>(None);
+}
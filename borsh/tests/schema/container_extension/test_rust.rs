@@ -0,0 +1,274 @@
+use crate::common_macro::schema_imports::*;
+use borsh::schema::rust::{generate, Error};
+
+#[allow(dead_code)]
+mod source {
+    use crate::common_macro::schema_imports::*;
+    use borsh::BorshSerialize;
+    use std::collections::{HashSet, VecDeque};
+
+    #[derive(BorshSchema, BorshSerialize)]
+    pub struct Account {
+        pub owner: [u8; 32],
+        pub balance: u128,
+        pub status: Status,
+        pub limits: BTreeMap<String, (u16, bool)>,
+        pub tags: HashSet<String>,
+        pub history: VecDeque<Option<i64>>,
+        pub tree: Option<Tree>,
+        #[borsh(length = u8)]
+        pub keys: Vec<Vec<u8>>,
+        #[borsh(varint, max_len = 16)]
+        pub memo: String,
+        #[borsh(varint)]
+        pub nonce: u64,
+        #[borsh(max_len = 4)]
+        pub backup: Option<Vec<u64>>,
+        pub endian: borsh::endian::BigEndian<u32>,
+        pub non_zero: core::num::NonZeroU16,
+        pub result: Result<u8, String>,
+        pub point: Point,
+        pub unit: Unit,
+        pub config: v2::Config,
+        pub wrapper: Wrapper<u8>,
+    }
+
+    #[derive(BorshSchema, BorshSerialize)]
+    #[borsh(use_discriminant = true)]
+    #[repr(u8)]
+    pub enum Status {
+        Active = 1,
+        Frozen { reason: String } = 3,
+        Closed(u64) = 4,
+    }
+
+    #[derive(BorshSchema, BorshSerialize)]
+    pub struct Tree {
+        pub value: u8,
+        pub children: Vec<Tree>,
+        pub next: Option<Box<Tree>>,
+    }
+
+    #[derive(BorshSchema, BorshSerialize)]
+    pub struct Point(pub i32, pub i32);
+
+    #[derive(BorshSchema, BorshSerialize)]
+    pub struct Unit;
+
+    #[derive(BorshSchema, BorshSerialize)]
+    pub struct Wrapper<T> {
+        pub value: T,
+    }
+
+    pub mod v2 {
+        use crate::common_macro::schema_imports::*;
+        use borsh::BorshSerialize;
+
+        #[derive(BorshSchema, BorshSerialize)]
+        #[borsh(schema(name = "v2::Config"))]
+        pub enum Config {
+            Default,
+            Custom(Vec<f32>),
+        }
+    }
+}
+
+#[allow(dead_code)]
+mod generated {
+    include!("generated/account.rs");
+}
+
+#[test]
+fn rust_round_trip() {
+    let container = BorshSchemaContainer::for_type::<source::Account>();
+    let generated = generate(&container).unwrap();
+    assert_eq!(
+        generated,
+        include_str!("generated/account.rs"),
+        "generated/account.rs is outdated"
+    );
+    assert_eq!(
+        BorshSchemaContainer::for_type::<generated::Account>(),
+        container
+    );
+}
+
+#[test]
+fn rust_value_round_trip() {
+    use borsh::endian::BigEndian;
+    use core::num::NonZeroU16;
+
+    let source = source::Account {
+        owner: [7; 32],
+        balance: u128::MAX - 1,
+        status: source::Status::Frozen {
+            reason: "audit".to_string(),
+        },
+        limits: BTreeMap::from([("daily".to_string(), (500, true))]),
+        tags: vec!["vip".to_string()].into_iter().collect(),
+        history: vec![Some(-3), None].into_iter().collect(),
+        tree: Some(source::Tree {
+            value: 1,
+            children: vec![source::Tree {
+                value: 2,
+                children: vec![],
+                next: None,
+            }],
+            next: Some(Box::new(source::Tree {
+                value: 3,
+                children: vec![],
+                next: None,
+            })),
+        }),
+        keys: vec![vec![1, 2], vec![]],
+        memo: "memo".to_string(),
+        nonce: 300,
+        backup: Some(vec![1, 2, 3]),
+        endian: BigEndian(0x0102_0304),
+        non_zero: NonZeroU16::new(9).unwrap(),
+        result: Err("failed".to_string()),
+        point: source::Point(-1, 1),
+        unit: source::Unit,
+        config: source::v2::Config::Custom(vec![0.5]),
+        wrapper: source::Wrapper { value: 42 },
+    };
+    let generated = generated::Account {
+        owner: [7; 32],
+        balance: u128::MAX - 1,
+        status: generated::Status::Frozen {
+            reason: "audit".to_string(),
+        },
+        limits: BTreeMap::from([("daily".to_string(), (500, true))]),
+        tags: vec!["vip".to_string()].into_iter().collect(),
+        history: vec![Some(-3), None].into_iter().collect(),
+        tree: Some(generated::Tree {
+            value: 1,
+            children: vec![generated::Tree {
+                value: 2,
+                children: vec![],
+                next: Box::new(None),
+            }],
+            next: Box::new(Some(generated::Tree {
+                value: 3,
+                children: vec![],
+                next: Box::new(None),
+            })),
+        }),
+        keys: vec![vec![1, 2], vec![]],
+        memo: "memo".to_string(),
+        nonce: 300,
+        backup: Some(vec![1, 2, 3]),
+        endian: BigEndian(0x0102_0304),
+        non_zero: NonZeroU16::new(9).unwrap(),
+        result: Err("failed".to_string()),
+        point: generated::Point(-1, 1),
+        unit: generated::Unit,
+        config: generated::V2Config::Custom(vec![0.5]),
+        wrapper: generated::WrapperU8 { value: 42 },
+    };
+
+    let bytes = borsh::to_vec(&source).unwrap();
+    assert_eq!(borsh::to_vec(&generated).unwrap(), bytes);
+    assert_eq!(
+        borsh::from_slice::<generated::Account>(&bytes).unwrap(),
+        generated
+    );
+}
+
+#[test]
+fn rust_foreign_schema() {
+    // variants, which aren't defined with structs, and names, which clash with used types
+    let container: BorshSchemaContainer = r#"
+        root Message;
+        enum Message: u8 { Text(String) = 0, Data("Map<u8>") = 1, Vec(Vec) = 2 }
+        struct Vec { type: "(u8, f32)" }
+        type "Map<u8>" = seq<u32> "(u8, f32)";
+        type "(u8, f32)" = (u8, f32);
+        type String = seq<u32> u8;
+        type u8 = primitive(1);
+        type f32 = primitive(4);
+    "#
+    .parse()
+    .unwrap();
+    let generated = generate(&container).unwrap();
+    assert!(generated.contains(
+        "pub enum Message {
+    Text(String),
+    Data(Vec<(u8, f32)>),
+    Vec(Vec2),
+}"
+    ));
+    assert!(generated.contains(
+        "#[borsh(schema(name = \"Vec\"))]
+pub struct Vec2 {
+    pub r#type: (u8, f32),
+}"
+    ));
+}
+
+#[test]
+fn rust_errors() {
+    let container = BorshSchemaContainer::new("A".to_string(), schema_map! {});
+    assert_eq!(
+        generate(&container),
+        Err(Error::MissingDefinition("A".to_string()))
+    );
+
+    let container = BorshSchemaContainer::new(
+        "A".to_string(),
+        schema_map! {
+            "A" => Definition::Struct { fields: Fields::UnnamedFields(vec!["[u8]".to_string()]) },
+            "[u8]" => Definition::Sequence {
+                length_width: 0,
+                length_range: Definition::DEFAULT_LENGTH_RANGE,
+                elements: "u8".to_string(),
+            },
+            "u8" => Definition::Primitive(1)
+        },
+    );
+    assert_eq!(
+        generate(&container),
+        Err(Error::UnsupportedLength("[u8]".to_string()))
+    );
+
+    let container = BorshSchemaContainer::new(
+        "A".to_string(),
+        schema_map! {
+            "A" => Definition::Enum {
+                tag_width: 1,
+                variants: vec![(256, "B".to_string(), "u24".to_string())],
+            },
+            "u24" => Definition::Primitive(3)
+        },
+    );
+    assert_eq!(
+        generate(&container),
+        Err(Error::UnsupportedTag("A".to_string()))
+    );
+
+    let container = BorshSchemaContainer::new(
+        "A".to_string(),
+        schema_map! {
+            "A" => Definition::Struct { fields: Fields::UnnamedFields(vec!["u24".to_string()]) },
+            "u24" => Definition::Primitive(3)
+        },
+    );
+    assert_eq!(
+        generate(&container),
+        Err(Error::UnsupportedPrimitive("u24".to_string()))
+    );
+
+    let container = BorshSchemaContainer::new(
+        "A".to_string(),
+        schema_map! {
+            "A" => Definition::Struct {
+                fields: Fields::NamedFields(vec![("a-b".to_string(), "u8".to_string())])
+            },
+            "u8" => Definition::Primitive(1)
+        },
+    );
+    assert_eq!(
+        generate(&container),
+        Err(Error::UnsupportedName("a-b".to_string()))
+    );
+}
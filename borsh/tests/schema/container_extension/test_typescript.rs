@@ -0,0 +1,119 @@
+use crate::common_macro::schema_imports::*;
+use borsh::schema::typescript::{generate, Error};
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+#[borsh(use_discriminant = true)]
+#[repr(u8)]
+enum Status {
+    Active = 1,
+    Closed = 3,
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+enum Tree {
+    Leaf(u8),
+    Node { children: Vec<Tree>, label: Option<String> },
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+struct Account {
+    owner: [u8; 32],
+    balance: u128,
+    status: Status,
+    limits: BTreeMap<String, (u16, bool)>,
+    tree: Option<Box<Tree>>,
+    endian: borsh::endian::BigEndian<u32>,
+}
+
+#[test]
+fn typescript_account() {
+    let generated = generate(&BorshSchemaContainer::for_type::<Account>()).unwrap();
+    #[cfg(feature = "std")]
+    insta::assert_snapshot!(generated);
+    assert!(generated.contains("  status: Status;\n"));
+    assert!(generated.contains("export const schema: Schema = AccountSchema;\n"));
+}
+
+#[test]
+fn typescript_inline_root() {
+    assert_eq!(
+        generate(&BorshSchemaContainer::for_type::<Vec<Option<u64>>>()).unwrap(),
+        "// Generated by borsh from the schema of `Vec<Option<u64>>`. Do not edit.
+import type { Schema } from 'borsh';
+
+export const schema: Schema = { array: { type: { option: 'u64' } } };
+"
+    );
+}
+
+#[test]
+fn typescript_names() {
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    struct Map<T>(T);
+
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    struct Schema {
+        inner: Map<u8>,
+        #[borsh(length = u32)]
+        items: Vec<()>,
+    }
+
+    let generated = generate(&BorshSchemaContainer::for_type::<Schema>()).unwrap();
+    assert!(generated.contains("export type MapU8 = {\n  0: number;\n};\n"));
+    assert!(generated.contains("export type Schema2 = {\n  inner: MapU8;\n  items: Record<string, never>[];\n};\n"));
+    assert!(generated.contains("export const schema: Schema = Schema2Schema;\n"));
+}
+
+#[test]
+fn typescript_errors() {
+    #[allow(unused)]
+    #[derive(BorshSchema)]
+    struct Short {
+        #[borsh(length = u8)]
+        bytes: Vec<u8>,
+    }
+
+    assert_eq!(
+        generate(&BorshSchemaContainer::for_type::<Short>()),
+        Err(Error::UnsupportedLength("Vec<u8, length = u8>".to_string()))
+    );
+    assert_eq!(
+        generate(&BorshSchemaContainer::for_type::<borsh::varint::VarU32>()),
+        Err(Error::UnsupportedLength("VarU32".to_string()))
+    );
+    assert_eq!(
+        generate(&BorshSchemaContainer::new(
+            "A".to_string(),
+            schema_map! {"A" => Definition::Tuple { elements: vec!["B".to_string()] }}
+        ))
+        .unwrap_err()
+        .to_string(),
+        "`B` lacks definition"
+    );
+    assert_eq!(
+        generate(&BorshSchemaContainer::new(
+            "A".to_string(),
+            schema_map! {"A" => Definition::Enum {
+                tag_width: 2,
+                variants: vec![(0, "B".to_string(), "()".to_string())],
+            }, "()" => Definition::Primitive(0)}
+        )),
+        Err(Error::UnsupportedTag("A".to_string()))
+    );
+    assert_eq!(
+        generate(&BorshSchemaContainer::new(
+            "A".to_string(),
+            schema_map! {"A" => Definition::Sequence {
+                length_width: Definition::DEFAULT_LENGTH_WIDTH,
+                length_range: Definition::DEFAULT_LENGTH_RANGE,
+                elements: "A".to_string(),
+            }}
+        )),
+        Err(Error::UnsupportedRecursion("A".to_string()))
+    );
+}
@@ -11,9 +11,12 @@ struct ConflictingSchema;
 impl BorshSchema for ConflictingSchema {
     #[inline]
     fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        DefinitionsCollector::collect_or_panic(definitions, Self::collect_definitions);
+    }
+    fn collect_definitions(collector: &mut DefinitionsCollector<'_>) {
         let fields = Fields::Empty;
         let def = Definition::Struct { fields };
-        add_definition(Self::declaration(), def, definitions);
+        collector.add_definition(Self::declaration(), def);
     }
     #[inline]
     fn declaration() -> Declaration {
@@ -21,6 +24,20 @@ impl BorshSchema for ConflictingSchema {
     }
 }
 
+/// Implements only `add_definitions_recursively`, so conflicts within it can't be collected.
+struct AddOnlyConflictingSchema;
+
+impl BorshSchema for AddOnlyConflictingSchema {
+    fn add_definitions_recursively(definitions: &mut BTreeMap<Declaration, Definition>) {
+        let fields = Fields::Empty;
+        let def = Definition::Struct { fields };
+        add_definition(Self::declaration(), def, definitions);
+    }
+    fn declaration() -> Declaration {
+        "i64".into()
+    }
+}
+
 #[test]
 #[should_panic(expected = "Redefining type schema for i64")]
 fn test_conflict() {
@@ -164,3 +181,189 @@ fn test_implicit_conflict_tuple() {
     <(i64, u8) as borsh::BorshSchema>::add_definitions_recursively(&mut defs);
     <(ConflictingSchema, u8) as borsh::BorshSchema>::add_definitions_recursively(&mut defs);
 }
+
+#[test]
+fn test_try_add_definition_conflict() {
+    let mut defs = Default::default();
+    <i64 as borsh::BorshSchema>::add_definitions_recursively(&mut defs);
+    let err = borsh::schema::try_add_definition(
+        "i64".to_string(),
+        Definition::Struct {
+            fields: Fields::Empty,
+        },
+        &mut defs,
+    )
+    .unwrap_err();
+    assert_eq!(err.declaration, "i64");
+    assert_eq!(*err.existing, Definition::Primitive(8));
+    assert_eq!(
+        *err.conflicting,
+        Definition::Struct {
+            fields: Fields::Empty
+        }
+    );
+    assert!(err
+        .to_string()
+        .starts_with("Redefining type schema for i64"));
+    assert_eq!(defs.get("i64"), Some(&Definition::Primitive(8)));
+
+    assert!(borsh::schema::try_add_definition(
+        "i64".to_string(),
+        Definition::Primitive(8),
+        &mut defs
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_try_for_type_conflict() {
+    let err = BorshSchemaContainer::try_for_type::<(Vec<i64>, ConflictingSchema)>().unwrap_err();
+    assert_eq!(err.declaration, "i64");
+    assert_eq!(*err.existing, Definition::Primitive(8));
+
+    let err = BorshSchemaContainer::try_for_type::<SelfConflictingEnum>().unwrap_err();
+    assert_eq!(err.declaration, "i64");
+
+    let container = BorshSchemaContainer::try_for_type::<GenericEnum<i64>>().unwrap();
+    assert_eq!(
+        container,
+        BorshSchemaContainer::for_type::<GenericEnum<i64>>()
+    );
+}
+
+#[test]
+fn test_definitions_collector() {
+    let mut defs = Default::default();
+    let mut collector = DefinitionsCollector::new(&mut defs);
+    <Vec<i64> as borsh::BorshSchema>::collect_definitions(&mut collector);
+    <Option<ConflictingSchema> as borsh::BorshSchema>::collect_definitions(&mut collector);
+    <[ConflictingSchema; 2] as borsh::BorshSchema>::collect_definitions(&mut collector);
+    assert_eq!(collector.conflicts().len(), 2);
+    assert_eq!(
+        collector.definitions().get("i64"),
+        Some(&Definition::Primitive(8))
+    );
+    let err = collector.finish().unwrap_err();
+    assert_eq!(err.declaration, "i64");
+    assert_eq!(
+        *err.conflicting,
+        Definition::Struct {
+            fields: Fields::Empty
+        }
+    );
+    assert!(defs.contains_key("Option<i64>"));
+}
+
+#[test]
+fn test_try_for_type_add_only_impl() {
+    // `collect_definitions` of `AddOnlyConflictingSchema` falls back to `add_definitions_recursively`
+    let err = BorshSchemaContainer::try_for_type::<(i64, AddOnlyConflictingSchema)>().unwrap_err();
+    assert_eq!(err.declaration, "i64");
+    assert_eq!(*err.existing, Definition::Primitive(8));
+    assert_eq!(
+        *err.conflicting,
+        Definition::Struct {
+            fields: Fields::Empty
+        }
+    );
+
+    let err =
+        BorshSchemaContainer::try_for_type::<(AddOnlyConflictingSchema, Vec<i64>)>().unwrap_err();
+    assert_eq!(err.declaration, "i64");
+    assert_eq!(
+        *err.existing,
+        Definition::Struct {
+            fields: Fields::Empty
+        }
+    );
+}
+
+mod a {
+    use crate::common_macro::schema_imports::*;
+
+    #[allow(unused)]
+    #[derive(borsh::BorshSchema)]
+    pub struct Config {
+        pub retries: u8,
+    }
+
+    #[allow(unused)]
+    #[derive(borsh::BorshSchema)]
+    #[borsh(schema(name = "a::Mode"))]
+    pub enum Mode {
+        Fast,
+        Slow { delay: u32 },
+    }
+}
+
+mod b {
+    use crate::common_macro::schema_imports::*;
+
+    #[allow(unused)]
+    #[derive(borsh::BorshSchema)]
+    #[borsh(schema(name = "b::Config"))]
+    pub struct Config {
+        pub retries: u16,
+    }
+
+    #[allow(unused)]
+    #[derive(borsh::BorshSchema)]
+    pub enum Mode {
+        Fast,
+        Slow { delay: u64 },
+    }
+}
+
+#[allow(unused)]
+#[derive(borsh::BorshSchema)]
+struct Settings {
+    a: a::Config,
+    b: b::Config,
+    a_mode: a::Mode,
+    b_mode: b::Mode,
+}
+
+#[test]
+fn test_schema_name_resolves_conflict() {
+    let container = BorshSchemaContainer::try_for_type::<Settings>().unwrap();
+    assert_eq!(
+        container.get_definition("Settings"),
+        Some(&Definition::Struct {
+            fields: Fields::NamedFields(vec![
+                ("a".to_string(), "Config".to_string()),
+                ("b".to_string(), "b::Config".to_string()),
+                ("a_mode".to_string(), "a::Mode".to_string()),
+                ("b_mode".to_string(), "Mode".to_string()),
+            ])
+        })
+    );
+    assert_eq!(
+        container.get_definition("b::Config"),
+        Some(&Definition::Struct {
+            fields: Fields::NamedFields(vec![("retries".to_string(), "u16".to_string())])
+        })
+    );
+    assert_eq!(
+        container.get_definition("a::Mode"),
+        Some(&Definition::Enum {
+            tag_width: 1,
+            variants: vec![
+                (0, "Fast".to_string(), "a::ModeFast".to_string()),
+                (1, "Slow".to_string(), "a::ModeSlow".to_string()),
+            ]
+        })
+    );
+    assert_eq!(
+        container.get_definition("a::ModeSlow"),
+        Some(&Definition::Struct {
+            fields: Fields::NamedFields(vec![("delay".to_string(), "u32".to_string())])
+        })
+    );
+    assert_eq!(
+        container.get_definition("ModeSlow"),
+        Some(&Definition::Struct {
+            fields: Fields::NamedFields(vec![("delay".to_string(), "u64".to_string())])
+        })
+    );
+    assert!(container.validate().is_ok());
+}
@@ -0,0 +1,68 @@
+use crate::common_macro::schema_imports::*;
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+struct Header {
+    #[borsh(big_endian)]
+    magic: u32,
+    #[borsh(big_endian, length = u8)]
+    offsets: Vec<i16>,
+    flags: u16,
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+#[borsh(big_endian)]
+enum Command {
+    Stop,
+    Speed(f32),
+}
+
+#[test]
+pub fn big_endian_fields() {
+    let mut defs = Default::default();
+    Header::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "Header" => Definition::Struct {
+            fields: Fields::NamedFields(vec![
+                ("magic".to_string(), "BigEndian<u32>".to_string()),
+                ("offsets".to_string(), "Vec<BigEndian<i16>, length = u8>".to_string()),
+                ("flags".to_string(), "u16".to_string())
+            ])
+        },
+        "BigEndian<u32>" => Definition::Primitive(4),
+        "Vec<BigEndian<i16>, length = u8>" => Definition::Sequence {
+            length_width: 1,
+            length_range: 0..=255,
+            elements: "BigEndian<i16>".to_string()
+        },
+        "BigEndian<i16>" => Definition::Primitive(2),
+        "u16" => Definition::Primitive(2)
+        },
+        defs
+    );
+}
+
+#[test]
+pub fn big_endian_enum() {
+    let mut defs = Default::default();
+    Command::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "Command" => Definition::Enum {
+            tag_width: 1,
+            variants: vec![
+                (0, "Stop".to_string(), "CommandStop".to_string()),
+                (1, "Speed".to_string(), "CommandSpeed".to_string())
+            ]
+        },
+        "CommandStop" => Definition::Struct { fields: Fields::Empty },
+        "CommandSpeed" => Definition::Struct {
+            fields: Fields::UnnamedFields(vec!["BigEndian<f32>".to_string()])
+        },
+        "BigEndian<f32>" => Definition::Primitive(4)
+        },
+        defs
+    );
+}
@@ -0,0 +1,106 @@
+use crate::common_macro::schema_imports::*;
+use borsh::bounded::{BoundedString, BoundedVec};
+
+#[test]
+pub fn bounded_vec() {
+    assert_eq!("BoundedVec<u16, 1, 3>", BoundedVec::<u16, 1, 3>::declaration());
+    let mut defs = Default::default();
+    BoundedVec::<u16, 1, 3>::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "BoundedVec<u16, 1, 3>" => Definition::Sequence {
+            length_width: Definition::DEFAULT_LENGTH_WIDTH,
+            length_range: 1..=3,
+            elements: "u16".to_string()
+        },
+        "u16" => Definition::Primitive(2)
+        },
+        defs
+    );
+    assert_eq!(
+        Ok(4 + 3 * 2),
+        borsh::max_serialized_size::<BoundedVec<u16, 1, 3>>()
+    );
+    assert_eq!(
+        Ok(()),
+        borsh::schema_container_of::<BoundedVec<u16, 1, 3>>().validate()
+    );
+}
+
+#[test]
+pub fn bounded_string() {
+    assert_eq!("BoundedString<0, 32>", BoundedString::<0, 32>::declaration());
+    let mut defs = Default::default();
+    BoundedString::<0, 32>::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "BoundedString<0, 32>" => Definition::Sequence {
+            length_width: Definition::DEFAULT_LENGTH_WIDTH,
+            length_range: 0..=32,
+            elements: "u8".to_string()
+        },
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+    assert_eq!(
+        Ok(4 + 32),
+        borsh::max_serialized_size::<BoundedString<0, 32>>()
+    );
+}
+
+#[test]
+pub fn struct_with_max_len_fields() {
+    #[allow(unused)]
+    #[derive(borsh::BorshSchema)]
+    struct A {
+        #[borsh(max_len = 3)]
+        items: Vec<Vec<u8>>,
+        #[borsh(max_len = 16, length = u8)]
+        name: String,
+        #[borsh(max_len = 2, varint)]
+        bytes: Vec<u8>,
+    }
+    let mut defs = Default::default();
+    A::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "A" => Definition::Struct{ fields: Fields::NamedFields(vec![
+            ("items".to_string(), "Vec<Vec<u8>, max_len = 3>".to_string()),
+            ("name".to_string(), "String<length = u8, max_len = 16>".to_string()),
+            ("bytes".to_string(), "Vec<u8, length = varint, max_len = 2>".to_string()),
+        ])},
+        "Vec<Vec<u8>, max_len = 3>" => Definition::Sequence {
+            length_width: Definition::DEFAULT_LENGTH_WIDTH,
+            length_range: 0..=3,
+            elements: "Vec<u8>".to_string()
+        },
+        "Vec<u8>" => Definition::Sequence {
+            length_width: Definition::DEFAULT_LENGTH_WIDTH,
+            length_range: Definition::DEFAULT_LENGTH_RANGE,
+            elements: "u8".to_string()
+        },
+        "String<length = u8, max_len = 16>" => Definition::Sequence {
+            length_width: 1,
+            length_range: 0..=16,
+            elements: "u8".to_string()
+        },
+        "Vec<u8, length = varint, max_len = 2>" => Definition::Tuple {
+            elements: vec!["VarU32".to_string(), "[u8; ..=2]".to_string()]
+        },
+        "[u8; ..=2]" => Definition::Sequence {
+            length_width: 0,
+            length_range: 0..=2,
+            elements: "u8".to_string()
+        },
+        "VarU32" => Definition::Sequence {
+            length_width: 0,
+            length_range: 1..=5,
+            elements: "u8".to_string()
+        },
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+    assert_eq!(Ok(()), borsh::schema_container_of::<A>().validate());
+}
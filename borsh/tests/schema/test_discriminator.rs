@@ -0,0 +1,70 @@
+use crate::common_macro::schema_imports::*;
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+#[borsh(discriminator = [0xca, 0xfe])]
+struct Counter {
+    value: u8,
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+#[borsh(discriminator = [1])]
+enum Instruction {
+    Increment,
+    Set(u8),
+}
+
+#[test]
+pub fn discriminator_struct() {
+    let mut defs = Default::default();
+    Counter::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "Counter" => Definition::Tuple {
+            elements: vec!["Discriminator<[202, 254]>".to_string(), "CounterBody".to_string()]
+        },
+        "Discriminator<[202, 254]>" => Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range: 2..=2,
+            elements: "u8".to_string()
+        },
+        "CounterBody" => Definition::Struct {
+            fields: Fields::NamedFields(vec![("value".to_string(), "u8".to_string())])
+        },
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+}
+
+#[test]
+pub fn discriminator_enum() {
+    let mut defs = Default::default();
+    Instruction::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "Instruction" => Definition::Tuple {
+            elements: vec!["Discriminator<[1]>".to_string(), "InstructionBody".to_string()]
+        },
+        "Discriminator<[1]>" => Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range: 1..=1,
+            elements: "u8".to_string()
+        },
+        "InstructionBody" => Definition::Enum {
+            tag_width: 1,
+            variants: vec![
+                (0, "Increment".to_string(), "InstructionIncrement".to_string()),
+                (1, "Set".to_string(), "InstructionSet".to_string())
+            ]
+        },
+        "InstructionIncrement" => Definition::Struct { fields: Fields::Empty },
+        "InstructionSet" => Definition::Struct {
+            fields: Fields::UnnamedFields(vec!["u8".to_string()])
+        },
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+}
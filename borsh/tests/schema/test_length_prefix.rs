@@ -0,0 +1,134 @@
+use crate::common_macro::schema_imports::*;
+
+#[test]
+pub fn struct_with_length_prefixes() {
+    #[allow(unused)]
+    #[derive(borsh::BorshSchema)]
+    struct A {
+        #[borsh(length = u8)]
+        bytes: Vec<u8>,
+        #[borsh(length = u16)]
+        name: String,
+        #[borsh(length = u32)]
+        same_as_default: Vec<u8>,
+    }
+    let mut defs = Default::default();
+    A::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "A" => Definition::Struct{ fields: Fields::NamedFields(vec![
+            ("bytes".to_string(), "Vec<u8, length = u8>".to_string()),
+            ("name".to_string(), "String<length = u16>".to_string()),
+            ("same_as_default".to_string(), "Vec<u8>".to_string()),
+        ])},
+        "Vec<u8, length = u8>" => Definition::Sequence {
+            length_width: 1,
+            length_range: 0..=255,
+            elements: "u8".to_string()
+        },
+        "String<length = u16>" => Definition::Sequence {
+            length_width: 2,
+            length_range: 0..=65535,
+            elements: "u8".to_string()
+        },
+        "Vec<u8>" => Definition::Sequence {
+            length_width: Definition::DEFAULT_LENGTH_WIDTH,
+            length_range: Definition::DEFAULT_LENGTH_RANGE,
+            elements: "u8".to_string()
+        },
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+}
+
+#[test]
+pub fn nested_length_prefixes() {
+    #[allow(unused)]
+    #[derive(borsh::BorshSchema)]
+    struct A {
+        #[borsh(length = u8)]
+        map: BTreeMap<String, Vec<u64>>,
+        #[borsh(length = u8)]
+        maybe: Option<Vec<u64>>,
+    }
+    let mut defs = Default::default();
+    A::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "A" => Definition::Struct{ fields: Fields::NamedFields(vec![
+            ("map".to_string(), "BTreeMap<String<length = u8>, Vec<u64, length = u8>, length = u8>".to_string()),
+            ("maybe".to_string(), "Option<Vec<u64, length = u8>>".to_string()),
+        ])},
+        "BTreeMap<String<length = u8>, Vec<u64, length = u8>, length = u8>" => Definition::Sequence {
+            length_width: 1,
+            length_range: 0..=255,
+            elements: "(String<length = u8>, Vec<u64, length = u8>)".to_string()
+        },
+        "(String<length = u8>, Vec<u64, length = u8>)" => Definition::Tuple {
+            elements: vec!["String<length = u8>".to_string(), "Vec<u64, length = u8>".to_string()]
+        },
+        "String<length = u8>" => Definition::Sequence {
+            length_width: 1,
+            length_range: 0..=255,
+            elements: "u8".to_string()
+        },
+        "Vec<u64, length = u8>" => Definition::Sequence {
+            length_width: 1,
+            length_range: 0..=255,
+            elements: "u64".to_string()
+        },
+        "Option<Vec<u64, length = u8>>" => Definition::Enum {
+            tag_width: 1,
+            variants: vec![
+                (0, "None".to_string(), "()".to_string()),
+                (1, "Some".to_string(), "Vec<u64, length = u8>".to_string()),
+            ]
+        },
+        "()" => Definition::Primitive(0),
+        "u64" => Definition::Primitive(8),
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+}
+
+#[test]
+pub fn enum_with_length_prefixes() {
+    #[allow(unused)]
+    #[derive(borsh::BorshSchema)]
+    enum E {
+        Bytes(#[borsh(length = u8)] Vec<u8>),
+    }
+    let mut defs = Default::default();
+    E::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "E" => Definition::Enum {
+            tag_width: 1,
+            variants: vec![(0, "Bytes".to_string(), "EBytes".to_string())]
+        },
+        "EBytes" => Definition::Struct{ fields: Fields::UnnamedFields(vec![
+            "Vec<u8, length = u8>".to_string(),
+        ])},
+        "Vec<u8, length = u8>" => Definition::Sequence {
+            length_width: 1,
+            length_range: 0..=255,
+            elements: "u8".to_string()
+        },
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+}
+
+#[test]
+pub fn max_size_with_length_prefixes() {
+    #[allow(unused)]
+    #[derive(borsh::BorshSchema)]
+    struct A {
+        #[borsh(length = u8)]
+        bytes: Vec<u8>,
+    }
+    assert_eq!(Ok(1 + 255), borsh::max_serialized_size::<A>());
+}
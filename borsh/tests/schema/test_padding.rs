@@ -0,0 +1,102 @@
+use crate::common_macro::schema_imports::*;
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+#[borsh(reserved_after = 2)]
+struct Header {
+    version: u8,
+    #[borsh(pad = 3)]
+    length: u32,
+}
+
+#[derive(BorshSchema)]
+#[borsh(pad_after = 4)]
+struct Filler;
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+enum Frame {
+    Data(u8, #[borsh(pad = 1)] u16),
+}
+
+#[test]
+pub fn padding_struct() {
+    let mut defs = Default::default();
+    Header::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "Header" => Definition::Struct {
+            fields: Fields::NamedFields(vec![
+                ("version".to_string(), "u8".to_string()),
+                ("_pad_length".to_string(), "[u8; 3]".to_string()),
+                ("length".to_string(), "u32".to_string()),
+                ("_reserved_after".to_string(), "[u8; 2]".to_string())
+            ])
+        },
+        "[u8; 3]" => Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range: 3..=3,
+            elements: "u8".to_string()
+        },
+        "[u8; 2]" => Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range: 2..=2,
+            elements: "u8".to_string()
+        },
+        "u8" => Definition::Primitive(1),
+        "u32" => Definition::Primitive(4)
+        },
+        defs
+    );
+    assert_eq!(Ok(10), borsh::max_serialized_size::<Header>());
+}
+
+#[test]
+pub fn padding_unit_struct() {
+    let mut defs = Default::default();
+    Filler::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "Filler" => Definition::Struct {
+            fields: Fields::UnnamedFields(vec!["[u8; 4]".to_string()])
+        },
+        "[u8; 4]" => Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range: 4..=4,
+            elements: "u8".to_string()
+        },
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+    assert_eq!(Ok(4), borsh::max_serialized_size::<Filler>());
+}
+
+#[test]
+pub fn padding_enum() {
+    let mut defs = Default::default();
+    Frame::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "Frame" => Definition::Enum {
+            tag_width: 1,
+            variants: vec![(0, "Data".to_string(), "FrameData".to_string())]
+        },
+        "FrameData" => Definition::Struct {
+            fields: Fields::UnnamedFields(vec![
+                "u8".to_string(),
+                "[u8; 1]".to_string(),
+                "u16".to_string()
+            ])
+        },
+        "[u8; 1]" => Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range: 1..=1,
+            elements: "u8".to_string()
+        },
+        "u8" => Definition::Primitive(1),
+        "u16" => Definition::Primitive(2)
+        },
+        defs
+    );
+}
@@ -0,0 +1,109 @@
+use crate::common_macro::schema_imports::*;
+
+/// Types, which are assumed to be defined in another crate
+mod other_crate {
+    use crate::common_macro::schema_imports::*;
+
+    #[allow(unused)]
+    pub struct Config {
+        pub name: String,
+        pub retries: u8,
+    }
+
+    #[allow(unused)]
+    pub enum Shape<T> {
+        Dot,
+        Line(T, T),
+    }
+}
+
+#[allow(dead_code)]
+#[derive(BorshSchema)]
+#[borsh(remote = "other_crate::Config")]
+struct ConfigDef {
+    name: String,
+    retries: u8,
+}
+
+#[allow(dead_code)]
+#[derive(BorshSchema)]
+#[borsh(remote = "other_crate::Shape")]
+enum ShapeDef<T> {
+    Dot,
+    Line(T, T),
+}
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+struct Scene {
+    #[borsh(schema(with_funcs(
+        declaration = "ConfigDef::declaration",
+        definitions = "ConfigDef::add_definitions_recursively"
+    )))]
+    config: other_crate::Config,
+    #[borsh(schema(with_funcs(
+        declaration = "ShapeDef::<u16>::declaration",
+        definitions = "ShapeDef::<u16>::add_definitions_recursively"
+    )))]
+    shape: other_crate::Shape<u16>,
+}
+
+#[test]
+pub fn remote_struct() {
+    assert_eq!("Config", ConfigDef::declaration());
+    let mut defs = Default::default();
+    ConfigDef::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "Config" => Definition::Struct {
+            fields: Fields::NamedFields(vec![
+                ("name".to_string(), "String".to_string()),
+                ("retries".to_string(), "u8".to_string())
+            ])
+        },
+        "String" => Definition::Sequence {
+            length_width: Definition::DEFAULT_LENGTH_WIDTH,
+            length_range: Definition::DEFAULT_LENGTH_RANGE,
+            elements: "u8".to_string()
+        },
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+}
+
+#[test]
+pub fn remote_fields() {
+    assert_eq!("Shape<u16>", ShapeDef::<u16>::declaration());
+    let mut defs = Default::default();
+    Scene::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        defs.get("Scene"),
+        Some(&Definition::Struct {
+            fields: Fields::NamedFields(vec![
+                ("config".to_string(), "Config".to_string()),
+                ("shape".to_string(), "Shape<u16>".to_string())
+            ])
+        })
+    );
+    assert_eq!(
+        defs.get("Shape<u16>"),
+        Some(&Definition::Enum {
+            tag_width: 1,
+            variants: vec![
+                (0, "Dot".to_string(), "ShapeDot".to_string()),
+                (1, "Line".to_string(), "ShapeLine<u16>".to_string())
+            ]
+        })
+    );
+    assert_eq!(
+        defs.get("ShapeLine<u16>"),
+        Some(&Definition::Struct {
+            fields: Fields::UnnamedFields(vec!["u16".to_string(), "u16".to_string()])
+        })
+    );
+    assert_eq!(
+        Ok(()),
+        borsh::schema_container_of::<Scene>().validate()
+    );
+}
@@ -0,0 +1,69 @@
+use crate::common_macro::schema_imports::*;
+
+mod third_party_impl {
+    use borsh::BorshSerialize;
+
+    pub(super) fn serialize<K: BorshSerialize, V: BorshSerialize, W: borsh::io::Write>(
+        obj: &super::ThirdParty<K, V>,
+        writer: &mut W,
+    ) -> borsh::io::Result<()> {
+        obj.0.serialize(writer)
+    }
+}
+
+#[allow(unused)]
+struct ThirdParty<K, V>(pub BTreeMap<K, V>);
+
+#[allow(unused)]
+#[derive(borsh::BorshSerialize, BorshSchema)]
+#[borsh(big_endian)]
+struct A<K, V> {
+    #[borsh(
+        serialize_with = "third_party_impl::serialize",
+        schema(as = "BTreeMap<K, V>")
+    )]
+    x: ThirdParty<K, V>,
+    #[borsh(schema(as = "[u8; 2]"))]
+    y: u16,
+    z: u16,
+}
+
+#[test]
+pub fn schema_as() {
+    assert_eq!("A<u64, String>".to_string(), <A<u64, String>>::declaration());
+    let mut defs = Default::default();
+    <A<u64, String>>::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "A<u64, String>" => Definition::Struct {
+            fields: Fields::NamedFields(vec![
+                ("x".to_string(), "BTreeMap<u64, String>".to_string()),
+                ("y".to_string(), "[u8; 2]".to_string()),
+                ("z".to_string(), "BigEndian<u16>".to_string())
+            ])
+        },
+        "BTreeMap<u64, String>" => Definition::Sequence {
+            length_width: Definition::DEFAULT_LENGTH_WIDTH,
+            length_range: Definition::DEFAULT_LENGTH_RANGE,
+            elements: "(u64, String)".to_string()
+        },
+        "(u64, String)" => Definition::Tuple {
+            elements: vec!["u64".to_string(), "String".to_string()]
+        },
+        "String" => Definition::Sequence {
+            length_width: Definition::DEFAULT_LENGTH_WIDTH,
+            length_range: Definition::DEFAULT_LENGTH_RANGE,
+            elements: "u8".to_string()
+        },
+        "[u8; 2]" => Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range: 2..=2,
+            elements: "u8".to_string()
+        },
+        "BigEndian<u16>" => Definition::Primitive(2),
+        "u64" => Definition::Primitive(8),
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+}
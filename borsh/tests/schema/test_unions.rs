@@ -0,0 +1,36 @@
+use crate::common_macro::schema_imports::*;
+
+#[test]
+pub fn simple_union() {
+    #[allow(unused)]
+    #[derive(borsh::BorshSchema)]
+    #[repr(C)]
+    union A {
+        value: u32,
+        bytes: [u8; 4],
+    }
+    assert_eq!("A".to_string(), A::declaration());
+    let mut defs = Default::default();
+    A::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "A" => Definition::Enum {
+            tag_width: 0,
+            variants: vec![
+                (0, "value".to_string(), "u32".to_string()),
+                (1, "bytes".to_string(), "[u8; 4]".to_string())
+            ]
+        },
+        "u32" => Definition::Primitive(4),
+        "[u8; 4]" => Definition::Sequence {
+            length_width: Definition::ARRAY_LENGTH_WIDTH,
+            length_range: 4..=4,
+            elements: "u8".to_string()
+        },
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+    assert_eq!(Ok(4), borsh::max_serialized_size::<A>());
+    assert_eq!(Ok(()), borsh::schema_container_of::<A>().validate());
+}
@@ -0,0 +1,68 @@
+use crate::common_macro::schema_imports::*;
+use borsh::varint::{VarI64, VarU32};
+
+#[test]
+pub fn varint_types() {
+    assert_eq!("VarU32", VarU32::declaration());
+    let mut defs = Default::default();
+    VarU32::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "VarU32" => Definition::Sequence {
+            length_width: 0,
+            length_range: 1..=5,
+            elements: "u8".to_string()
+        },
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+    assert_eq!(Ok(10), borsh::max_serialized_size::<VarI64>());
+}
+
+#[test]
+pub fn struct_with_varint_fields() {
+    #[allow(unused)]
+    #[derive(borsh::BorshSchema)]
+    struct A {
+        #[borsh(varint)]
+        x: i64,
+        #[borsh(varint)]
+        bytes: Vec<u8>,
+    }
+    let mut defs = Default::default();
+    A::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "A" => Definition::Struct{ fields: Fields::NamedFields(vec![
+            ("x".to_string(), "VarI64".to_string()),
+            ("bytes".to_string(), "Vec<u8, length = varint>".to_string()),
+        ])},
+        "VarI64" => Definition::Sequence {
+            length_width: 0,
+            length_range: 1..=10,
+            elements: "u8".to_string()
+        },
+        "Vec<u8, length = varint>" => Definition::Tuple {
+            elements: vec!["VarU32".to_string(), "[u8]".to_string()]
+        },
+        "[u8]" => Definition::Sequence {
+            length_width: 0,
+            length_range: Definition::DEFAULT_LENGTH_RANGE,
+            elements: "u8".to_string()
+        },
+        "VarU32" => Definition::Sequence {
+            length_width: 0,
+            length_range: 1..=5,
+            elements: "u8".to_string()
+        },
+        "u8" => Definition::Primitive(1)
+        },
+        defs
+    );
+    assert_eq!(
+        Ok(10 + 5 + u32::MAX as usize),
+        borsh::max_serialized_size::<A>()
+    );
+    assert_eq!(Ok(()), borsh::schema_container_of::<A>().validate());
+}
@@ -0,0 +1,30 @@
+use crate::common_macro::schema_imports::*;
+
+#[allow(unused)]
+#[derive(BorshSchema)]
+struct Record {
+    #[borsh(with = "borsh::with::big_endian::u32")]
+    id: u32,
+    #[borsh(with = "borsh::with::fixed_point::e6")]
+    price: f64,
+}
+
+#[test]
+pub fn with_modules() {
+    let mut defs = Default::default();
+    Record::add_definitions_recursively(&mut defs);
+    assert_eq!(
+        schema_map! {
+        "Record" => Definition::Struct {
+            fields: Fields::NamedFields(vec![
+                ("id".to_string(), "BigEndian<u32>".to_string()),
+                ("price".to_string(), "i64".to_string())
+            ])
+        },
+        "BigEndian<u32>" => Definition::Primitive(4),
+        "i64" => Definition::Primitive(8)
+        },
+        defs
+    );
+    assert_eq!(Ok(12), borsh::max_serialized_size::<Record>());
+}
@@ -35,6 +35,7 @@ mod roundtrip {
     #[cfg(feature = "ascii")]
     mod test_ascii_strings;
     mod test_arrays;
+    mod test_bounded;
     mod test_vecs;
     mod test_tuple;
     mod test_primitives;
@@ -65,6 +66,20 @@ mod roundtrip {
         mod test_recursive_enums;
         mod test_serde_with_third_party;
         mod test_enum_discriminants;
+        mod test_length_prefix;
+        mod test_varint;
+        mod test_max_len;
+        mod test_unions;
+        mod test_skip_default;
+        mod test_enum_other;
+        mod test_enum_ext;
+        mod test_remote;
+        mod test_with;
+        mod test_big_endian;
+        mod test_discriminator;
+        mod test_padding;
+        mod test_slice_deserialize;
+        mod test_context;
         #[cfg(feature = "bytes")]
         mod test_ultimate_many_features_combined;
         #[cfg(feature = "bson")]
@@ -105,6 +120,16 @@ mod schema {
     mod test_recursive_enums;
     mod test_schema_with_third_party; // NOTE: this test corresponds to `roundtrip::test_serde_with_third_party`
     mod test_enum_discriminants;
+    mod test_length_prefix;
+    mod test_varint;
+    mod test_unions;
+    mod test_remote;
+    mod test_with;
+    mod test_big_endian;
+    mod test_discriminator;
+    mod test_padding;
+    mod test_schema_as;
+    mod test_bounded; // NOTE: this test also covers `roundtrip::requires_derive_category::test_max_len`
     // mod test_ultimate_many_features_combined;  // NOTE: there's nothing corresponding to `roundtrip::test_ultimate_many_features_combined`
     // mod test_bson_object_ids; // NOTE: there's nothing corresponding to `roundtrip::test_bson_object_ids`
     mod schema_conflict {
@@ -114,6 +139,18 @@ mod schema {
     mod container_extension {
         mod test_schema_validate;
         mod test_max_size;
+        mod test_min_size;
+        mod test_deserialize_value;
+        #[cfg(feature = "serde_json")]
+        mod test_json;
+        #[cfg(feature = "serde_json")]
+        mod test_json_schema;
+        mod test_compat;
+        mod test_fingerprint;
+        mod test_typescript;
+        mod test_idl;
+        #[cfg(feature = "std")]
+        mod test_rust;
     }
 }
 